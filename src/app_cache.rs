@@ -0,0 +1,215 @@
+use crate::{custom, desktop_dirs};
+use gtk::glib;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// The sorted app order from a previous run, valid while `hash` matches the
+/// current state of the desktop entry directories.
+#[derive(Deserialize, Serialize)]
+pub struct OrderCache {
+    pub hash: u64,
+    pub entries: Vec<OrderEntry>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct OrderEntry {
+    pub key: String,
+    pub sort_key: String,
+}
+
+fn cache_path() -> PathBuf {
+    glib::user_cache_dir()
+        .join("hyperfind")
+        .join("app-order.json")
+}
+
+/// The XDG application directories that exist, with symlinks resolved and
+/// duplicates (several data dirs pointing at one tree) removed, in
+/// precedence order.
+pub fn application_dirs() -> Vec<PathBuf> {
+    let mut candidates = vec![glib::user_data_dir().join("applications")];
+    candidates.extend(
+        glib::system_data_dirs()
+            .into_iter()
+            .map(|dir| dir.join("applications")),
+    );
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        let Ok(real) = fs::canonicalize(&candidate) else {
+            continue;
+        };
+        if real.is_dir() && !dirs.contains(&real) {
+            dirs.push(real);
+        }
+    }
+    dirs
+}
+
+/// Hashes when `path`, or what it links to, was last modified, if it
+/// exists.
+fn hash_mtime(path: &Path, hasher: &mut DefaultHasher) {
+    if let Ok(modified) = fs::metadata(path).and_then(|metadata| metadata.modified()) {
+        modified
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .hash(hasher);
+    }
+}
+
+fn hash_dir(dir: &Path, hasher: &mut DefaultHasher) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        entry.file_name().hash(hasher);
+
+        // Symlink farms: the link itself rarely changes, so hash where it
+        // points and the target's mtime.
+        if file_type.is_symlink()
+            && let Ok(target) = fs::read_link(entry.path())
+        {
+            target.hash(hasher);
+        }
+        hash_mtime(&entry.path(), hasher);
+        if file_type.is_dir() {
+            hash_dir(&entry.path(), hasher);
+        }
+    }
+}
+
+/// Hashes the resolved application directories, the config's and
+/// `--desktop-dir`'s extra ones and the name, symlink target, and mtime of
+/// every file under them, plus `entries.toml` and the locale, which
+/// decides the display names.
+pub fn desktop_dirs_hash() -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for var in ["LC_ALL", "LC_MESSAGES", "LANG", "LANGUAGE"] {
+        env::var_os(var).hash(&mut hasher);
+    }
+    for dir in application_dirs().into_iter().chain(desktop_dirs::dirs()) {
+        dir.hash(&mut hasher);
+        hash_mtime(&dir, &mut hasher);
+        hash_dir(&dir, &mut hasher);
+    }
+    // Custom entries are sorted in with the apps.
+    if let Some(path) = custom::entries_path() {
+        path.hash(&mut hasher);
+        hash_mtime(&path, &mut hasher);
+    }
+    hasher.finish()
+}
+
+pub fn load(hash: u64) -> Option<OrderCache> {
+    parse(&fs::read_to_string(cache_path()).ok()?, hash)
+}
+
+/// The cache in `contents`, unless it was written for another `hash`.
+fn parse(contents: &str, hash: u64) -> Option<OrderCache> {
+    let cache: OrderCache = serde_json::from_str(contents).ok()?;
+    (cache.hash == hash).then_some(cache)
+}
+
+pub fn save(cache: &OrderCache) {
+    let path = cache_path();
+    if let Some(parent) = path.parent()
+        && let Err(err) = fs::create_dir_all(parent)
+    {
+        eprintln!("Failed to create cache dir: {}", err);
+        return;
+    }
+
+    let Ok(payload) = serde_json::to_string(cache) else {
+        return;
+    };
+
+    if let Err(err) = fs::write(path, payload) {
+        eprintln!("Failed to save app order cache: {}", err);
+    }
+}
+
+/// Returns, for each cached entry in order, the index of the matching key in
+/// `keys` and its cached sort key. Gives up if any key is not covered by the
+/// cache, since its position would be unknown.
+pub fn apply(cache: OrderCache, keys: &[&str]) -> Option<Vec<(usize, String)>> {
+    let mut positions: HashMap<&str, usize> = HashMap::with_capacity(keys.len());
+    for (index, key) in keys.iter().enumerate() {
+        if positions.insert(key, index).is_some() {
+            return None;
+        }
+    }
+
+    let order: Vec<(usize, String)> = cache
+        .entries
+        .into_iter()
+        .filter_map(|entry| {
+            positions
+                .remove(entry.key.as_str())
+                .map(|index| (index, entry.sort_key))
+        })
+        .collect();
+
+    positions.is_empty().then_some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(hash: u64, keys: &[&str]) -> OrderCache {
+        OrderCache {
+            hash,
+            entries: keys
+                .iter()
+                .map(|key| OrderEntry {
+                    key: key.to_string(),
+                    sort_key: key.to_lowercase(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_another_hash() {
+        let contents = serde_json::to_string(&cache(1, &["a.desktop"])).unwrap();
+        assert!(parse(&contents, 1).is_some());
+        assert!(parse(&contents, 2).is_none());
+        assert!(parse("not json", 1).is_none());
+    }
+
+    #[test]
+    fn apply_maps_cached_order_onto_keys() {
+        let order = apply(cache(1, &["b", "c", "a"]), &["a", "b", "c"]).unwrap();
+        assert_eq!(
+            order,
+            vec![
+                (1, "b".to_string()),
+                (2, "c".to_string()),
+                (0, "a".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_gives_up_on_uncovered_or_repeated_keys() {
+        assert!(apply(cache(1, &["a", "b"]), &["a", "b", "new"]).is_none());
+        assert!(apply(cache(1, &["a"]), &["a", "a"]).is_none());
+        // A cached key that is gone is simply skipped.
+        assert_eq!(
+            apply(cache(1, &["gone", "a"]), &["a"]).unwrap(),
+            vec![(0, "a".to_string())]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use gio::prelude::*;
+use gtk::glib;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+const GROUP: &str = "Desktop Entry";
+const ENABLED_KEY: &str = "X-GNOME-Autostart-enabled";
+
+fn dir() -> PathBuf {
+    glib::user_config_dir().join("autostart")
+}
+
+/// The autostart file for the app with usage key `key`: its own desktop
+/// id, or for an entry without one a `hyperfind-` name made from the key.
+pub fn file_name(key: &str) -> String {
+    if key.ends_with(".desktop") && !key.contains('/') {
+        return key.to_string();
+    }
+    let slug: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("hyperfind-{}.desktop", slug)
+}
+
+fn enabled(path: &PathBuf) -> bool {
+    let entry = glib::KeyFile::new();
+    if entry
+        .load_from_file(path, glib::KeyFileFlags::NONE)
+        .is_err()
+    {
+        return false;
+    }
+    !entry.boolean(GROUP, "Hidden").unwrap_or(false)
+        && entry.boolean(GROUP, ENABLED_KEY).unwrap_or(true)
+}
+
+/// File names of the user's autostart entries that are switched on.
+pub fn load() -> HashSet<String> {
+    let Ok(entries) = fs::read_dir(dir()) else {
+        return HashSet::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "desktop") && enabled(path))
+        .filter_map(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+        .collect()
+}
+
+/// Starts the app at login: a copy of its desktop file, or one written
+/// from its name, command and icon when it has none, marked enabled.
+pub fn enable(key: &str, app: &gio::AppInfo) -> Result<(), String> {
+    let entry = glib::KeyFile::new();
+    let source = app
+        .downcast_ref::<gio::DesktopAppInfo>()
+        .and_then(|desktop| desktop.filename());
+    if let Some(source) = source {
+        entry
+            .load_from_file(
+                &source,
+                glib::KeyFileFlags::KEEP_COMMENTS | glib::KeyFileFlags::KEEP_TRANSLATIONS,
+            )
+            .map_err(|err| format!("Couldn't read {}: {}", source.display(), err))?;
+    } else {
+        let Some(command) = app.commandline() else {
+            return Err(format!("{} has no command to start", app.display_name()));
+        };
+        entry.set_string(GROUP, "Type", "Application");
+        entry.set_string(GROUP, "Name", &app.display_name());
+        entry.set_string(GROUP, "Exec", &command.to_string_lossy());
+        if let Some(icon) = app.icon().and_then(|icon| IconExt::to_string(&icon)) {
+            entry.set_string(GROUP, "Icon", &icon);
+        }
+    }
+    // A copy of a hidden entry would stay hidden.
+    let _ = entry.remove_key(GROUP, "Hidden");
+    entry.set_boolean(GROUP, ENABLED_KEY, true);
+
+    let dir = dir();
+    fs::create_dir_all(&dir)
+        .map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    let path = dir.join(file_name(key));
+    entry
+        .save_to_file(&path)
+        .map_err(|err| format!("Couldn't write {}: {}", path.display(), err))
+}
+
+/// Stops the app starting at login by removing its autostart file.
+pub fn disable(key: &str) -> Result<(), String> {
+    let path = dir().join(file_name(key));
+    fs::remove_file(&path).map_err(|err| format!("Couldn't remove {}: {}", path.display(), err))
+}
@@ -0,0 +1,63 @@
+use crate::config;
+use std::collections::BTreeMap;
+
+/// A keyword that searches one site for the rest of the query.
+pub struct Bang {
+    pub keyword: String,
+    /// What the row calls the site.
+    pub name: String,
+    /// The search URL, with `{}` where the query goes.
+    pub template: String,
+}
+
+/// `example.com` for `https://www.example.com/search?q={}`, naming a bang
+/// configured without a name.
+fn host_name(template: &str) -> String {
+    let rest = template
+        .split_once("://")
+        .map_or(template, |(_, rest)| rest);
+    let host = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    host.strip_prefix("www.").unwrap_or(host).to_string()
+}
+
+/// The configured bangs, reporting and leaving out those that can't work.
+pub fn load(configured: BTreeMap<String, config::Bang>) -> Vec<Bang> {
+    let mut bangs = Vec::new();
+    for (keyword, bang) in configured {
+        let (name, template) = match bang {
+            config::Bang::Url(url) => (host_name(&url), url),
+            config::Bang::Named { name, url } => (name, url),
+        };
+        if keyword.is_empty() || keyword.contains(char::is_whitespace) {
+            eprintln!("Ignoring bang '{}': keywords can't contain spaces", keyword);
+            continue;
+        }
+        if !template.contains("{}") {
+            eprintln!(
+                "Ignoring bang '{}': its URL has no {{}} for the search",
+                keyword
+            );
+            continue;
+        }
+        bangs.push(Bang {
+            keyword,
+            name,
+            template,
+        });
+    }
+    bangs
+}
+
+/// The bang a query starts with and what it searches for. The keyword
+/// matches regardless of case; the bool says whether it was typed exactly.
+pub fn parse<'a>(bangs: &'a [Bang], query: &'a str) -> Option<(&'a Bang, &'a str, bool)> {
+    let (keyword, rest) = query.trim_start().split_once(' ')?;
+    let rest = rest.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    let bang = bangs
+        .iter()
+        .find(|bang| bang.keyword.eq_ignore_ascii_case(keyword))?;
+    Some((bang, rest, bang.keyword == keyword))
+}
@@ -0,0 +1,174 @@
+use serde_json::Value;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bookmarks past this many, across all browsers, aren't searched.
+const MAX_INDEXED: usize = 1000;
+
+/// Where Chromium-based browsers keep their bookmarks, under `~/.config`.
+const CHROMIUM_FILES: &[&str] = &[
+    "chromium/Default/Bookmarks",
+    "google-chrome/Default/Bookmarks",
+    "BraveSoftware/Brave-Browser/Default/Bookmarks",
+    "vivaldi/Default/Bookmarks",
+];
+
+#[derive(Clone)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+}
+
+impl Bookmark {
+    /// Opened bookmarks are recorded under `bm:<url>`.
+    pub fn usage_key(&self) -> String {
+        format!("bm:{}", self.url)
+    }
+}
+
+fn home() -> Option<PathBuf> {
+    env::var_os("HOME").map(PathBuf::from)
+}
+
+/// Collects the bookmarks in a Chromium `Bookmarks` node and the folders
+/// below it.
+fn collect_chromium(node: &Value, bookmarks: &mut Vec<Bookmark>) {
+    match node.get("type").and_then(Value::as_str) {
+        Some("url") => {
+            let (Some(title), Some(url)) = (
+                node.get("name").and_then(Value::as_str),
+                node.get("url").and_then(Value::as_str),
+            ) else {
+                return;
+            };
+            bookmarks.push(Bookmark {
+                title: title.to_string(),
+                url: url.to_string(),
+            });
+        }
+        Some("folder") => {
+            let Some(children) = node.get("children").and_then(Value::as_array) else {
+                return;
+            };
+            for child in children {
+                collect_chromium(child, bookmarks);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn parse_chromium(contents: &str) -> Vec<Bookmark> {
+    let mut bookmarks = Vec::new();
+    let Ok(root) = serde_json::from_str::<Value>(contents) else {
+        return bookmarks;
+    };
+    let Some(roots) = root.get("roots").and_then(Value::as_object) else {
+        return bookmarks;
+    };
+    for node in roots.values() {
+        collect_chromium(node, &mut bookmarks);
+    }
+    bookmarks
+}
+
+fn load_chromium(config: &Path) -> Vec<Bookmark> {
+    CHROMIUM_FILES
+        .iter()
+        .filter_map(|file| fs::read_to_string(config.join(file)).ok())
+        .flat_map(|contents| parse_chromium(&contents))
+        .collect()
+}
+
+/// Every Firefox profile directory that has a bookmarks database.
+#[cfg(feature = "sqlite")]
+fn firefox_databases(home: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(home.join(".mozilla").join("firefox")) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path().join("places.sqlite"))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Firefox holds a lock on `places.sqlite` while it runs, so the database
+/// (and its write-ahead log, which has the newest changes) is copied to a
+/// temporary file and read from there.
+#[cfg(feature = "sqlite")]
+fn load_firefox_database(database: &Path) -> rusqlite::Result<Vec<Bookmark>> {
+    use rusqlite::{Connection, params};
+
+    let copy = env::temp_dir().join(format!("hyperfind-places-{}.sqlite", std::process::id()));
+    let wal = |path: &Path| {
+        let mut name = path.as_os_str().to_owned();
+        name.push("-wal");
+        PathBuf::from(name)
+    };
+    if let Err(err) = fs::copy(database, &copy) {
+        eprintln!("Failed to copy {}: {}", database.display(), err);
+        return Ok(Vec::new());
+    }
+    let _ = fs::copy(wal(database), wal(&copy));
+
+    let result = (|| {
+        let connection = Connection::open(&copy)?;
+        let mut statement = connection.prepare(
+            "SELECT b.title, p.url FROM moz_bookmarks b
+             JOIN moz_places p ON p.id = b.fk
+             WHERE b.type = 1 AND b.title IS NOT NULL
+             ORDER BY b.lastModified DESC LIMIT ?1",
+        )?;
+        let rows = statement.query_map(params![MAX_INDEXED as i64], |row| {
+            Ok(Bookmark {
+                title: row.get::<_, String>(0)?,
+                url: row.get::<_, String>(1)?,
+            })
+        })?;
+        rows.collect()
+    })();
+    let _ = fs::remove_file(&copy);
+    let _ = fs::remove_file(wal(&copy));
+    result
+}
+
+#[cfg(feature = "sqlite")]
+fn load_firefox(home: &Path) -> Vec<Bookmark> {
+    let mut bookmarks = Vec::new();
+    for database in firefox_databases(home) {
+        match load_firefox_database(&database) {
+            Ok(found) => bookmarks.extend(found),
+            Err(err) => eprintln!("Failed to read {}: {}", database.display(), err),
+        }
+    }
+    bookmarks
+}
+
+/// Firefox bookmarks are kept in SQLite, so they need the `sqlite` feature.
+#[cfg(not(feature = "sqlite"))]
+fn load_firefox(_home: &Path) -> Vec<Bookmark> {
+    Vec::new()
+}
+
+/// Bookmarks from Firefox and Chromium-based browsers, each URL once,
+/// skipping `javascript:` bookmarklets and other non-web links.
+pub fn load() -> Vec<Bookmark> {
+    let Some(home) = home() else {
+        return Vec::new();
+    };
+
+    let mut seen = HashSet::new();
+    load_firefox(&home)
+        .into_iter()
+        .chain(load_chromium(&home.join(".config")))
+        .filter(|bookmark| {
+            bookmark.url.starts_with("http://") || bookmark.url.starts_with("https://")
+        })
+        .filter(|bookmark| !bookmark.title.trim().is_empty())
+        .filter(|bookmark| seen.insert(bookmark.url.clone()))
+        .take(MAX_INDEXED)
+        .collect()
+}
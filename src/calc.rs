@@ -0,0 +1,184 @@
+/// Typing `= expr` always evaluates `expr`; a bare expression only shows a
+/// result when it parses and contains an operator.
+pub const PREFIX: &str = "=";
+
+/// A recursive-descent evaluator for `+ - * / % ^`, parentheses and
+/// decimals. `^` binds tightest and is right-associative; unary minus
+/// binds looser than `^`, so `-2^2` is -4.
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_spaces(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_spaces();
+        self.chars.peek().copied()
+    }
+
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        loop {
+            match self.peek() {
+                Some('+') => {
+                    self.chars.next();
+                    value += self.term()?;
+                }
+                Some('-') => {
+                    self.chars.next();
+                    value -= self.term()?;
+                }
+                _ => return Some(value),
+            }
+        }
+    }
+
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.unary()?;
+        loop {
+            match self.peek() {
+                Some('*') => {
+                    self.chars.next();
+                    value *= self.unary()?;
+                }
+                Some('/') => {
+                    self.chars.next();
+                    value /= self.unary()?;
+                }
+                Some('%') => {
+                    self.chars.next();
+                    value %= self.unary()?;
+                }
+                _ => return Some(value),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Option<f64> {
+        match self.peek() {
+            Some('-') => {
+                self.chars.next();
+                Some(-self.unary()?)
+            }
+            Some('+') => {
+                self.chars.next();
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Option<f64> {
+        let base = self.atom()?;
+        if self.peek() == Some('^') {
+            self.chars.next();
+            return Some(base.powf(self.unary()?));
+        }
+        Some(base)
+    }
+
+    fn atom(&mut self) -> Option<f64> {
+        if self.peek()? == '(' {
+            self.chars.next();
+            let value = self.expression()?;
+            if self.peek()? != ')' {
+                return None;
+            }
+            self.chars.next();
+            return Some(value);
+        }
+
+        let mut number = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+            number.push(c);
+        }
+        number.parse().ok()
+    }
+}
+
+/// Evaluates `input`, or returns None unless all of it is a well-formed
+/// expression with a finite result.
+pub fn evaluate(input: &str) -> Option<f64> {
+    let mut parser = Parser {
+        chars: input.chars().peekable(),
+    };
+    let value = parser.expression()?;
+    if parser.peek().is_some() || !value.is_finite() {
+        return None;
+    }
+    Some(value)
+}
+
+/// The expression a query asks for: everything after a leading `=`, or the
+/// whole query when it looks like arithmetic rather than a name.
+pub fn expression(query: &str) -> Option<&str> {
+    let query = query.trim();
+    if let Some(expression) = query.strip_prefix(PREFIX) {
+        return Some(expression.trim());
+    }
+    let has_operator = query
+        .char_indices()
+        .any(|(index, c)| "+-*/%^".contains(c) && index > 0);
+    (has_operator && evaluate(query).is_some()).then_some(query)
+}
+
+/// Whole numbers without a trailing `.0`, everything else to at most 10
+/// decimal places with trailing zeros dropped.
+pub fn format(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        return format!("{}", value as i64);
+    }
+    let formatted = format!("{:.10}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_with_the_usual_precedence() {
+        assert_eq!(evaluate("12*37+5"), Some(449.0));
+        assert_eq!(evaluate("(1 + 2) * 3"), Some(9.0));
+        assert_eq!(evaluate("7 % 4 + 1.5"), Some(4.5));
+        assert_eq!(evaluate("10 - 4 - 3"), Some(3.0));
+        assert_eq!(evaluate("2^3^2"), Some(512.0));
+        assert_eq!(evaluate("-2^2"), Some(-4.0));
+        assert_eq!(evaluate("2^-1"), Some(0.5));
+    }
+
+    #[test]
+    fn rejects_partial_and_non_finite_expressions() {
+        assert_eq!(evaluate("12*"), None);
+        assert_eq!(evaluate("(1 + 2"), None);
+        assert_eq!(evaluate("1 2"), None);
+        assert_eq!(evaluate("1..2"), None);
+        assert_eq!(evaluate("1/0"), None);
+        assert_eq!(evaluate(""), None);
+    }
+
+    #[test]
+    fn only_arithmetic_looking_queries_are_expressions() {
+        assert_eq!(expression("= 12*37+5"), Some("12*37+5"));
+        assert_eq!(expression("=12*"), Some("12*"));
+        assert_eq!(expression(" 3 * 4 "), Some("3 * 4"));
+        assert_eq!(expression("42"), None);
+        assert_eq!(expression("-5"), None);
+        assert_eq!(expression("gnome-terminal"), None);
+    }
+
+    #[test]
+    fn formats_whole_numbers_without_a_fraction() {
+        assert_eq!(format(449.0), "449");
+        assert_eq!(format(-4.0), "-4");
+        assert_eq!(format(0.5), "0.5");
+        assert_eq!(format(1.0 / 3.0), "0.3333333333");
+        assert_eq!(format(1e20), "100000000000000000000");
+    }
+}
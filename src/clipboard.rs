@@ -0,0 +1,267 @@
+use crate::{matching, profile};
+use gtk::{gdk, glib};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+/// Queries starting with this list the clipboard history.
+pub const PREFIX: &str = "clip ";
+
+/// `clip clear` offers to forget every entry.
+pub const CLEAR: &str = "clear";
+
+/// Rows show this many characters of an entry at most; activating one
+/// still copies all of it.
+const PREVIEW_CHARS: usize = 80;
+
+/// Offered by password managers such as KeePassXC alongside a copied
+/// secret, so clipboard histories leave it out.
+pub const PASSWORD_HINT: &str = "x-kde-passwordManagerHint";
+
+/// Text copied while `--daemon` runs, most recent first. Empty and not
+/// watching in every other process.
+#[derive(Default)]
+pub struct History {
+    entries: Vec<String>,
+    limit: usize,
+    /// The gpg key the history is saved for, or None to keep it in memory
+    /// only.
+    recipient: Option<String>,
+    watching: bool,
+    /// Set while hyperfind itself copies a password, which mustn't be
+    /// kept.
+    paused: bool,
+}
+
+impl History {
+    /// Starts keeping up to `limit` entries, reading back the saved ones
+    /// when there is a key to decrypt them with.
+    pub fn start(&mut self, limit: usize, recipient: Option<String>) {
+        if recipient.is_some() {
+            self.entries = load();
+            self.entries.truncate(limit);
+        }
+        self.limit = limit;
+        self.recipient = recipient;
+        self.watching = true;
+    }
+
+    pub fn watching(&self) -> bool {
+        self.watching
+    }
+
+    /// Stops recording until `resume`, for copying a password.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Puts `text` first, dropping an earlier copy of it and the oldest
+    /// entry past the limit. Blank text is ignored, as is anything while
+    /// paused.
+    pub fn push(&mut self, text: &str) {
+        if self.paused
+            || text.trim().is_empty()
+            || self.entries.first().is_some_and(|first| first == text)
+        {
+            return;
+        }
+        self.entries.retain(|entry| entry != text);
+        self.entries.insert(0, text.to_string());
+        self.entries.truncate(self.limit);
+        self.save();
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Some(recipient) = &self.recipient {
+            save_later(recipient.clone(), self.entries.clone());
+        }
+    }
+}
+
+/// History entries matching `filter` best first, or all of them newest
+/// first while it's empty.
+pub fn search<'a>(entries: &'a [String], filter: &str) -> Vec<&'a String> {
+    let mut scored: Vec<(i64, usize, &String)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| Some((matching::score(entry, filter)?, index, entry)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, entry)| entry).collect()
+}
+
+/// Puts `text` on the clipboard and hands it to a clipboard manager, if
+/// any, so it outlives us.
+pub fn copy(text: &str) {
+    let clipboard = gtk::Clipboard::get(&gdk::SELECTION_CLIPBOARD);
+    clipboard.set_text(text);
+    clipboard.store();
+}
+
+/// An entry as its row shows it: whitespace runs, line breaks included,
+/// collapsed to one space and the rest cut off after `PREVIEW_CHARS`.
+pub fn preview(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= PREVIEW_CHARS {
+        return collapsed;
+    }
+    let mut cut: String = collapsed.chars().take(PREVIEW_CHARS - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Next to the usage data, and only ever written encrypted.
+fn history_path() -> PathBuf {
+    profile::dir(glib::user_data_dir().join("hyperfind")).join("clipboard.gpg")
+}
+
+/// The saved entries, or none if there's no file or gpg can't decrypt it.
+fn load() -> Vec<String> {
+    let path = history_path();
+    if !path.exists() {
+        return Vec::new();
+    }
+    let output = match Command::new("gpg")
+        .args(["--batch", "--quiet", "--decrypt", "--"])
+        .arg(&path)
+        .stderr(Stdio::null())
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(_) => {
+            eprintln!("Couldn't decrypt {}", path.display());
+            return Vec::new();
+        }
+        Err(err) => {
+            eprintln!("Couldn't run gpg: {}", err);
+            return Vec::new();
+        }
+    };
+    serde_json::from_slice(&output.stdout).unwrap_or_else(|err| {
+        eprintln!("Ignoring {}: {}", path.display(), err);
+        Vec::new()
+    })
+}
+
+/// Bumped for every save, so a write that waited behind a newer one is
+/// skipped instead of putting older entries back.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static WRITING: Mutex<()> = Mutex::new(());
+
+/// Encrypts `entries` for `recipient` and replaces the file with them off
+/// the main thread.
+fn save_later(recipient: String, entries: Vec<String>) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    thread::spawn(move || {
+        let _writing = WRITING.lock();
+        if GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if let Err(err) = save(&recipient, &entries) {
+            eprintln!("Failed to save the clipboard history: {}", err);
+        }
+    });
+}
+
+fn save(recipient: &str, entries: &[String]) -> Result<(), String> {
+    let path = history_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let json = serde_json::to_vec(entries).map_err(|err| err.to_string())?;
+    let mut child = Command::new("gpg")
+        .args(["--batch", "--yes", "--quiet", "--encrypt", "--recipient"])
+        .arg(recipient)
+        .arg("--output")
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("couldn't run gpg: {}", err))?;
+    child
+        .stdin
+        .take()
+        .ok_or("no stdin")?
+        .write_all(&json)
+        .map_err(|err| err.to_string())?;
+    let output = child.wait_with_output().map_err(|err| err.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(limit: usize) -> History {
+        History {
+            limit,
+            watching: true,
+            ..History::default()
+        }
+    }
+
+    #[test]
+    fn copying_again_moves_an_entry_to_the_top() {
+        let mut history = history(10);
+        for text in ["one", "two", "one", "one", "   "] {
+            history.push(text);
+        }
+        assert_eq!(history.entries(), ["one", "two"]);
+    }
+
+    #[test]
+    fn the_oldest_entries_fall_off_past_the_limit() {
+        let mut history = history(3);
+        for text in ["a", "b", "c", "d", "e"] {
+            history.push(text);
+        }
+        assert_eq!(history.entries(), ["e", "d", "c"]);
+    }
+
+    #[test]
+    fn nothing_is_recorded_while_paused() {
+        let mut history = history(10);
+        history.push("before");
+        history.pause();
+        history.push("hunter2");
+        history.resume();
+        history.push("after");
+        assert_eq!(history.entries(), ["after", "before"]);
+    }
+
+    #[test]
+    fn previews_collapse_whitespace_and_cut_long_entries() {
+        assert_eq!(preview("  two\n\tlines  here "), "two lines here");
+        let exact = "x".repeat(PREVIEW_CHARS);
+        assert_eq!(preview(&exact), exact);
+        let long = "é".repeat(PREVIEW_CHARS + 5);
+        let cut = preview(&long);
+        assert_eq!(cut.chars().count(), PREVIEW_CHARS);
+        assert!(cut.ends_with("é…"));
+    }
+}
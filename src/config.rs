@@ -0,0 +1,305 @@
+use crate::placement::{Monitor, Position};
+use crate::profile;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Settings read from `~/.config/hyperfind/config.toml`. Every key is
+/// optional; missing keys keep their defaults.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Subtracted from the score of apps meant for another desktop
+    /// environment. 0 disables the penalty.
+    pub foreign_desktop_penalty: i64,
+    /// Usage for apps that are no longer installed is dropped at startup
+    /// once it has gone unused this many days.
+    pub usage_prune_days: u64,
+    pub usage_backend: UsageBackend,
+    /// Favour apps usually launched at the current time of day.
+    pub contextual_ranking: bool,
+    /// Days over which a launch fades from recent to stale in the ranking.
+    pub recency_window_days: u64,
+    /// Runs `>command;` queries, SSH sessions and `Terminal=true` apps;
+    /// started as `<terminal> sh -c <command>`. Unset tries the usual
+    /// terminals in turn.
+    pub terminal: Option<String>,
+    /// Start each launched app in a systemd user scope of its own, as
+    /// desktop shells do.
+    pub systemd_scope: bool,
+    /// Runs the command after it as root for `Ctrl+Shift+Enter`.
+    pub elevate_command: String,
+    /// Quit once `Ctrl+C` or `Ctrl+Shift+C` has copied an app's command or
+    /// desktop file.
+    pub quit_after_copy: bool,
+    /// Bring an app's open window to the front instead of launching it
+    /// again. X11 only.
+    pub activate_running: bool,
+    /// How long `--wait-for-startup` watches a launched app before
+    /// trusting that it started.
+    pub startup_wait_secs: u64,
+    /// Always show the Alt+number next to the first nine apps, not only
+    /// while Alt is held.
+    pub number_hints: bool,
+    /// Moving past the last result selects the first, and past the first
+    /// the last.
+    pub wrap_selection: bool,
+    /// The first Escape clears the search and only the next one closes.
+    pub escape_clears: bool,
+    /// Close when another window takes focus or the keyboard grab, or on a
+    /// click outside the launcher, which grabs the pointer for it.
+    pub close_on_focus_loss: bool,
+    /// Show each app's description under its name.
+    pub descriptions: bool,
+    /// Pixels square for row icons; 0 shows none.
+    pub icon_size: i32,
+    /// Tighter rows and no descriptions, for small screens.
+    pub compact: bool,
+    pub layout: Layout,
+    /// Apps on each line with `layout = "grid"`.
+    pub grid_columns: usize,
+    /// The window fits its height to the results, up to this many pixels.
+    pub max_height: i32,
+    /// Where on its monitor the window opens, moved down `y_offset`
+    /// pixels, and which monitor that is.
+    pub position: Position,
+    pub y_offset: i32,
+    pub monitor: Monitor,
+    /// Set for apps started on the dedicated GPU, instead of asking
+    /// switcheroo-control or using `DRI_PRIME=1`.
+    pub dedicated_gpu_env: Option<BTreeMap<String, String>>,
+    /// Environment variables set for one app's launches, by desktop id.
+    pub app_env: AppEnv,
+    /// Also search executables on `$PATH`, like `--path-bins`.
+    pub path_bins: bool,
+    /// Search files from GTK's recently used list.
+    pub recent_files: bool,
+    /// Also list a few recent files when the search is empty.
+    pub recent_files_section: bool,
+    /// Extra directories of `.desktop` files, each overriding the ones
+    /// before it (and the system menu) for entries with the same name.
+    pub desktop_dirs: Vec<PathBuf>,
+    /// Runs `find ` searches, with the file name as its last argument.
+    pub locate_command: Vec<String>,
+    /// List GNOME Settings panels or KDE settings modules as apps.
+    pub settings_panels: bool,
+    /// Search Firefox and Chromium bookmarks.
+    pub bookmarks: bool,
+    /// Directories whose subdirectories are searched as projects.
+    pub project_roots: Vec<PathBuf>,
+    /// Project directories listed one by one, outside any root.
+    pub projects: Vec<PathBuf>,
+    /// Opens a project, with `{}` where its path goes.
+    pub project_command: String,
+    /// List password-store entries after typing `pass `.
+    pub pass: bool,
+    /// Offer hosts from `~/.ssh/config` after typing `ssh `.
+    pub ssh_hosts: bool,
+    /// Offer hosts from `~/.ssh/known_hosts` as well.
+    pub ssh_known_hosts: bool,
+    /// Offer Lock, Log out, Suspend, Reboot and Shut down in searches.
+    pub power_menu: bool,
+    /// Commands to use instead of the loginctl/systemctl defaults, keyed
+    /// by `lock`, `logout`, `suspend`, `reboot` or `shutdown`.
+    pub power_commands: HashMap<String, String>,
+    /// Run with a picked emoji as its last argument to type it into the
+    /// focused window, e.g. `xdotool type --` or `wtype`. Unset only copies
+    /// it.
+    pub emoji_type_command: Option<String>,
+    /// Offer a web search when a query matches nothing.
+    pub web_search: bool,
+    /// The search engine, with `{}` where the query goes.
+    pub web_search_url: String,
+    /// Site searches by keyword: `yt rust` searches the `yt` one for
+    /// `rust`.
+    pub bangs: BTreeMap<String, Bang>,
+    /// Text copied by name after typing `snip `; `{date}` and `{time}`
+    /// are filled in when it's copied.
+    pub snippets: BTreeMap<String, String>,
+    /// Keep text copied while `hyperfind --daemon` runs, listed after
+    /// typing `clip `.
+    pub clipboard_history: bool,
+    /// How many copies the history keeps.
+    pub clipboard_history_size: usize,
+    /// Also save the history, encrypted with gpg for this key, so it
+    /// survives restarting the daemon. Unset keeps it in memory only.
+    pub clipboard_history_gpg_id: Option<String>,
+    /// What to do with an app installed more than once, e.g. natively and
+    /// as a flatpak.
+    pub duplicate_apps: DuplicateApps,
+    /// How many apps Frequently Used lists at most. 0 hides the section.
+    pub frequent_size: usize,
+    /// Launches an app needs before it is listed under Frequently Used.
+    pub frequent_min_count: u64,
+}
+
+/// Variable names and values for each desktop id they're set for.
+pub type AppEnv = BTreeMap<String, BTreeMap<String, String>>;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageBackend {
+    Json,
+    /// Needs hyperfind built with the `sqlite` feature.
+    Sqlite,
+}
+
+/// How the results are shown.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    /// A row per result.
+    List,
+    /// Apps as large icons, several to a line.
+    Grid,
+    /// One line across the top of the screen, dmenu style.
+    Bar,
+}
+
+/// A `[bangs]` entry: the URL with `{}` where the search goes, or a table
+/// naming the site as well.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum Bang {
+    Url(String),
+    Named { name: String, url: String },
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateApps {
+    /// List each, with "(Flatpak)" or "(Snap)" after the name.
+    Badge,
+    /// List only the one launched most.
+    Collapse,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            foreign_desktop_penalty: 300,
+            usage_prune_days: 90,
+            usage_backend: UsageBackend::Json,
+            contextual_ranking: false,
+            recency_window_days: 90,
+            terminal: None,
+            systemd_scope: false,
+            elevate_command: "pkexec".to_string(),
+            quit_after_copy: true,
+            activate_running: true,
+            startup_wait_secs: 5,
+            number_hints: false,
+            wrap_selection: true,
+            escape_clears: true,
+            close_on_focus_loss: true,
+            descriptions: true,
+            icon_size: 20,
+            compact: false,
+            layout: Layout::List,
+            grid_columns: 5,
+            max_height: 480,
+            position: Position::Center,
+            y_offset: 0,
+            monitor: Monitor::Cursor,
+            dedicated_gpu_env: None,
+            app_env: AppEnv::new(),
+            path_bins: false,
+            recent_files: true,
+            recent_files_section: false,
+            desktop_dirs: Vec::new(),
+            locate_command: vec![
+                "plocate".to_string(),
+                "--limit".to_string(),
+                "50".to_string(),
+            ],
+            settings_panels: true,
+            bookmarks: false,
+            project_roots: Vec::new(),
+            projects: Vec::new(),
+            project_command: "xdg-open {}".to_string(),
+            pass: false,
+            ssh_hosts: true,
+            ssh_known_hosts: false,
+            power_menu: true,
+            power_commands: HashMap::new(),
+            emoji_type_command: None,
+            web_search: true,
+            web_search_url: "https://duckduckgo.com/?q={}".to_string(),
+            bangs: BTreeMap::new(),
+            snippets: BTreeMap::new(),
+            clipboard_history: false,
+            clipboard_history_size: 50,
+            clipboard_history_gpg_id: None,
+            duplicate_apps: DuplicateApps::Badge,
+            frequent_size: 5,
+            frequent_min_count: 1,
+        }
+    }
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("hyperfind"))
+}
+
+/// A profile's own `config.toml` if it has one, otherwise the shared one.
+fn config_path() -> Option<PathBuf> {
+    let dir = config_dir()?;
+    let path = profile::dir(dir.clone()).join("config.toml");
+    if path.exists() {
+        Some(path)
+    } else {
+        Some(dir.join("config.toml"))
+    }
+}
+
+pub fn load() -> Config {
+    load_reporting().0
+}
+
+/// The config, along with what was wrong with the file, for the launcher
+/// to show: why it was ignored, or the settings in it that were.
+pub fn load_reporting() -> (Config, Vec<String>) {
+    let Some(path) = config_path() else {
+        return (Config::default(), Vec::new());
+    };
+
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return (Config::default(), Vec::new());
+    };
+
+    let (config, warnings) = match toml::from_str::<Config>(&contents) {
+        Ok(mut config) => {
+            let warnings = drop_invalid_env(&mut config.app_env);
+            (config, warnings)
+        }
+        Err(err) => (
+            Config::default(),
+            vec![format!("Ignoring {}: {}", path.display(), err)],
+        ),
+    };
+    for warning in &warnings {
+        eprintln!("{}", warning);
+    }
+    (config, warnings)
+}
+
+/// Removes variables no environment can hold, an empty name or one with
+/// `=` in it, saying which app each was for.
+fn drop_invalid_env(app_env: &mut AppEnv) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (app, env) in app_env.iter_mut() {
+        env.retain(|name, _| {
+            let valid = !name.is_empty() && !name.contains('=');
+            if !valid {
+                warnings.push(format!(
+                    "Ignoring environment variable {:?} for {}: names can't be empty or contain '='",
+                    name, app
+                ));
+            }
+            valid
+        });
+    }
+    warnings
+}
@@ -0,0 +1,109 @@
+use crate::{AppEntry, config};
+use gio::prelude::*;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// One `[[entry]]` of `entries.toml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomEntry {
+    name: String,
+    exec: String,
+    /// An icon name from the theme, or a path to an image.
+    icon: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    terminal: bool,
+}
+
+/// Entries are checked one by one, so a typo in one doesn't hide the rest.
+#[derive(Deserialize)]
+struct EntriesFile {
+    #[serde(default)]
+    entry: Vec<toml::Table>,
+}
+
+pub fn entries_path() -> Option<PathBuf> {
+    Some(config::config_dir()?.join("entries.toml"))
+}
+
+fn build_entry(entry: CustomEntry) -> Result<AppEntry, String> {
+    if entry.name.trim().is_empty() {
+        return Err("name is empty".to_string());
+    }
+    let flags = if entry.terminal {
+        gio::AppInfoCreateFlags::NEEDS_TERMINAL
+    } else {
+        gio::AppInfoCreateFlags::NONE
+    };
+    let app_info = gio::AppInfo::create_from_commandline(&entry.exec, Some(&entry.name), flags)
+        .map_err(|err| err.to_string())?;
+    let icon = entry.icon.map(|icon| -> gio::Icon {
+        if icon.contains('/') {
+            gio::FileIcon::new(&gio::File::for_path(icon)).upcast()
+        } else {
+            gio::ThemedIcon::new(&icon).upcast()
+        }
+    });
+    Ok(AppEntry {
+        key: format!("custom:{}", entry.name),
+        name: entry.name,
+        untranslated_name: None,
+        sort_key: String::new(),
+        icon,
+        app_info,
+        hidden: false,
+        desktops: Vec::new(),
+        not_desktops: Vec::new(),
+        keywords: entry.keywords,
+        packaging: None,
+        duplicate: false,
+        terminal: entry.terminal,
+        dedicated_gpu: false,
+        autostart: false,
+        running: false,
+        description: None,
+    })
+}
+
+/// Entries from `~/.config/hyperfind/entries.toml`, listed alongside the
+/// installed apps. Each one that can't be used is reported and skipped.
+pub fn load() -> Vec<AppEntry> {
+    let Some(path) = entries_path() else {
+        return Vec::new();
+    };
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let file: EntriesFile = match toml::from_str(&contents) {
+        Ok(file) => file,
+        Err(err) => {
+            eprintln!("Ignoring {}: {}", path.display(), err);
+            return Vec::new();
+        }
+    };
+
+    file.entry
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, table)| {
+            let label = match table.get("name").and_then(toml::Value::as_str) {
+                Some(name) => format!("entry {} ({})", index + 1, name),
+                None => format!("entry {}", index + 1),
+            };
+            let entry = toml::Value::Table(table)
+                .try_into()
+                .map_err(|err: toml::de::Error| err.to_string())
+                .and_then(build_entry);
+            match entry {
+                Ok(entry) => Some(entry),
+                Err(err) => {
+                    eprintln!("Skipping {} in {}: {}", label, path.display(), err);
+                    None
+                }
+            }
+        })
+        .collect()
+}
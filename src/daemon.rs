@@ -0,0 +1,14 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once `--daemon` has made this the process that stays running.
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Marks the process as the daemon. Returns false if it already was.
+pub fn start() -> bool {
+    !RUNNING.swap(true, Ordering::SeqCst)
+}
+
+/// Whether closing the launcher should keep the process alive.
+pub fn running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
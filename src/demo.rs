@@ -0,0 +1,108 @@
+use crate::{AppEntry, UsageEntry, UsageMap};
+use gio::prelude::*;
+use std::collections::HashMap;
+
+/// Fixed timestamp the demo usage is anchored to, so labels and ordering
+/// never depend on the machine's clock or history.
+const DEMO_EPOCH: u64 = 1_700_000_000;
+
+/// (name, icon name, launch count). Icon names come from the freedesktop
+/// naming spec so every theme has them. Includes the awkward cases on
+/// purpose: duplicate names, a very long name, a missing icon, and
+/// non-ASCII names.
+const DEMO_APPS: &[(&str, Option<&str>, u64)] = &[
+    ("Terminal", Some("utilities-terminal"), 42),
+    ("Terminal", Some("utilities-terminal"), 3),
+    ("Web Browser", Some("web-browser"), 37),
+    ("Files", Some("system-file-manager"), 25),
+    ("Text Editor", Some("accessories-text-editor"), 19),
+    ("Calculator", Some("accessories-calculator"), 11),
+    ("Mail", Some("mail-send"), 8),
+    ("Calendar", Some("x-office-calendar"), 6),
+    ("Settings", Some("preferences-desktop"), 5),
+    ("System Monitor", Some("utilities-system-monitor"), 4),
+    ("Character Map", Some("accessories-character-map"), 2),
+    ("Dictionary", Some("accessories-dictionary"), 1),
+    ("Document Writer", Some("x-office-document"), 0),
+    ("Spreadsheet", Some("x-office-spreadsheet"), 0),
+    ("Presentation", Some("x-office-presentation"), 0),
+    ("Music Player", Some("applications-multimedia"), 0),
+    ("Image Viewer", Some("applications-graphics"), 0),
+    ("Camera", Some("camera-photo"), 0),
+    ("Printers", Some("printer"), 0),
+    ("Network Browser", Some("network-workgroup"), 0),
+    ("Software", Some("system-software-install"), 0),
+    ("Help", Some("help-browser"), 0),
+    ("Games", Some("applications-games"), 0),
+    ("Developer Tools", Some("applications-development"), 0),
+    ("Trash", Some("user-trash"), 0),
+    (
+        "Extremely Long Application Name That Keeps Going Well Past The Edge Of The Window",
+        Some("applications-utilities"),
+        0,
+    ),
+    ("No Icon Tool", None, 0),
+    ("Éditeur d’images", Some("applications-graphics"), 0),
+    ("ターミナル", Some("utilities-terminal"), 0),
+    ("Ωmega Notes", Some("accessories-text-editor"), 0),
+];
+
+pub fn apps() -> Vec<AppEntry> {
+    let mut apps: Vec<AppEntry> = DEMO_APPS
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (name, icon_name, _))| {
+            let app_info = gio::AppInfo::create_from_commandline(
+                "true",
+                Some(name),
+                gio::AppInfoCreateFlags::NONE,
+            )
+            .ok()?;
+            Some(AppEntry {
+                key: format!("demo-{:02}.desktop", index),
+                name: name.to_string(),
+                untranslated_name: None,
+                sort_key: name.to_lowercase(),
+                icon: icon_name.map(|icon_name| gio::ThemedIcon::new(icon_name).upcast()),
+                app_info,
+                hidden: false,
+                desktops: Vec::new(),
+                not_desktops: Vec::new(),
+                keywords: Vec::new(),
+                packaging: None,
+                duplicate: false,
+                terminal: false,
+                dedicated_gpu: false,
+                autostart: false,
+                running: false,
+                description: None,
+            })
+        })
+        .collect();
+
+    apps.sort_by(|a, b| a.sort_key.cmp(&b.sort_key).then_with(|| a.key.cmp(&b.key)));
+    apps
+}
+
+/// The clock the demo is displayed at.
+pub fn now() -> u64 {
+    DEMO_EPOCH
+}
+
+pub fn usage() -> UsageMap {
+    let mut usage = HashMap::new();
+    for (index, (_, _, count)) in DEMO_APPS.iter().enumerate() {
+        if *count == 0 {
+            continue;
+        }
+        usage.insert(
+            format!("demo-{:02}.desktop", index),
+            UsageEntry {
+                count: *count,
+                last_used: DEMO_EPOCH - index as u64 * 3600,
+                ..UsageEntry::default()
+            },
+        );
+    }
+    usage
+}
@@ -0,0 +1,68 @@
+use crate::config;
+use gio::prelude::*;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// `--desktop-dir` values, collected once in `main`.
+static FROM_ARGS: OnceLock<Vec<PathBuf>> = OnceLock::new();
+
+/// Every `--desktop-dir <path>` in `args`, made absolute against the
+/// working directory.
+pub fn from_args(args: &[OsString]) -> Vec<PathBuf> {
+    let current = std::env::current_dir().unwrap_or_default();
+    args.windows(2)
+        .filter(|pair| pair[0] == "--desktop-dir")
+        .map(|pair| current.join(&pair[1]))
+        .collect()
+}
+
+pub fn set(dirs: Vec<PathBuf>) {
+    let _ = FROM_ARGS.set(dirs);
+}
+
+/// The config's `desktop_dirs`, then the `--desktop-dir`s, in order of
+/// increasing precedence.
+pub fn dirs() -> Vec<PathBuf> {
+    let mut dirs = config::load().desktop_dirs;
+    dirs.extend(FROM_ARGS.get().into_iter().flatten().cloned());
+    dirs
+}
+
+fn load_dir(dir: &Path, entries: &mut Vec<(String, gio::AppInfo)>) {
+    let Ok(files) = fs::read_dir(dir) else {
+        eprintln!("Can't read desktop dir {}", dir.display());
+        return;
+    };
+    let mut files: Vec<PathBuf> = files
+        .flatten()
+        .map(|file| file.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "desktop"))
+        .collect();
+    files.sort();
+    for path in files {
+        let Some(id) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        let Some(info) = gio::DesktopAppInfo::from_filename(&path) else {
+            eprintln!("Skipping {}: not a valid desktop entry", path.display());
+            continue;
+        };
+        entries.retain(|(other, _)| *other != id);
+        entries.push((id, info.upcast()));
+    }
+}
+
+/// The entries in the extra directories with their desktop ids. An id
+/// found in several directories comes from the last of them.
+pub fn load() -> Vec<(String, gio::AppInfo)> {
+    let mut entries = Vec::new();
+    for dir in dirs() {
+        load_dir(&dir, &mut entries);
+    }
+    entries
+}
@@ -0,0 +1,61 @@
+use gio::prelude::*;
+use gtk::glib;
+
+/// How long to wait for systemd to create a scope; hyperfind is about to
+/// quit, so the call can't be left running.
+const SCOPE_TIMEOUT_MS: i32 = 1000;
+
+/// Runs in the forked child just before it execs the app. A session of
+/// its own keeps the hangup sent when hyperfind's terminal or session
+/// scope goes away from reaching the app too.
+pub fn child_setup() {
+    unsafe {
+        libc::setsid();
+    }
+}
+
+/// `app-hyperfind-<key>-<pid>.scope`, with anything a unit name can't
+/// hold replaced by `_`.
+fn scope_name(key: &str, pid: i32) -> String {
+    let key: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("app-hyperfind-{}-{}.scope", key, pid)
+}
+
+/// Moves a launched app into a transient scope of the systemd user
+/// instance, the way desktop shells do, so stopping whatever started
+/// hyperfind doesn't stop the app with it. The app is already running, so
+/// failing only gets reported.
+pub fn move_to_scope(key: &str, pid: glib::Pid) {
+    let bus = match gio::bus_get_sync(gio::BusType::Session, gio::Cancellable::NONE) {
+        Ok(bus) => bus,
+        Err(err) => {
+            eprintln!("Can't reach the session bus for a systemd scope: {}", err);
+            return;
+        }
+    };
+    let properties = vec![("PIDs".to_string(), vec![pid.0 as u32].to_variant())];
+    let auxiliary: Vec<(String, Vec<(String, glib::Variant)>)> = Vec::new();
+    let parameters = (scope_name(key, pid.0), "fail", properties, auxiliary).to_variant();
+    if let Err(err) = bus.call_sync(
+        Some("org.freedesktop.systemd1"),
+        "/org/freedesktop/systemd1",
+        "org.freedesktop.systemd1.Manager",
+        "StartTransientUnit",
+        Some(&parameters),
+        None,
+        gio::DBusCallFlags::NONE,
+        SCOPE_TIMEOUT_MS,
+        gio::Cancellable::NONE,
+    ) {
+        eprintln!("Couldn't move {} into a systemd scope: {}", key, err);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::matching;
+use std::cell::Cell;
+use std::ffi::OsString;
+use std::io::{self, BufRead, Write};
+
+/// Lines read from stdin by `--dmenu`, picked from instead of apps.
+pub struct Dmenu {
+    pub lines: Vec<String>,
+    /// Shown in the empty search entry, from `-p`.
+    pub prompt: Option<String>,
+    /// Print the picked line's 0-based index instead of the line, from
+    /// `--index`.
+    pub print_index: bool,
+    /// Set once a line has been printed; hyperfind exits with 1 otherwise.
+    pub picked: Cell<bool>,
+}
+
+/// Reads stdin when `--dmenu` was passed. This has to happen before the
+/// application runs, since a remote instance couldn't see our stdin.
+pub fn from_args(args: &[OsString]) -> Option<Dmenu> {
+    if !args.iter().any(|arg| arg == "--dmenu") {
+        return None;
+    }
+    let lines = io::stdin()
+        .lock()
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.is_empty())
+        .collect();
+    Some(Dmenu {
+        lines,
+        prompt: crate::flag_value(args, "-p"),
+        print_index: args.iter().any(|arg| arg == "--index"),
+        picked: Cell::new(false),
+    })
+}
+
+impl Dmenu {
+    /// The lines matching `query` best first with their positions, or all
+    /// of them in their original order while it's empty.
+    pub fn search(&self, query: &str) -> Vec<(usize, &String)> {
+        let mut scored: Vec<(i64, usize, &String)> = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| Some((matching::score(line, query)?, index, line)))
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        scored
+            .into_iter()
+            .map(|(_, index, line)| (index, line))
+            .collect()
+    }
+
+    /// Prints line `index` (or the index itself) for the calling script.
+    pub fn pick(&self, index: usize) {
+        if self.picked.replace(true) {
+            return;
+        }
+        let mut stdout = io::stdout();
+        let result = if self.print_index {
+            writeln!(stdout, "{}", index)
+        } else {
+            writeln!(stdout, "{}", self.lines[index])
+        };
+        if let Err(err) = result.and_then(|()| stdout.flush()) {
+            eprintln!("Failed to print selection: {}", err);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// How often an elevated launch is checked on while it waits for the
+/// password.
+pub const POLL: Duration = Duration::from_millis(200);
+
+/// What `pkexec` exits with when its dialog is dismissed, and when the
+/// user isn't allowed to authenticate.
+const DISMISSED: i32 = 126;
+const NOT_AUTHORIZED: i32 = 127;
+
+/// Command names in `/proc/<pid>/comm` are cut to this many bytes.
+const COMM_LEN: usize = 15;
+
+fn comm(pid: i32) -> Option<String> {
+    let comm = fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    Some(comm.trim_end().to_string())
+}
+
+fn children(pid: i32) -> Vec<i32> {
+    let Ok(tasks) = fs::read_dir(format!("/proc/{}/task", pid)) else {
+        return Vec::new();
+    };
+    tasks
+        .flatten()
+        .filter_map(|task| fs::read_to_string(task.path().join("children")).ok())
+        .flat_map(|children| {
+            children
+                .split_whitespace()
+                .filter_map(|child| child.parse().ok())
+                .collect::<Vec<i32>>()
+        })
+        .collect()
+}
+
+/// A program's name as `comm` shows it.
+fn short_name(program: &str) -> String {
+    let name = Path::new(program)
+        .file_name()
+        .map_or(program.into(), |name| name.to_string_lossy());
+    name.chars().take(COMM_LEN).collect()
+}
+
+/// Whether the command `program` was asked to run has started: `pkexec`
+/// becomes it once the password is accepted, while `sudo` starts it as a
+/// child. The askpass helper `sudo -A` runs meanwhile doesn't count.
+pub fn started(pid: i32, program: &str) -> bool {
+    let mut helpers = vec![short_name(program)];
+    if let Some(askpass) = std::env::var_os("SUDO_ASKPASS") {
+        helpers.push(short_name(&askpass.to_string_lossy()));
+    }
+    let mut pending = vec![pid];
+    while let Some(pid) = pending.pop() {
+        let Some(name) = comm(pid) else {
+            continue;
+        };
+        if !helpers.contains(&name) {
+            return true;
+        }
+        pending.extend(children(pid));
+    }
+    false
+}
+
+/// Whether a wait status means the command ran and exited cleanly, as a
+/// quick one can before `started` notices it.
+pub fn succeeded(status: i32) -> bool {
+    libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0
+}
+
+/// Why `program` exited before starting the command.
+pub fn failure(program: &str, status: i32) -> String {
+    if !libc::WIFEXITED(status) {
+        return format!("{} was killed", program);
+    }
+    match libc::WEXITSTATUS(status) {
+        DISMISSED => "Authentication was cancelled".to_string(),
+        NOT_AUTHORIZED => "Not authorized to run it as root".to_string(),
+        code => format!("{} exited with status {}", program, code),
+    }
+}
@@ -0,0 +1,1026 @@
+use crate::matching;
+use gtk::glib;
+
+/// Typing `:` followed by a name or keyword searches these.
+pub const PREFIX: &str = ":";
+
+pub struct Emoji {
+    pub emoji: &'static str,
+    /// The CLDR short name.
+    pub name: &'static str,
+    pub keywords: &'static [&'static str],
+}
+
+/// Emoji whose name or a keyword matches `filter`, best first, or the
+/// whole table in its own order while it's empty.
+pub fn search(filter: &str) -> Vec<&'static Emoji> {
+    let mut scored: Vec<(i64, usize, &'static Emoji)> = EMOJI
+        .iter()
+        .enumerate()
+        .filter_map(|(index, emoji)| {
+            let score = std::iter::once(emoji.name)
+                .chain(emoji.keywords.iter().copied())
+                .filter_map(|name| matching::score(name, filter))
+                .max()?;
+            Some((score, index, emoji))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, emoji)| emoji).collect()
+}
+
+/// Types `emoji` into whatever window gets focus once hyperfind has quit,
+/// by running `command` with it as the last argument.
+pub fn type_later(command: &str, emoji: &str) {
+    let quoted = glib::shell_quote(emoji);
+    let script = format!("sleep 0.2; {} {}", command, quoted.to_string_lossy());
+    let quoted_script = glib::shell_quote(&script);
+    let command_line = format!("sh -c {}", quoted_script.to_string_lossy());
+    if let Err(err) = glib::spawn_command_line_async(&command_line) {
+        eprintln!("Failed to run {}: {}", command, err);
+    }
+}
+
+/// A hand-picked set of commonly used emoji rather than the whole CLDR
+/// list. Builds without the `emoji` feature leave the table empty.
+#[cfg(feature = "emoji")]
+pub const EMOJI: &[Emoji] = &[
+    Emoji {
+        emoji: "😀",
+        name: "grinning face",
+        keywords: &["smile", "happy"],
+    },
+    Emoji {
+        emoji: "😃",
+        name: "grinning face with big eyes",
+        keywords: &["smile", "happy"],
+    },
+    Emoji {
+        emoji: "😄",
+        name: "grinning face with smiling eyes",
+        keywords: &["smile", "happy", "laugh"],
+    },
+    Emoji {
+        emoji: "😁",
+        name: "beaming face with smiling eyes",
+        keywords: &["grin", "smile"],
+    },
+    Emoji {
+        emoji: "😆",
+        name: "grinning squinting face",
+        keywords: &["laugh", "happy"],
+    },
+    Emoji {
+        emoji: "😅",
+        name: "grinning face with sweat",
+        keywords: &["relief", "laugh"],
+    },
+    Emoji {
+        emoji: "🤣",
+        name: "rolling on the floor laughing",
+        keywords: &["rofl", "laugh"],
+    },
+    Emoji {
+        emoji: "😂",
+        name: "face with tears of joy",
+        keywords: &["lol", "laugh", "cry"],
+    },
+    Emoji {
+        emoji: "🙂",
+        name: "slightly smiling face",
+        keywords: &["smile"],
+    },
+    Emoji {
+        emoji: "🙃",
+        name: "upside-down face",
+        keywords: &["silly", "sarcasm"],
+    },
+    Emoji {
+        emoji: "😉",
+        name: "winking face",
+        keywords: &["wink"],
+    },
+    Emoji {
+        emoji: "😊",
+        name: "smiling face with smiling eyes",
+        keywords: &["blush", "smile"],
+    },
+    Emoji {
+        emoji: "😇",
+        name: "smiling face with halo",
+        keywords: &["angel", "innocent"],
+    },
+    Emoji {
+        emoji: "🥰",
+        name: "smiling face with hearts",
+        keywords: &["love", "adore"],
+    },
+    Emoji {
+        emoji: "😍",
+        name: "smiling face with heart-eyes",
+        keywords: &["love", "crush"],
+    },
+    Emoji {
+        emoji: "🤩",
+        name: "star-struck",
+        keywords: &["wow", "starry"],
+    },
+    Emoji {
+        emoji: "😘",
+        name: "face blowing a kiss",
+        keywords: &["kiss", "love"],
+    },
+    Emoji {
+        emoji: "😋",
+        name: "face savoring food",
+        keywords: &["yum", "tasty"],
+    },
+    Emoji {
+        emoji: "😛",
+        name: "face with tongue",
+        keywords: &["tongue", "playful"],
+    },
+    Emoji {
+        emoji: "😜",
+        name: "winking face with tongue",
+        keywords: &["joke", "playful"],
+    },
+    Emoji {
+        emoji: "🤪",
+        name: "zany face",
+        keywords: &["crazy", "goofy"],
+    },
+    Emoji {
+        emoji: "🤔",
+        name: "thinking face",
+        keywords: &["think", "hmm"],
+    },
+    Emoji {
+        emoji: "🤨",
+        name: "face with raised eyebrow",
+        keywords: &["skeptical", "doubt"],
+    },
+    Emoji {
+        emoji: "😐",
+        name: "neutral face",
+        keywords: &["meh", "blank"],
+    },
+    Emoji {
+        emoji: "😑",
+        name: "expressionless face",
+        keywords: &["blank", "meh"],
+    },
+    Emoji {
+        emoji: "😶",
+        name: "face without mouth",
+        keywords: &["silent", "quiet"],
+    },
+    Emoji {
+        emoji: "🙄",
+        name: "face with rolling eyes",
+        keywords: &["eyeroll", "whatever"],
+    },
+    Emoji {
+        emoji: "😏",
+        name: "smirking face",
+        keywords: &["smirk", "smug"],
+    },
+    Emoji {
+        emoji: "😬",
+        name: "grimacing face",
+        keywords: &["grimace", "awkward"],
+    },
+    Emoji {
+        emoji: "😌",
+        name: "relieved face",
+        keywords: &["relief", "calm"],
+    },
+    Emoji {
+        emoji: "😔",
+        name: "pensive face",
+        keywords: &["sad", "dejected"],
+    },
+    Emoji {
+        emoji: "😪",
+        name: "sleepy face",
+        keywords: &["tired", "sleep"],
+    },
+    Emoji {
+        emoji: "😴",
+        name: "sleeping face",
+        keywords: &["sleep", "zzz"],
+    },
+    Emoji {
+        emoji: "😷",
+        name: "face with medical mask",
+        keywords: &["sick", "mask"],
+    },
+    Emoji {
+        emoji: "🤒",
+        name: "face with thermometer",
+        keywords: &["sick", "ill"],
+    },
+    Emoji {
+        emoji: "🤢",
+        name: "nauseated face",
+        keywords: &["sick", "gross"],
+    },
+    Emoji {
+        emoji: "🤮",
+        name: "face vomiting",
+        keywords: &["sick", "vomit"],
+    },
+    Emoji {
+        emoji: "🥵",
+        name: "hot face",
+        keywords: &["hot", "heat"],
+    },
+    Emoji {
+        emoji: "🥶",
+        name: "cold face",
+        keywords: &["cold", "freezing"],
+    },
+    Emoji {
+        emoji: "🤯",
+        name: "exploding head",
+        keywords: &["mind blown", "shocked"],
+    },
+    Emoji {
+        emoji: "🥳",
+        name: "partying face",
+        keywords: &["party", "celebrate"],
+    },
+    Emoji {
+        emoji: "😎",
+        name: "smiling face with sunglasses",
+        keywords: &["cool", "sunglasses"],
+    },
+    Emoji {
+        emoji: "🤓",
+        name: "nerd face",
+        keywords: &["nerd", "geek"],
+    },
+    Emoji {
+        emoji: "😕",
+        name: "confused face",
+        keywords: &["confused"],
+    },
+    Emoji {
+        emoji: "😟",
+        name: "worried face",
+        keywords: &["worried"],
+    },
+    Emoji {
+        emoji: "🙁",
+        name: "slightly frowning face",
+        keywords: &["frown", "sad"],
+    },
+    Emoji {
+        emoji: "😮",
+        name: "face with open mouth",
+        keywords: &["surprise", "wow"],
+    },
+    Emoji {
+        emoji: "😲",
+        name: "astonished face",
+        keywords: &["shocked", "surprise"],
+    },
+    Emoji {
+        emoji: "😳",
+        name: "flushed face",
+        keywords: &["embarrassed", "blush"],
+    },
+    Emoji {
+        emoji: "🥺",
+        name: "pleading face",
+        keywords: &["please", "puppy eyes"],
+    },
+    Emoji {
+        emoji: "😢",
+        name: "crying face",
+        keywords: &["cry", "sad", "tear"],
+    },
+    Emoji {
+        emoji: "😭",
+        name: "loudly crying face",
+        keywords: &["sob", "cry", "sad"],
+    },
+    Emoji {
+        emoji: "😱",
+        name: "face screaming in fear",
+        keywords: &["scream", "scared"],
+    },
+    Emoji {
+        emoji: "😤",
+        name: "face with steam from nose",
+        keywords: &["triumph", "frustrated"],
+    },
+    Emoji {
+        emoji: "😡",
+        name: "pouting face",
+        keywords: &["angry", "mad", "rage"],
+    },
+    Emoji {
+        emoji: "😠",
+        name: "angry face",
+        keywords: &["angry", "mad"],
+    },
+    Emoji {
+        emoji: "🤬",
+        name: "face with symbols on mouth",
+        keywords: &["swear", "cursing"],
+    },
+    Emoji {
+        emoji: "💀",
+        name: "skull",
+        keywords: &["dead", "death"],
+    },
+    Emoji {
+        emoji: "💩",
+        name: "pile of poo",
+        keywords: &["poop"],
+    },
+    Emoji {
+        emoji: "🤡",
+        name: "clown face",
+        keywords: &["clown"],
+    },
+    Emoji {
+        emoji: "👻",
+        name: "ghost",
+        keywords: &["halloween", "boo"],
+    },
+    Emoji {
+        emoji: "👽",
+        name: "alien",
+        keywords: &["ufo", "extraterrestrial"],
+    },
+    Emoji {
+        emoji: "🤖",
+        name: "robot",
+        keywords: &["bot", "machine"],
+    },
+    Emoji {
+        emoji: "😺",
+        name: "grinning cat",
+        keywords: &["cat", "smile"],
+    },
+    Emoji {
+        emoji: "🙈",
+        name: "see-no-evil monkey",
+        keywords: &["monkey", "hide"],
+    },
+    Emoji {
+        emoji: "💋",
+        name: "kiss mark",
+        keywords: &["kiss", "lips"],
+    },
+    Emoji {
+        emoji: "❤️",
+        name: "red heart",
+        keywords: &["love", "heart"],
+    },
+    Emoji {
+        emoji: "🧡",
+        name: "orange heart",
+        keywords: &["love", "heart"],
+    },
+    Emoji {
+        emoji: "💛",
+        name: "yellow heart",
+        keywords: &["love", "heart"],
+    },
+    Emoji {
+        emoji: "💚",
+        name: "green heart",
+        keywords: &["love", "heart"],
+    },
+    Emoji {
+        emoji: "💙",
+        name: "blue heart",
+        keywords: &["love", "heart"],
+    },
+    Emoji {
+        emoji: "💜",
+        name: "purple heart",
+        keywords: &["love", "heart"],
+    },
+    Emoji {
+        emoji: "🖤",
+        name: "black heart",
+        keywords: &["love", "heart"],
+    },
+    Emoji {
+        emoji: "💔",
+        name: "broken heart",
+        keywords: &["heartbreak", "sad"],
+    },
+    Emoji {
+        emoji: "💯",
+        name: "hundred points",
+        keywords: &["100", "perfect", "score"],
+    },
+    Emoji {
+        emoji: "💥",
+        name: "collision",
+        keywords: &["boom", "explosion"],
+    },
+    Emoji {
+        emoji: "💫",
+        name: "dizzy",
+        keywords: &["star"],
+    },
+    Emoji {
+        emoji: "💤",
+        name: "zzz",
+        keywords: &["sleep", "snore"],
+    },
+    Emoji {
+        emoji: "👋",
+        name: "waving hand",
+        keywords: &["wave", "hello", "bye"],
+    },
+    Emoji {
+        emoji: "👌",
+        name: "OK hand",
+        keywords: &["ok", "perfect"],
+    },
+    Emoji {
+        emoji: "✌️",
+        name: "victory hand",
+        keywords: &["peace", "v"],
+    },
+    Emoji {
+        emoji: "🤞",
+        name: "crossed fingers",
+        keywords: &["luck", "hope"],
+    },
+    Emoji {
+        emoji: "🤘",
+        name: "sign of the horns",
+        keywords: &["rock", "metal"],
+    },
+    Emoji {
+        emoji: "👈",
+        name: "backhand index pointing left",
+        keywords: &["left", "point"],
+    },
+    Emoji {
+        emoji: "👉",
+        name: "backhand index pointing right",
+        keywords: &["right", "point"],
+    },
+    Emoji {
+        emoji: "👆",
+        name: "backhand index pointing up",
+        keywords: &["up", "point"],
+    },
+    Emoji {
+        emoji: "👇",
+        name: "backhand index pointing down",
+        keywords: &["down", "point"],
+    },
+    Emoji {
+        emoji: "👍",
+        name: "thumbs up",
+        keywords: &["+1", "yes", "like", "approve"],
+    },
+    Emoji {
+        emoji: "👎",
+        name: "thumbs down",
+        keywords: &["-1", "no", "dislike"],
+    },
+    Emoji {
+        emoji: "✊",
+        name: "raised fist",
+        keywords: &["fist", "power"],
+    },
+    Emoji {
+        emoji: "👊",
+        name: "oncoming fist",
+        keywords: &["punch", "fist bump"],
+    },
+    Emoji {
+        emoji: "👏",
+        name: "clapping hands",
+        keywords: &["clap", "applause"],
+    },
+    Emoji {
+        emoji: "🙌",
+        name: "raising hands",
+        keywords: &["hooray", "celebrate"],
+    },
+    Emoji {
+        emoji: "🙏",
+        name: "folded hands",
+        keywords: &["please", "thanks", "pray"],
+    },
+    Emoji {
+        emoji: "💪",
+        name: "flexed biceps",
+        keywords: &["strong", "muscle"],
+    },
+    Emoji {
+        emoji: "👀",
+        name: "eyes",
+        keywords: &["look", "see"],
+    },
+    Emoji {
+        emoji: "🧠",
+        name: "brain",
+        keywords: &["smart", "think"],
+    },
+    Emoji {
+        emoji: "🤷",
+        name: "person shrugging",
+        keywords: &["shrug", "whatever"],
+    },
+    Emoji {
+        emoji: "🤦",
+        name: "person facepalming",
+        keywords: &["facepalm", "ugh"],
+    },
+    Emoji {
+        emoji: "🐶",
+        name: "dog face",
+        keywords: &["dog", "puppy"],
+    },
+    Emoji {
+        emoji: "🐱",
+        name: "cat face",
+        keywords: &["cat", "kitten"],
+    },
+    Emoji {
+        emoji: "🦊",
+        name: "fox",
+        keywords: &["fox"],
+    },
+    Emoji {
+        emoji: "🐻",
+        name: "bear",
+        keywords: &["bear"],
+    },
+    Emoji {
+        emoji: "🐼",
+        name: "panda",
+        keywords: &["panda"],
+    },
+    Emoji {
+        emoji: "🐸",
+        name: "frog",
+        keywords: &["frog"],
+    },
+    Emoji {
+        emoji: "🐧",
+        name: "penguin",
+        keywords: &["linux", "tux", "bird"],
+    },
+    Emoji {
+        emoji: "🦀",
+        name: "crab",
+        keywords: &["rust", "ferris"],
+    },
+    Emoji {
+        emoji: "🐍",
+        name: "snake",
+        keywords: &["python", "serpent"],
+    },
+    Emoji {
+        emoji: "🐛",
+        name: "bug",
+        keywords: &["insect", "debug"],
+    },
+    Emoji {
+        emoji: "🦄",
+        name: "unicorn",
+        keywords: &["magic"],
+    },
+    Emoji {
+        emoji: "🌵",
+        name: "cactus",
+        keywords: &["desert", "plant"],
+    },
+    Emoji {
+        emoji: "🌲",
+        name: "evergreen tree",
+        keywords: &["tree", "forest"],
+    },
+    Emoji {
+        emoji: "🌸",
+        name: "cherry blossom",
+        keywords: &["flower", "spring"],
+    },
+    Emoji {
+        emoji: "🌹",
+        name: "rose",
+        keywords: &["flower", "love"],
+    },
+    Emoji {
+        emoji: "🍀",
+        name: "four leaf clover",
+        keywords: &["luck", "lucky"],
+    },
+    Emoji {
+        emoji: "🍎",
+        name: "red apple",
+        keywords: &["apple", "fruit"],
+    },
+    Emoji {
+        emoji: "🍌",
+        name: "banana",
+        keywords: &["fruit"],
+    },
+    Emoji {
+        emoji: "🍕",
+        name: "pizza",
+        keywords: &["food", "slice"],
+    },
+    Emoji {
+        emoji: "🍔",
+        name: "hamburger",
+        keywords: &["burger", "food"],
+    },
+    Emoji {
+        emoji: "🌮",
+        name: "taco",
+        keywords: &["food", "mexican"],
+    },
+    Emoji {
+        emoji: "🍣",
+        name: "sushi",
+        keywords: &["food", "japanese"],
+    },
+    Emoji {
+        emoji: "🍩",
+        name: "doughnut",
+        keywords: &["donut", "sweet"],
+    },
+    Emoji {
+        emoji: "🍪",
+        name: "cookie",
+        keywords: &["sweet", "biscuit"],
+    },
+    Emoji {
+        emoji: "🎂",
+        name: "birthday cake",
+        keywords: &["birthday", "cake"],
+    },
+    Emoji {
+        emoji: "☕",
+        name: "hot beverage",
+        keywords: &["coffee", "tea"],
+    },
+    Emoji {
+        emoji: "🍺",
+        name: "beer mug",
+        keywords: &["beer", "drink"],
+    },
+    Emoji {
+        emoji: "🍷",
+        name: "wine glass",
+        keywords: &["wine", "drink"],
+    },
+    Emoji {
+        emoji: "🥂",
+        name: "clinking glasses",
+        keywords: &["cheers", "toast"],
+    },
+    Emoji {
+        emoji: "🌍",
+        name: "globe showing Europe-Africa",
+        keywords: &["earth", "world"],
+    },
+    Emoji {
+        emoji: "🏠",
+        name: "house",
+        keywords: &["home"],
+    },
+    Emoji {
+        emoji: "🚀",
+        name: "rocket",
+        keywords: &["launch", "ship", "space"],
+    },
+    Emoji {
+        emoji: "✈️",
+        name: "airplane",
+        keywords: &["plane", "flight", "travel"],
+    },
+    Emoji {
+        emoji: "🚗",
+        name: "automobile",
+        keywords: &["car"],
+    },
+    Emoji {
+        emoji: "🚲",
+        name: "bicycle",
+        keywords: &["bike"],
+    },
+    Emoji {
+        emoji: "⏰",
+        name: "alarm clock",
+        keywords: &["alarm", "time"],
+    },
+    Emoji {
+        emoji: "⌛",
+        name: "hourglass done",
+        keywords: &["time", "wait"],
+    },
+    Emoji {
+        emoji: "🌙",
+        name: "crescent moon",
+        keywords: &["moon", "night"],
+    },
+    Emoji {
+        emoji: "☀️",
+        name: "sun",
+        keywords: &["sunny", "weather"],
+    },
+    Emoji {
+        emoji: "⭐",
+        name: "star",
+        keywords: &["favorite"],
+    },
+    Emoji {
+        emoji: "🌈",
+        name: "rainbow",
+        keywords: &["pride"],
+    },
+    Emoji {
+        emoji: "⚡",
+        name: "high voltage",
+        keywords: &["lightning", "zap", "fast"],
+    },
+    Emoji {
+        emoji: "🔥",
+        name: "fire",
+        keywords: &["flame", "hot", "lit"],
+    },
+    Emoji {
+        emoji: "💧",
+        name: "droplet",
+        keywords: &["water", "drop"],
+    },
+    Emoji {
+        emoji: "❄️",
+        name: "snowflake",
+        keywords: &["snow", "cold", "winter"],
+    },
+    Emoji {
+        emoji: "🎉",
+        name: "party popper",
+        keywords: &["party", "tada", "celebrate"],
+    },
+    Emoji {
+        emoji: "🎁",
+        name: "wrapped gift",
+        keywords: &["present", "gift"],
+    },
+    Emoji {
+        emoji: "🏆",
+        name: "trophy",
+        keywords: &["win", "award"],
+    },
+    Emoji {
+        emoji: "⚽",
+        name: "soccer ball",
+        keywords: &["football", "sport"],
+    },
+    Emoji {
+        emoji: "🎮",
+        name: "video game",
+        keywords: &["game", "controller"],
+    },
+    Emoji {
+        emoji: "🎵",
+        name: "musical note",
+        keywords: &["music", "note"],
+    },
+    Emoji {
+        emoji: "🎧",
+        name: "headphone",
+        keywords: &["music", "headphones"],
+    },
+    Emoji {
+        emoji: "📱",
+        name: "mobile phone",
+        keywords: &["phone", "cell"],
+    },
+    Emoji {
+        emoji: "💻",
+        name: "laptop",
+        keywords: &["computer", "pc"],
+    },
+    Emoji {
+        emoji: "⌨️",
+        name: "keyboard",
+        keywords: &["typing"],
+    },
+    Emoji {
+        emoji: "🖥️",
+        name: "desktop computer",
+        keywords: &["computer", "monitor"],
+    },
+    Emoji {
+        emoji: "💾",
+        name: "floppy disk",
+        keywords: &["save", "disk"],
+    },
+    Emoji {
+        emoji: "📷",
+        name: "camera",
+        keywords: &["photo"],
+    },
+    Emoji {
+        emoji: "💡",
+        name: "light bulb",
+        keywords: &["idea", "bulb"],
+    },
+    Emoji {
+        emoji: "📚",
+        name: "books",
+        keywords: &["read", "library"],
+    },
+    Emoji {
+        emoji: "📝",
+        name: "memo",
+        keywords: &["note", "write"],
+    },
+    Emoji {
+        emoji: "📌",
+        name: "pushpin",
+        keywords: &["pin"],
+    },
+    Emoji {
+        emoji: "📎",
+        name: "paperclip",
+        keywords: &["attach", "clip"],
+    },
+    Emoji {
+        emoji: "✂️",
+        name: "scissors",
+        keywords: &["cut"],
+    },
+    Emoji {
+        emoji: "🔒",
+        name: "locked",
+        keywords: &["lock", "secure"],
+    },
+    Emoji {
+        emoji: "🔑",
+        name: "key",
+        keywords: &["password", "unlock"],
+    },
+    Emoji {
+        emoji: "🔨",
+        name: "hammer",
+        keywords: &["tool", "build"],
+    },
+    Emoji {
+        emoji: "🔧",
+        name: "wrench",
+        keywords: &["tool", "fix"],
+    },
+    Emoji {
+        emoji: "⚙️",
+        name: "gear",
+        keywords: &["settings", "cog"],
+    },
+    Emoji {
+        emoji: "🧪",
+        name: "test tube",
+        keywords: &["test", "science"],
+    },
+    Emoji {
+        emoji: "💰",
+        name: "money bag",
+        keywords: &["money", "cash"],
+    },
+    Emoji {
+        emoji: "📦",
+        name: "package",
+        keywords: &["box", "ship"],
+    },
+    Emoji {
+        emoji: "📅",
+        name: "calendar",
+        keywords: &["date"],
+    },
+    Emoji {
+        emoji: "📈",
+        name: "chart increasing",
+        keywords: &["graph", "up", "growth"],
+    },
+    Emoji {
+        emoji: "📉",
+        name: "chart decreasing",
+        keywords: &["graph", "down"],
+    },
+    Emoji {
+        emoji: "🗑️",
+        name: "wastebasket",
+        keywords: &["trash", "delete"],
+    },
+    Emoji {
+        emoji: "✅",
+        name: "check mark button",
+        keywords: &["done", "yes", "ok"],
+    },
+    Emoji {
+        emoji: "✔️",
+        name: "check mark",
+        keywords: &["done", "yes"],
+    },
+    Emoji {
+        emoji: "❌",
+        name: "cross mark",
+        keywords: &["no", "wrong", "x"],
+    },
+    Emoji {
+        emoji: "❓",
+        name: "red question mark",
+        keywords: &["question"],
+    },
+    Emoji {
+        emoji: "❗",
+        name: "red exclamation mark",
+        keywords: &["exclamation", "important"],
+    },
+    Emoji {
+        emoji: "⚠️",
+        name: "warning",
+        keywords: &["caution", "alert"],
+    },
+    Emoji {
+        emoji: "🚫",
+        name: "prohibited",
+        keywords: &["forbidden", "no"],
+    },
+    Emoji {
+        emoji: "♻️",
+        name: "recycling symbol",
+        keywords: &["recycle"],
+    },
+    Emoji {
+        emoji: "➡️",
+        name: "right arrow",
+        keywords: &["arrow", "next"],
+    },
+    Emoji {
+        emoji: "⬅️",
+        name: "left arrow",
+        keywords: &["arrow", "back"],
+    },
+    Emoji {
+        emoji: "⬆️",
+        name: "up arrow",
+        keywords: &["arrow"],
+    },
+    Emoji {
+        emoji: "⬇️",
+        name: "down arrow",
+        keywords: &["arrow"],
+    },
+    Emoji {
+        emoji: "🔁",
+        name: "repeat button",
+        keywords: &["loop", "repeat"],
+    },
+    Emoji {
+        emoji: "➕",
+        name: "plus",
+        keywords: &["add"],
+    },
+    Emoji {
+        emoji: "➖",
+        name: "minus",
+        keywords: &["subtract"],
+    },
+    Emoji {
+        emoji: "♾️",
+        name: "infinity",
+        keywords: &["forever"],
+    },
+    Emoji {
+        emoji: "©️",
+        name: "copyright",
+        keywords: &["c"],
+    },
+    Emoji {
+        emoji: "™️",
+        name: "trade mark",
+        keywords: &["tm"],
+    },
+    Emoji {
+        emoji: "🏳️‍🌈",
+        name: "rainbow flag",
+        keywords: &["pride", "lgbt"],
+    },
+    Emoji {
+        emoji: "🏁",
+        name: "chequered flag",
+        keywords: &["finish", "race"],
+    },
+];
+
+#[cfg(not(feature = "emoji"))]
+pub const EMOJI: &[Emoji] = &[];
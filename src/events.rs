@@ -0,0 +1,147 @@
+use gtk::glib;
+use serde::Serialize;
+use std::ffi::CString;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+
+/// Something external tools may want to react to. Serialized as one JSON
+/// object per line, tagged by `event`.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    Shown,
+    Hidden,
+    QueryChanged {
+        query: &'a str,
+    },
+    Launched {
+        key: &'a str,
+        name: &'a str,
+    },
+    LaunchFailed {
+        key: &'a str,
+        name: &'a str,
+        error: String,
+    },
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    time: u64,
+    #[serde(flatten)]
+    event: &'a Event<'a>,
+}
+
+pub fn events_path() -> PathBuf {
+    glib::user_runtime_dir().join("hyperfind").join("events")
+}
+
+/// The event as one line of JSON, or None if it wouldn't fit in a single
+/// atomic pipe write. A longer write could be interleaved with another
+/// instance's, and queries and launch errors have no length limit.
+fn event_line(event: &Event, now: u64) -> Option<String> {
+    let mut line = serde_json::to_string(&Envelope { time: now, event }).ok()?;
+    line.push('\n');
+    (line.len() <= libc::PIPE_BUF).then_some(line)
+}
+
+/// Writes an event to the events FIFO without ever blocking: if nobody is
+/// listening, or the listener has fallen behind and the pipe is full, the
+/// event is dropped. So is one too long to write atomically.
+pub fn emit(event: &Event, now: u64) {
+    let Some(line) = event_line(event, now) else {
+        return;
+    };
+
+    let Ok(mut fifo) = fs::OpenOptions::new()
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(events_path())
+    else {
+        return;
+    };
+
+    // Lines up to PIPE_BUF are written whole or not at all.
+    let _ = fifo.write(line.as_bytes());
+}
+
+fn make_fifo(path: &Path) -> io::Result<()> {
+    if let Ok(metadata) = fs::metadata(path) {
+        if metadata.file_type().is_fifo() {
+            return Ok(());
+        }
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} exists and is not a FIFO", path.display()),
+        ));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Creates the events FIFO if needed and copies events to stdout until
+/// interrupted.
+pub fn listen() -> i32 {
+    let path = events_path();
+    if let Err(err) = make_fifo(&path) {
+        eprintln!("Failed to create {}: {}", path.display(), err);
+        return 1;
+    }
+
+    // Holding the write end open too means the read never sees EOF when a
+    // launcher instance exits.
+    let fifo = match fs::OpenOptions::new().read(true).write(true).open(&path) {
+        Ok(fifo) => fifo,
+        Err(err) => {
+            eprintln!("Failed to open {}: {}", path.display(), err);
+            return 1;
+        }
+    };
+
+    let stdout = io::stdout();
+    for line in BufReader::new(fifo).lines() {
+        let Ok(line) = line else {
+            return 1;
+        };
+        let mut out = stdout.lock();
+        if writeln!(out, "{}", line).and_then(|_| out.flush()).is_err() {
+            return 0;
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_too_long_for_one_pipe_write_are_dropped() {
+        let line = event_line(&Event::QueryChanged { query: "fire" }, 7).unwrap();
+        assert_eq!(
+            line,
+            "{\"time\":7,\"event\":\"query_changed\",\"query\":\"fire\"}\n"
+        );
+
+        let event = |length| Event::LaunchFailed {
+            key: "a.desktop",
+            name: "A",
+            error: "x".repeat(length),
+        };
+        let short = event_line(&event(0), 7).unwrap().len();
+        let fits = libc::PIPE_BUF - short;
+        assert_eq!(event_line(&event(fits), 7).unwrap().len(), libc::PIPE_BUF);
+        assert!(event_line(&event(fits + 1), 7).is_none());
+    }
+}
@@ -0,0 +1,132 @@
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+fn is_executable(path: &Path) -> bool {
+    fs::metadata(path)
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+/// Resolves a program the way exec would: names containing a slash are
+/// used as-is, anything else is looked up in each `path_var` directory.
+pub fn resolve_program(program: &str, path_var: Option<&OsStr>) -> Option<PathBuf> {
+    if program.is_empty() {
+        return None;
+    }
+
+    if program.contains('/') {
+        let path = PathBuf::from(program);
+        return is_executable(&path).then_some(path);
+    }
+
+    env::split_paths(path_var?)
+        .map(|dir| dir.join(program))
+        .find(|candidate| is_executable(candidate))
+}
+
+/// Splits an Exec line into arguments, honouring double quotes and
+/// backslash escapes as the desktop entry spec describes.
+fn split_exec(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quoted = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_arg = true;
+            }
+            '\\' if quoted => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// The program an Exec line actually runs, looking through a leading
+/// `env` and its options and `VAR=value` assignments.
+pub fn exec_program(exec: &str) -> Option<String> {
+    let mut args = split_exec(exec).into_iter();
+    let first = args.next()?;
+    if Path::new(&first).file_name() != Some(OsStr::new("env")) {
+        return Some(first);
+    }
+
+    while let Some(arg) = args.next() {
+        if arg == "-u" || arg == "--unset" || arg == "-C" || arg == "--chdir" {
+            args.next();
+        } else if !arg.starts_with('-') && !arg.contains('=') {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_exec_honours_quotes_and_escapes() {
+        assert_eq!(
+            split_exec(r#"foo  "a b" "say \"hi\"" c"#),
+            vec!["foo", "a b", "say \"hi\"", "c"]
+        );
+        assert_eq!(split_exec(r#"foo """#), vec!["foo", ""]);
+    }
+
+    #[test]
+    fn exec_program_looks_through_env() {
+        assert_eq!(exec_program("firefox %u").as_deref(), Some("firefox"));
+        assert_eq!(
+            exec_program("env -u DISPLAY GDK_BACKEND=x11 /usr/bin/app --flag").as_deref(),
+            Some("/usr/bin/app")
+        );
+        assert_eq!(exec_program("/usr/bin/env FOO=1"), None);
+        assert_eq!(exec_program(""), None);
+    }
+
+    #[test]
+    fn resolve_program_needs_an_executable_file() {
+        let dir = env::temp_dir().join(format!("hyperfind-test-{}-exec", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let program = dir.join("tool");
+        fs::write(&program, "#!/bin/sh\n").unwrap();
+        fs::write(dir.join("data"), "").unwrap();
+        fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let path_var = env::join_paths(["/nonexistent".as_ref(), dir.as_path()]).unwrap();
+        assert_eq!(
+            resolve_program("tool", Some(&path_var)),
+            Some(program.clone())
+        );
+        assert_eq!(resolve_program("data", Some(&path_var)), None);
+        assert_eq!(resolve_program("tool", None), None);
+        assert_eq!(
+            resolve_program(program.to_str().unwrap(), None),
+            Some(program)
+        );
+    }
+}
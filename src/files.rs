@@ -0,0 +1,68 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+pub const ROOT_PREFIX: &str = "/";
+pub const HOME_PREFIX: &str = "~/";
+
+#[derive(Clone)]
+pub struct FileMatch {
+    pub path: PathBuf,
+    /// What the search should read to select this entry, keeping a
+    /// leading `~/` as typed. Directories end in `/`.
+    pub completion: String,
+    pub name: String,
+    pub is_dir: bool,
+}
+
+fn expand_home(typed: &str) -> Option<PathBuf> {
+    match typed.strip_prefix(HOME_PREFIX) {
+        Some(rest) => Some(PathBuf::from(env::var_os("HOME")?).join(rest)),
+        None => Some(PathBuf::from(typed)),
+    }
+}
+
+/// Entries of the directory the query names whose names start with its
+/// last segment, ignoring case; directories first. Dotfiles only show once
+/// the segment starts with a dot. A directory that can't be read has no
+/// matches.
+pub fn complete(query: &str) -> Vec<FileMatch> {
+    let query = query.trim_start();
+    let split = query.rfind('/').map_or(0, |index| index + 1);
+    let (dir_text, segment) = query.split_at(split);
+    let Some(dir) = expand_home(dir_text) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let segment = segment.to_lowercase();
+    let show_hidden = segment.starts_with('.');
+    let mut matches: Vec<FileMatch> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            if name.starts_with('.') && !show_hidden {
+                return None;
+            }
+            if !name.to_lowercase().starts_with(&segment) {
+                return None;
+            }
+            // Follows symlinks so a link to a directory can be entered.
+            let is_dir = entry.path().is_dir();
+            let mut completion = format!("{}{}", dir_text, name);
+            if is_dir {
+                completion.push('/');
+            }
+            Some(FileMatch {
+                path: entry.path(),
+                completion,
+                name,
+                is_dir,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    matches
+}
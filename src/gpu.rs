@@ -0,0 +1,109 @@
+use gio::prelude::*;
+use gtk::glib;
+use std::collections::BTreeMap;
+
+/// Ids of the rows listed with every app's own desktop actions, to pick
+/// the GPU for one launch.
+pub const DEDICATED_ACTION: &str = "hyperfind-dedicated-gpu";
+pub const DEFAULT_ACTION: &str = "hyperfind-default-gpu";
+
+/// What Mesa's drivers need to render on the other GPU, used when
+/// switcheroo-control isn't running to say.
+const FALLBACK_ENV: &[(&str, &str)] = &[("DRI_PRIME", "1")];
+
+/// Asked at startup, so a hung service can only hold it up this long.
+const SWITCHEROO_TIMEOUT_MS: i32 = 500;
+
+/// Variables, and their values, set for an app started on the dedicated
+/// GPU.
+pub type Env = Vec<(String, String)>;
+
+/// switcheroo-control's environment for its first GPU that isn't the
+/// default, or None when there's only the one. Errors when the service
+/// isn't running.
+fn switcheroo_env() -> Result<Option<Env>, glib::Error> {
+    let bus = gio::bus_get_sync(gio::BusType::System, gio::Cancellable::NONE)?;
+    let reply = bus.call_sync(
+        Some("net.hadess.SwitcherooControl"),
+        "/net/hadess/SwitcherooControl",
+        "org.freedesktop.DBus.Properties",
+        "Get",
+        Some(&("net.hadess.SwitcherooControl", "GPUs").to_variant()),
+        None,
+        gio::DBusCallFlags::NO_AUTO_START,
+        SWITCHEROO_TIMEOUT_MS,
+        gio::Cancellable::NONE,
+    )?;
+    let Some(gpus) = reply.child_value(0).as_variant() else {
+        return Ok(None);
+    };
+    let dedicated = gpus
+        .iter()
+        .map(|gpu| glib::VariantDict::new(Some(&gpu)))
+        .find(|gpu| {
+            !gpu.lookup::<bool>("Default")
+                .ok()
+                .flatten()
+                .unwrap_or(false)
+        });
+    let Some(environment) =
+        dedicated.and_then(|gpu| gpu.lookup::<Vec<String>>("Environment").ok().flatten())
+    else {
+        return Ok(None);
+    };
+    // A flat list of names each followed by its value.
+    Ok(Some(
+        environment
+            .chunks_exact(2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect(),
+    ))
+}
+
+/// The environment that starts an app on the dedicated GPU: the
+/// configured one, else switcheroo-control's, else `DRI_PRIME=1`. None
+/// when switcheroo-control knows of only one GPU.
+pub fn offload_env(configured: Option<BTreeMap<String, String>>) -> Option<Env> {
+    if let Some(configured) = configured {
+        return Some(configured.into_iter().collect());
+    }
+    switcheroo_env().unwrap_or_else(|_| {
+        Some(
+            FALLBACK_ENV
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        )
+    })
+}
+
+/// Whether an entry asks for the dedicated GPU, with the freedesktop key
+/// or KDE's older one.
+pub fn prefers_dedicated(desktop: &gio::DesktopAppInfo) -> bool {
+    desktop.boolean("PrefersNonDefaultGPU") || desktop.boolean("X-KDE-RunOnDiscreteGpu")
+}
+
+/// The row offering the other GPU from the one `dedicated` says the app
+/// starts on, as an (action, label) pair.
+pub fn action(dedicated: bool) -> (String, String) {
+    if dedicated {
+        (
+            DEFAULT_ACTION.to_string(),
+            "Launch on default GPU".to_string(),
+        )
+    } else {
+        (
+            DEDICATED_ACTION.to_string(),
+            "Launch on dedicated GPU".to_string(),
+        )
+    }
+}
+
+/// For one of the added actions, whether it picks the dedicated GPU.
+pub fn action_choice(action: &str) -> Option<bool> {
+    match action {
+        DEDICATED_ACTION => Some(true),
+        DEFAULT_ACTION => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{AppEntry, IconCache, build_icon_image};
+use gtk::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Characters of an app's name a cell shows per line before wrapping.
+const NAME_CHARS: i32 = 12;
+/// Characters of a name the bar shows before cutting it off.
+const BAR_NAME_CHARS: i32 = 24;
+
+/// `layout = "grid"`: apps as large icons with their names underneath,
+/// so many to a line, and headers and every other kind of result on a
+/// line of their own. `layout = "bar"` is the same with one endless line,
+/// everything on it side by side. The hidden ListBox still holds a row
+/// per item for selection and activation; this only shows them.
+pub struct GridView {
+    pub grid: gtk::Grid,
+    columns: usize,
+    bar: bool,
+    /// What shows each item, in item order.
+    widgets: RefCell<Vec<gtk::Widget>>,
+    /// Each item's column and line.
+    positions: RefCell<Vec<(usize, usize)>>,
+    /// Where the next app goes.
+    next: Cell<(usize, usize)>,
+    checked: Cell<Option<usize>>,
+}
+
+impl GridView {
+    pub fn new(columns: usize) -> Self {
+        let grid = gtk::Grid::new();
+        grid.set_column_homogeneous(true);
+        grid.set_row_spacing(4);
+        grid.set_column_spacing(4);
+        GridView {
+            grid,
+            columns: columns.max(1),
+            bar: false,
+            widgets: RefCell::new(Vec::new()),
+            positions: RefCell::new(Vec::new()),
+            next: Cell::new((0, 0)),
+            checked: Cell::new(None),
+        }
+    }
+
+    /// One line of names, for the bar.
+    pub fn bar() -> Self {
+        let view = GridView {
+            columns: usize::MAX,
+            bar: true,
+            ..GridView::new(1)
+        };
+        // Each name only as wide as it needs.
+        view.grid.set_column_homogeneous(false);
+        view
+    }
+
+    pub fn is_bar(&self) -> bool {
+        self.bar
+    }
+
+    pub fn clear(&self) {
+        for child in self.grid.children() {
+            self.grid.remove(&child);
+        }
+        self.widgets.borrow_mut().clear();
+        self.positions.borrow_mut().clear();
+        self.next.set((0, 0));
+        self.checked.set(None);
+    }
+
+    /// The line after the current one, or the current one if nothing is on
+    /// it yet.
+    fn fresh_line(&self) -> usize {
+        let (column, line) = self.next.get();
+        if column == 0 { line } else { line + 1 }
+    }
+
+    fn push(&self, widget: gtk::Widget, position: (usize, usize)) {
+        self.widgets.borrow_mut().push(widget);
+        self.positions.borrow_mut().push(position);
+    }
+
+    /// Puts an app's cell in the next free column. The bar leaves the
+    /// icon out.
+    pub fn add_app(&self, app: &AppEntry, icons: &Rc<IconCache>, row: &gtk::ListBoxRow) {
+        let cell = gtk::Box::new(gtk::Orientation::Vertical, 4);
+        if let Some(icon) = &app.icon
+            && icons.size > 0
+            && !self.bar
+        {
+            cell.pack_start(&build_icon_image(icon, icons), false, false, 0);
+        }
+        let name = gtk::Label::new(Some(&app.name));
+        if self.bar {
+            name.set_max_width_chars(BAR_NAME_CHARS);
+        } else {
+            name.set_justify(gtk::Justification::Center);
+            name.set_line_wrap(true);
+            name.set_lines(2);
+            name.set_max_width_chars(NAME_CHARS);
+        }
+        name.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        if app.hidden {
+            name.style_context().add_class("dim-label");
+        }
+        cell.pack_start(&name, false, false, 0);
+        let button = cell_button(&cell, row);
+        button.set_tooltip_text(Some(&app.name));
+
+        let (column, line) = self.next.get();
+        self.grid.attach(&button, column as i32, line as i32, 1, 1);
+        self.next.set(if column + 1 == self.columns {
+            (0, line + 1)
+        } else {
+            (column + 1, line)
+        });
+        self.push(button.upcast(), (column, line));
+    }
+
+    /// Moves a list row's content onto a line of its own: a header as it
+    /// is, anything else as a cell that spans the line. The bar puts it
+    /// next to the others instead.
+    pub fn add_wide(&self, row: &gtk::ListBoxRow) {
+        let Some(content) = row.child() else {
+            return;
+        };
+        row.remove(&content);
+        let widget: gtk::Widget = if row.is_selectable() {
+            cell_button(&content, row).upcast()
+        } else {
+            content
+        };
+        if self.bar {
+            let (column, _) = self.next.get();
+            self.grid.attach(&widget, column as i32, 0, 1, 1);
+            self.next.set((column + 1, 0));
+            self.push(widget, (column, 0));
+            return;
+        }
+        let line = self.fresh_line();
+        self.grid
+            .attach(&widget, 0, line as i32, self.columns as i32, 1);
+        self.next.set((0, line + 1));
+        self.push(widget, (0, line));
+    }
+
+    /// Marks the item the ListBox has selected.
+    pub fn check(&self, index: Option<usize>) {
+        let widgets = self.widgets.borrow();
+        if let Some(previous) = self.checked.get().and_then(|index| widgets.get(index)) {
+            previous.unset_state_flags(gtk::StateFlags::CHECKED);
+        }
+        if let Some(widget) = index.and_then(|index| widgets.get(index)) {
+            widget.set_state_flags(gtk::StateFlags::CHECKED, false);
+        }
+        self.checked.set(index);
+    }
+
+    pub fn allocation(&self, index: usize) -> Option<gtk::Allocation> {
+        self.widgets
+            .borrow()
+            .get(index)
+            .map(|widget| widget.allocation())
+    }
+
+    pub fn allocations(&self) -> Vec<gtk::Allocation> {
+        self.widgets
+            .borrow()
+            .iter()
+            .map(|widget| widget.allocation())
+            .collect()
+    }
+
+    /// The selectable item on the nearest line above (`direction` < 0) or
+    /// below `index` that has one, in the column closest to its own.
+    pub fn line_neighbor(
+        &self,
+        index: usize,
+        direction: i32,
+        selectable: impl Fn(usize) -> bool,
+    ) -> Option<usize> {
+        let positions = self.positions.borrow();
+        let &(column, line) = positions.get(index)?;
+        let mut candidates: Vec<(usize, usize, usize)> = positions
+            .iter()
+            .enumerate()
+            .filter(|(other, (_, other_line))| {
+                selectable(*other)
+                    && if direction < 0 {
+                        *other_line < line
+                    } else {
+                        *other_line > line
+                    }
+            })
+            .map(|(other, (other_column, other_line))| (other, *other_column, *other_line))
+            .collect();
+        let target = if direction < 0 {
+            candidates.iter().map(|(_, _, line)| *line).max()?
+        } else {
+            candidates.iter().map(|(_, _, line)| *line).min()?
+        };
+        candidates.retain(|(_, _, line)| *line == target);
+        candidates
+            .into_iter()
+            .min_by_key(|(_, other_column, _)| other_column.abs_diff(column))
+            .map(|(other, _, _)| other)
+    }
+}
+
+/// A flat button around a cell's content that selects and activates the
+/// item's ListBox row when clicked. It never takes focus from the search.
+fn cell_button(content: &impl IsA<gtk::Widget>, row: &gtk::ListBoxRow) -> gtk::Button {
+    let button = gtk::Button::new();
+    button.set_relief(gtk::ReliefStyle::None);
+    button.set_can_focus(false);
+    button.add(content);
+    let row = row.clone();
+    button.connect_clicked(move |_| {
+        if let Some(listbox) = row
+            .parent()
+            .and_then(|parent| parent.downcast::<gtk::ListBox>().ok())
+        {
+            listbox.select_row(Some(&row));
+        }
+        row.activate();
+    });
+    button
+}
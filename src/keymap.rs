@@ -0,0 +1,372 @@
+use gtk::gdk;
+use gtk::gdk::keys::constants as keys;
+use std::fmt;
+
+/// How many app rows get an Alt+number.
+pub const NUMBERED: u8 = 9;
+
+const LAUNCH_NTH: [&str; NUMBERED as usize] = [
+    "launch-1", "launch-2", "launch-3", "launch-4", "launch-5", "launch-6", "launch-7", "launch-8",
+    "launch-9",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    SelectNext,
+    SelectPrevious,
+    SelectFirst,
+    SelectLast,
+    DeleteWord,
+    ClearQuery,
+    Launch,
+    IgnoreSelected,
+    ResetUsage,
+    RestoreUsage,
+    ToggleSnooze,
+    TogglePin,
+    ToggleFrequent,
+    /// Opens the selected directory, or else moves right in the grid, or
+    /// else shows the selected app's desktop actions. The key moves the
+    /// cursor when none of them applies.
+    ExpandActions,
+    Complete,
+    RestoreQuery,
+    /// Hides the desktop actions, or else moves left in the grid; the
+    /// cursor moves otherwise.
+    CollapseActions,
+    RunInTerminal,
+    RunElevated,
+    /// Launches the nth app row on screen, counting from 1.
+    LaunchNth(u8),
+    CopyCommand,
+    CopyDesktopFile,
+    ToggleAutostart,
+    EditDesktopFile,
+    LaunchNew,
+}
+
+impl Action {
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::SelectNext => "select-next",
+            Action::SelectPrevious => "select-previous",
+            Action::SelectFirst => "select-first",
+            Action::SelectLast => "select-last",
+            Action::DeleteWord => "delete-word",
+            Action::ClearQuery => "clear-query",
+            Action::Launch => "launch",
+            Action::IgnoreSelected => "ignore-selected",
+            Action::ResetUsage => "reset-usage",
+            Action::RestoreUsage => "restore-usage",
+            Action::ToggleSnooze => "toggle-snooze",
+            Action::TogglePin => "toggle-pin",
+            Action::ToggleFrequent => "toggle-frequent",
+            Action::ExpandActions => "expand-actions",
+            Action::Complete => "complete",
+            Action::RestoreQuery => "restore-query",
+            Action::CollapseActions => "collapse-actions",
+            Action::RunInTerminal => "run-in-terminal",
+            Action::RunElevated => "run-elevated",
+            Action::LaunchNth(n) => LAUNCH_NTH
+                .get(usize::from(n).wrapping_sub(1))
+                .copied()
+                .unwrap_or("launch-nth"),
+            Action::CopyCommand => "copy-command",
+            Action::CopyDesktopFile => "copy-desktop-file",
+            Action::ToggleAutostart => "toggle-autostart",
+            Action::EditDesktopFile => "edit-desktop-file",
+            Action::LaunchNew => "launch-new",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Chord {
+    pub key: gdk::keys::Key,
+    pub modifiers: gdk::ModifierType,
+}
+
+impl Chord {
+    fn new(key: gdk::keys::Key, modifiers: gdk::ModifierType) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Whether pressing this chord in the entry would otherwise type a
+    /// character. Shift alone still types (capitals, symbols).
+    fn types_text(&self) -> bool {
+        if !gdk::ModifierType::SHIFT_MASK.contains(self.modifiers) {
+            return false;
+        }
+        self.key.to_unicode().is_some_and(|c| !c.is_control())
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match gtk::accelerator_get_label(*self.key, self.modifiers) {
+            Some(label) => write!(f, "{}", label),
+            None => write!(f, "keyval {:#x}", *self.key),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Binding {
+    pub chord: Chord,
+    pub action: Action,
+}
+
+pub fn default_bindings() -> Vec<Binding> {
+    let none = gdk::ModifierType::empty();
+    let shift = gdk::ModifierType::SHIFT_MASK;
+    let control = gdk::ModifierType::CONTROL_MASK;
+    let alt = gdk::ModifierType::MOD1_MASK;
+    [
+        (keys::Escape, none, Action::Quit),
+        (keys::Down, none, Action::SelectNext),
+        (keys::Up, none, Action::SelectPrevious),
+        (keys::Home, none, Action::SelectFirst),
+        (keys::End, none, Action::SelectLast),
+        // Readline and vi habits.
+        (keys::n, control, Action::SelectNext),
+        (keys::p, control, Action::SelectPrevious),
+        (keys::j, control, Action::SelectNext),
+        (keys::k, control, Action::SelectPrevious),
+        (keys::w, control, Action::DeleteWord),
+        (keys::u, control, Action::ClearQuery),
+        (keys::Return, none, Action::Launch),
+        (keys::KP_Enter, none, Action::Launch),
+        (keys::Return, alt, Action::LaunchNew),
+        (keys::Return, shift, Action::RunInTerminal),
+        (keys::Return, control | shift, Action::RunElevated),
+        (keys::c, control, Action::CopyCommand),
+        (keys::c, control | shift, Action::CopyDesktopFile),
+        (keys::s, control | shift, Action::ToggleAutostart),
+        (keys::e, control, Action::EditDesktopFile),
+        (keys::Delete, shift, Action::IgnoreSelected),
+        (keys::Delete, control, Action::ResetUsage),
+        (keys::z, control, Action::RestoreUsage),
+        (keys::h, control | shift, Action::ToggleSnooze),
+        (keys::p, control | shift, Action::TogglePin),
+        (keys::h, control, Action::ToggleFrequent),
+        // Right and Left only reach the entry's cursor once opening a
+        // directory, moving in the grid and the desktop actions have had
+        // their turn, in that order.
+        (keys::Right, none, Action::ExpandActions),
+        (keys::Left, none, Action::CollapseActions),
+        (keys::Tab, none, Action::Complete),
+        (keys::ISO_Left_Tab, shift, Action::RestoreQuery),
+    ]
+    .into_iter()
+    .chain(
+        [
+            keys::_1,
+            keys::_2,
+            keys::_3,
+            keys::_4,
+            keys::_5,
+            keys::_6,
+            keys::_7,
+            keys::_8,
+            keys::_9,
+        ]
+        .into_iter()
+        .zip(1..=NUMBERED)
+        .map(|(key, n)| (key, alt, Action::LaunchNth(n))),
+    )
+    .map(|(key, modifiers, action)| Binding {
+        chord: Chord::new(key, modifiers),
+        action,
+    })
+    .collect()
+}
+
+/// Finds the action bound to a key event, ignoring lock modifiers such as
+/// Caps Lock and Num Lock. Letter bindings are written in lowercase.
+pub fn lookup(
+    bindings: &[Binding],
+    key: gdk::keys::Key,
+    state: gdk::ModifierType,
+) -> Option<Action> {
+    let key = key.to_lower();
+    let modifiers = state & gtk::accelerator_get_default_mod_mask();
+    bindings
+        .iter()
+        .find(|binding| binding.chord.key == key && binding.chord.modifiers == modifiers)
+        .map(|binding| binding.action)
+}
+
+#[derive(Debug)]
+pub enum Conflict {
+    ShadowsText {
+        chord: Chord,
+        action: Action,
+    },
+    SameChord {
+        chord: Chord,
+        first: Action,
+        second: Action,
+    },
+    OverlappingPrefixes {
+        shorter: String,
+        longer: String,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Conflict::ShadowsText { chord, action } => write!(
+                f,
+                "{} is bound to {} and can no longer be typed",
+                chord,
+                action.name()
+            ),
+            Conflict::SameChord {
+                chord,
+                first,
+                second,
+            } => write!(
+                f,
+                "{} is bound to both {} and {}",
+                chord,
+                first.name(),
+                second.name()
+            ),
+            Conflict::OverlappingPrefixes { shorter, longer } => write!(
+                f,
+                "mode prefix '{}' also matches queries meant for '{}'",
+                shorter, longer
+            ),
+        }
+    }
+}
+
+/// Checks the merged keymap and the mode prefix table for bindings that
+/// eat typed characters, chords with more than one action, and prefixes
+/// that swallow each other.
+pub fn find_conflicts(bindings: &[Binding], prefixes: &[&str]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+
+    for (index, binding) in bindings.iter().enumerate() {
+        if binding.chord.types_text() {
+            conflicts.push(Conflict::ShadowsText {
+                chord: binding.chord,
+                action: binding.action,
+            });
+        }
+
+        let earlier = bindings[..index]
+            .iter()
+            .find(|other| other.chord == binding.chord && other.action != binding.action);
+        if let Some(other) = earlier {
+            conflicts.push(Conflict::SameChord {
+                chord: binding.chord,
+                first: other.action,
+                second: binding.action,
+            });
+        }
+    }
+
+    for (index, prefix) in prefixes.iter().enumerate() {
+        for other in &prefixes[index + 1..] {
+            let (shorter, longer) = if prefix.len() <= other.len() {
+                (prefix, other)
+            } else {
+                (other, prefix)
+            };
+            if longer.starts_with(shorter) {
+                conflicts.push(Conflict::OverlappingPrefixes {
+                    shorter: shorter.to_string(),
+                    longer: longer.to_string(),
+                });
+            }
+        }
+    }
+
+    conflicts
+}
+
+pub fn dump(bindings: &[Binding], prefixes: &[&str]) {
+    for binding in bindings {
+        println!(
+            "{:<20} {}",
+            binding.chord.to_string(),
+            binding.action.name()
+        );
+    }
+    for prefix in prefixes {
+        println!("{:<20} mode prefix", format!("'{}'", prefix));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(key: gdk::keys::Key, modifiers: gdk::ModifierType, action: Action) -> Binding {
+        Binding {
+            chord: Chord::new(key, modifiers),
+            action,
+        }
+    }
+
+    #[test]
+    fn default_bindings_do_not_conflict() {
+        let conflicts = find_conflicts(&default_bindings(), &[]);
+        assert!(conflicts.is_empty(), "{:?}", conflicts);
+    }
+
+    #[test]
+    fn finds_a_chord_bound_twice() {
+        let none = gdk::ModifierType::empty();
+        let bindings = [
+            binding(keys::Return, none, Action::Launch),
+            binding(keys::Return, none, Action::Quit),
+            // The same action twice is fine.
+            binding(keys::Down, none, Action::SelectNext),
+            binding(keys::Down, none, Action::SelectNext),
+        ];
+        let conflicts = find_conflicts(&bindings, &[]);
+        assert!(matches!(
+            conflicts.as_slice(),
+            [Conflict::SameChord {
+                first: Action::Launch,
+                second: Action::Quit,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn finds_bindings_that_shadow_typing() {
+        let shift = gdk::ModifierType::SHIFT_MASK;
+        let control = gdk::ModifierType::CONTROL_MASK;
+        let bindings = [
+            binding(keys::a, gdk::ModifierType::empty(), Action::Quit),
+            binding(keys::A, shift, Action::Quit),
+            binding(keys::a, control, Action::Quit),
+            binding(keys::Tab, gdk::ModifierType::empty(), Action::Complete),
+        ];
+        let shadowed: Vec<_> = find_conflicts(&bindings, &[])
+            .into_iter()
+            .filter_map(|conflict| match conflict {
+                Conflict::ShadowsText { chord, .. } => Some(chord),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(shadowed, vec![bindings[0].chord, bindings[1].chord]);
+    }
+
+    #[test]
+    fn finds_prefixes_that_swallow_each_other() {
+        let conflicts = find_conflicts(&[], &["ssh ", "s", "kill "]);
+        assert!(matches!(
+            conflicts.as_slice(),
+            [Conflict::OverlappingPrefixes { shorter, longer }]
+                if shorter == "s" && longer == "ssh "
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+use gio::prelude::*;
+use gtk::glib;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Escapes a value for a desktop entry key, per the spec's `\s`-style
+/// escapes for characters that would otherwise end or mangle the line.
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn contents(name: &str, command: &str, icon: Option<&str>) -> String {
+    let mut contents = String::from("[Desktop Entry]\nType=Application\n");
+    contents.push_str(&format!("Name={}\n", escape_value(name)));
+    contents.push_str(&format!("Exec={}\n", escape_value(command)));
+    if let Some(icon) = icon {
+        contents.push_str(&format!("Icon={}\n", escape_value(icon)));
+    }
+    contents.push_str("Terminal=false\n");
+    contents
+}
+
+/// Lowercase ASCII letters and digits, with every other run of characters
+/// collapsed to a single dash.
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "launcher".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Creates `hyperfind-<slug>.desktop` next to the user's other launchers,
+/// numbering the name rather than overwriting an existing file.
+fn create_file(name: &str) -> io::Result<(PathBuf, fs::File)> {
+    let dir = glib::user_data_dir().join("applications");
+    fs::create_dir_all(&dir)?;
+
+    let slug = slug(name);
+    for attempt in 1.. {
+        let file_name = if attempt == 1 {
+            format!("hyperfind-{}.desktop", slug)
+        } else {
+            format!("hyperfind-{}-{}.desktop", slug, attempt)
+        };
+        let path = dir.join(file_name);
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    unreachable!()
+}
+
+/// Writes a desktop entry for `command` and checks that GIO parses it back
+/// into a launchable app. The file is deleted again if it doesn't.
+pub fn write(
+    name: &str,
+    command: &str,
+    icon: &str,
+) -> Result<(PathBuf, gio::DesktopAppInfo), String> {
+    let name = name.trim();
+    let command = command.trim();
+    let icon = icon.trim();
+    if name.is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    if command.is_empty() {
+        return Err("Command must not be empty".to_string());
+    }
+
+    let contents = contents(name, command, (!icon.is_empty()).then_some(icon));
+    let (path, mut file) =
+        create_file(name).map_err(|err| format!("Failed to create launcher: {}", err))?;
+    if let Err(err) = file.write_all(contents.as_bytes()) {
+        let _ = fs::remove_file(&path);
+        return Err(format!("Failed to write {}: {}", path.display(), err));
+    }
+    drop(file);
+
+    match gio::DesktopAppInfo::from_filename(&path) {
+        Some(info) if info.commandline().is_some() => Ok((path, info)),
+        _ => {
+            let _ = fs::remove_file(&path);
+            Err(format!("'{}' is not a valid launcher command", command))
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::files::{self, FileMatch};
+use gtk::glib;
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Queries starting with this search the locate database by file name.
+pub const PREFIX: &str = "find ";
+
+/// Waited for after typing in `find `, so a locate process isn't started
+/// for every letter.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+/// How often a running locate is checked for more output.
+const POLL: Duration = Duration::from_millis(30);
+
+/// A `find ` search: the files found so far, and the process still
+/// printing more.
+pub struct Search {
+    pub name: String,
+    running: Option<Running>,
+    pub found: Vec<FileMatch>,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+/// Starts `command` for `name` once typing pauses, then calls `refresh`
+/// as it prints paths, until it finishes or `search` holds another one.
+/// Replacing the search drops and so kills its process.
+pub fn search_later(
+    search: &Rc<RefCell<Option<Search>>>,
+    command: &Rc<Vec<String>>,
+    name: String,
+    refresh: Rc<dyn Fn()>,
+) {
+    search.replace(Some(Search {
+        name: name.clone(),
+        running: None,
+        found: Vec::new(),
+        done: false,
+        error: None,
+    }));
+
+    let search = Rc::clone(search);
+    let command = Rc::clone(command);
+    glib::timeout_add_local_once(DEBOUNCE, move || {
+        {
+            let mut search = search.borrow_mut();
+            let Some(search) = search.as_mut().filter(|search| search.name == name) else {
+                return;
+            };
+            match Running::start(&command, &name) {
+                Ok(running) => search.running = Some(running),
+                Err(err) => {
+                    search.error = Some(err);
+                    search.done = true;
+                }
+            }
+        }
+        refresh();
+
+        glib::timeout_add_local(POLL, move || {
+            let (added, finished) = {
+                let mut search = search.borrow_mut();
+                let Some(search) = search.as_mut().filter(|search| search.name == name) else {
+                    return glib::ControlFlow::Break;
+                };
+                let Some(running) = &mut search.running else {
+                    return glib::ControlFlow::Break;
+                };
+                let (found, finished) = running.poll();
+                let added = !found.is_empty();
+                search.found.extend(found.into_iter().map(file_match));
+                if finished {
+                    search.running = None;
+                    search.done = true;
+                }
+                (added, finished)
+            };
+            if added || finished {
+                refresh();
+            }
+            if finished {
+                glib::ControlFlow::Break
+            } else {
+                glib::ControlFlow::Continue
+            }
+        });
+    });
+}
+
+/// A locate process whose output is collected on a thread as it prints
+/// it. Dropping it kills the process.
+pub struct Running {
+    child: Child,
+    found: Arc<Mutex<Vec<PathBuf>>>,
+    reader: Option<thread::JoinHandle<()>>,
+}
+
+impl Running {
+    /// Runs `command` with `query` as its last argument.
+    pub fn start(command: &[String], query: &str) -> Result<Running, String> {
+        let Some((program, args)) = command.split_first() else {
+            return Err("locate_command is empty".to_string());
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .arg(query)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| match err.kind() {
+                std::io::ErrorKind::NotFound => format!("{} isn't installed", program),
+                _ => format!("Couldn't run {}: {}", program, err),
+            })?;
+        let stdout = child.stdout.take().ok_or("no stdout")?;
+        let found = Arc::new(Mutex::new(Vec::new()));
+        let found_for_reader = Arc::clone(&found);
+        let reader = thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if let Ok(mut found) = found_for_reader.lock() {
+                    found.push(PathBuf::from(line));
+                }
+            }
+        });
+        Ok(Running {
+            child,
+            found,
+            reader: Some(reader),
+        })
+    }
+
+    /// The paths printed since the last call, and whether the process has
+    /// finished and printed everything.
+    pub fn poll(&mut self) -> (Vec<PathBuf>, bool) {
+        let finished = !matches!(self.child.try_wait(), Ok(None));
+        if finished && let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        let found = self
+            .found
+            .lock()
+            .map(|mut found| std::mem::take(&mut *found))
+            .unwrap_or_default();
+        (found, finished)
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// A found path as a file row, shown with `~` for the home directory.
+/// Completing a directory switches to browsing it.
+pub fn file_match(path: PathBuf) -> FileMatch {
+    let is_dir = path.is_dir();
+    let name = abbreviate_home(&path);
+    let completion = if is_dir {
+        format!("{}/", name)
+    } else {
+        name.clone()
+    };
+    FileMatch {
+        path,
+        completion,
+        name,
+        is_dir,
+    }
+}
+
+fn abbreviate_home(path: &Path) -> String {
+    if let Some(home) = std::env::var_os("HOME")
+        && let Ok(rest) = path.strip_prefix(home)
+    {
+        return format!("{}{}", files::HOME_PREFIX, rest.display());
+    }
+    path.display().to_string()
+}
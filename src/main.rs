@@ -3,735 +3,6957 @@ use gtk::gdk;
 use gtk::gdk::prelude::*;
 use gtk::prelude::*;
 use gtk::{Application, ApplicationWindow, Entry, ListBox};
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use std::cell::{Cell, RefCell};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::time::{SystemTime, UNIX_EPOCH};
-use strsim::jaro_winkler;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use usage_store::UsageStore;
+
+mod app_cache;
+mod autostart;
+mod bangs;
+mod bookmarks;
+mod calc;
+mod clipboard;
+mod config;
+mod custom;
+mod daemon;
+mod demo;
+mod desktop_dirs;
+mod detach;
+mod dmenu;
+mod elevate;
+mod emoji;
+mod events;
+mod exec;
+mod files;
+mod gpu;
+mod grid;
+mod keymap;
+mod launcher_file;
+mod locate;
+mod matching;
+mod open_with;
+mod pass;
+mod path_bins;
+mod placement;
+mod plugins;
+mod power;
+mod procs;
+mod profile;
+mod projects;
+mod providers;
+mod recent;
+mod running;
+mod settings;
+mod snippets;
+mod snooze;
+mod ssh;
+mod symbols;
+mod systemd;
+mod terminal;
+mod tmux;
+mod url;
+mod usage_store;
+mod watch;
 
 #[derive(Clone)]
 struct AppEntry {
     key: String,
     name: String,
+    /// The untranslated `Name` from the desktop file, when it differs from
+    /// the localized display name.
+    untranslated_name: Option<String>,
+    sort_key: String,
     icon: Option<gio::Icon>,
     app_info: gio::AppInfo,
+    hidden: bool,
+    /// Desktop environments the entry targets, from OnlyShowIn, and the
+    /// ones NotShowIn rules out.
+    desktops: Vec<String>,
+    not_desktops: Vec<String>,
+    /// Further names the app can be found by, from `entries.toml`.
+    keywords: Vec<String>,
+    /// "Flatpak" or "Snap" for apps installed that way.
+    packaging: Option<&'static str>,
+    /// Another app has the same name and program, so the packaging is
+    /// shown to tell them apart.
+    duplicate: bool,
+    /// `Terminal=true`: launched inside the configured terminal.
+    terminal: bool,
+    /// `PrefersNonDefaultGPU=true`: started on the dedicated GPU.
+    dedicated_gpu: bool,
+    /// Has an enabled entry in `~/.config/autostart`.
+    autostart: bool,
+    /// Had a window open when the launcher started.
+    running: bool,
+    /// The desktop file's `Comment`, shown under the name.
+    description: Option<String>,
+}
+
+/// Knobs for `score_apps`, passed in rather than read from the environment
+/// so the scoring stays a pure function of its inputs.
+struct Ranking {
+    current_desktops: Vec<String>,
+    foreign_desktop_penalty: i64,
+    /// Nudges apps usually launched at this time of day up a little.
+    contextual: bool,
+    /// How long until a launch stops counting as recent.
+    recency_window_secs: u64,
+    frequent_size: usize,
+    /// Apps launched fewer times than this stay out of Frequently Used.
+    frequent_min_count: u64,
+    /// Lists recent files on an empty search, after Frequently Used.
+    recent_section: bool,
+}
+
+/// Frequently Used settings given on the command line, which win over the
+/// config file.
+#[derive(Clone, Copy, Default)]
+struct FrequentOverrides {
+    size: Option<usize>,
+    min_count: Option<u64>,
+}
+
+/// Switches from the command line, carried on `LauncherState`.
+#[derive(Clone, Copy, Default)]
+struct Options {
+    /// Shows each app's launch count and last use next to its name.
+    show_usage: bool,
+    show_hidden: bool,
+    strict_exec: bool,
+    emit_queries: bool,
+    /// Shows the built-in demo dataset; launches are printed and nothing is
+    /// written to disk.
+    demo: bool,
+    /// Launches work as usual but aren't recorded.
+    private: bool,
+    path_bins: bool,
+    /// Hides the window after a launch and quits only once the app looks
+    /// to have started, coming back with the error if it didn't.
+    wait_for_startup: bool,
+    frequent: FrequentOverrides,
+    /// `--position` and `--monitor`, over the config's.
+    position: Option<placement::Position>,
+    monitor: Option<placement::Monitor>,
 }
 
-enum ViewItem {
+/// A row of the results. Providers return these for a query, the view
+/// keeps them in the order shown, and activating a row hands its item
+/// back to the provider.
+#[derive(Clone)]
+enum ResultItem {
     Header(String),
     App(AppEntry),
+    /// Offers to write a desktop entry named after the query.
+    CreateLauncher(String),
+    /// One of the desktop actions of the app above it, shown while that
+    /// app is expanded.
+    Action {
+        parent_key: String,
+        action: String,
+        label: String,
+    },
+    /// A shell command typed after `>`.
+    RunCommand {
+        command: String,
+        in_terminal: bool,
+    },
+    /// An executable found on `$PATH`.
+    PathBin(String),
+    /// Arithmetic typed into the search; `value` is None while the
+    /// expression is still incomplete.
+    Calc {
+        expression: String,
+        value: Option<f64>,
+    },
+    /// A file or directory completing a typed path.
+    File(files::FileMatch),
+    Recent(recent::RecentFile),
+    Bookmark(bookmarks::Bookmark),
+    /// A project directory, opened with the configured editor.
+    Project(projects::Project),
+    /// A host from the SSH config, connected to in a terminal.
+    SshHost(String),
+    /// A session or power command; `confirming` once a destructive one
+    /// has been activated and is asking to be activated again.
+    Power {
+        action: &'static power::Action,
+        confirming: bool,
+    },
+    Emoji(&'static emoji::Emoji),
+    Symbol(&'static symbols::Symbol),
+    /// Offers to search the web for the query when nothing else matched,
+    /// or a site for the rest of a query starting with a bang keyword.
+    WebSearch {
+        /// `WEB_ENGINE`, or the bang's site.
+        engine: String,
+        query: String,
+        url: String,
+    },
+    /// A URL typed into the search, opened with its default handler.
+    OpenUrl(String),
+    /// A line read from stdin by `--dmenu`, and its position there.
+    Line {
+        index: usize,
+        text: String,
+    },
+    /// A systemd user service, started or stopped when activated.
+    Unit(systemd::Unit),
+    /// A password-store entry, whose password is copied when activated.
+    PassEntry(String),
+    /// A line printed by a plugin script.
+    Plugin(plugins::PluginResult),
+    /// Running processes sharing a command name; `confirming` once killing
+    /// another user's process has been asked for once.
+    Process {
+        process: procs::Process,
+        confirming: bool,
+    },
+    Tmux(tmux::Target),
+    /// Text copied earlier, set as the clipboard again when activated.
+    ClipboardEntry(String),
+    /// Forgets every clipboard history entry.
+    ClearClipboard,
+    /// Text from the config, copied when activated.
+    Snippet(snippets::Snippet),
+}
+
+impl ResultItem {
+    fn is_selectable(&self) -> bool {
+        !matches!(self, ResultItem::Header(_))
+    }
+
+    fn is_action(&self) -> bool {
+        matches!(self, ResultItem::Action { .. })
+    }
+
+    /// Whether the row is something the query found, rather than a header
+    /// or a last resort: searching the web for it, or creating a launcher
+    /// named after it.
+    fn is_match(&self) -> bool {
+        match self {
+            ResultItem::Header(_) | ResultItem::CreateLauncher(_) => false,
+            ResultItem::WebSearch { engine, .. } => engine != WEB_ENGINE,
+            _ => true,
+        }
+    }
 }
 
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 struct UsageEntry {
     count: u64,
     last_used: u64,
+    /// Launches per local date (`YYYY-MM-DD`) over the last `DAILY_DAYS`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    daily: BTreeMap<String, u64>,
+    /// Launches per 4-hour slice of the local day, midnight first.
+    #[serde(default)]
+    time_bins: [u64; TIME_BINS],
+    #[serde(default)]
+    fail_count: u64,
+    #[serde(default)]
+    last_failed: u64,
 }
 
-type UsageMap = HashMap<String, UsageEntry>;
+impl UsageEntry {
+    /// Whether the most recent launch attempt failed.
+    fn last_launch_failed(&self) -> bool {
+        self.last_failed > self.last_used
+    }
+}
 
-const MAX_RESULTS: usize = 10;
-const MAX_FREQUENT: usize = 5;
+/// Subtracted from the score of an app whose last launch failed, so a
+/// broken entry stops crowding out working ones until it launches again.
+const FAILED_LAUNCH_PENALTY: i64 = 500;
 
-struct ViewState {
-    items: Vec<ViewItem>,
-    offset: usize,
-    selected_index: Option<usize>,
-}
+/// A second launch of the same app this soon after the first only
+/// refreshes its last-used time.
+const DUPLICATE_LAUNCH_SECS: u64 = 3;
 
-fn now_unix() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
-}
+/// A plugin runs once typing has paused this long.
+const PLUGIN_DEBOUNCE: Duration = Duration::from_millis(150);
 
-fn usage_path() -> Option<PathBuf> {
-    env::var_os("HOME").map(|home| {
-        PathBuf::from(home)
-            .join(".local")
-            .join("share")
-            .join("hyperfind")
-            .join("usage.json")
-    })
-}
+/// How long a copied command or desktop file path shows before hyperfind
+/// quits.
+const COPY_CONFIRM_TIMEOUT: Duration = Duration::from_secs(1);
 
-fn load_usage() -> UsageMap {
-    let Some(path) = usage_path() else {
-        return HashMap::new();
-    };
+const DAILY_DAYS: u64 = 60;
 
-    let Ok(contents) = fs::read_to_string(path) else {
-        return HashMap::new();
-    };
+const TIME_BINS: usize = 6;
 
-    serde_json::from_str(&contents).unwrap_or_default()
+type UsageMap = HashMap<String, UsageEntry>;
+
+/// A usage entry removed by a reset, kept so it can be restored until it is
+/// purged `TRASH_GRACE_SECS` after deletion.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct TrashedUsage {
+    entry: UsageEntry,
+    deleted_at: u64,
 }
 
-fn save_usage(usage: &UsageMap) {
-    let Some(path) = usage_path() else {
-        return;
-    };
+type UsageTrash = HashMap<String, TrashedUsage>;
 
-    if let Some(parent) = path.parent() {
-        if let Err(err) = fs::create_dir_all(parent) {
-            eprintln!("Failed to create usage dir: {}", err);
-            return;
-        }
-    }
+const TRASH_GRACE_SECS: u64 = 30 * 24 * 60 * 60;
 
-    let Ok(payload) = serde_json::to_string(usage) else {
-        return;
-    };
+/// Rows the results show at once; more scroll.
+const MAX_RESULTS: usize = 8;
+/// Rows are built this many at a time, past the selection.
+const RENDER_CHUNK: usize = 40;
+/// Beyond this many rows the rest are left out; searching narrows them.
+const MAX_RENDERED: usize = 500;
+/// Roughly a line of row text, and the smaller description line under it,
+/// for sizing the list.
+const LINE_HEIGHT: i32 = 20;
+const DESCRIPTION_HEIGHT: i32 = 12;
+/// What the theme pads a row with, and what compact mode leaves of it.
+const ROW_PADDING: i32 = 12;
+const COMPACT_ROW_PADDING: i32 = 4;
+/// The default `icon_size`.
+const ICON_SIZE: i32 = 20;
+/// Grid cells show icons this many times `icon_size`.
+const GRID_ICON_SCALE: i32 = 2;
+/// Lines of grid cells shown at once; more scroll.
+const GRID_LINES: i32 = 3;
+/// How long the rows have to stay the same before the window is resized
+/// to fit them.
+const FIT_DELAY: Duration = Duration::from_millis(60);
+/// How wide the bar's search is, in characters.
+const BAR_ENTRY_CHARS: i32 = 30;
+const ICON_DEADLINE: Duration = Duration::from_millis(50);
+const FALLBACK_ICON: &str = "application-x-executable";
+/// Above any name match plus usage bonus.
+const EXACT_ID_SCORE: i64 = i64::MAX / 2;
+const REGEX_PREFIX: &str = "re:";
+const RUN_PREFIX: &str = ">";
+/// Subtracted from a `$PATH` executable's match score, so an app ranks
+/// above its own binary and above commands that match as well.
+const PATH_BIN_PENALTY: i64 = 300;
+/// Subtracted from a recent file's score, so a file named like an app
+/// doesn't push the app down.
+const RECENT_FILE_PENALTY: i64 = 200;
+/// Subtracted from a bookmark's score, for the same reason.
+const BOOKMARK_PENALTY: i64 = 200;
+/// Subtracted from a project's score, so an app of the same name comes
+/// first until the project has been opened a few times.
+const PROJECT_PENALTY: i64 = 100;
+/// How many files "Recent Files" lists on an empty search.
+const RECENT_SECTION_SIZE: usize = 5;
+/// Query prefixes that switch the launcher into another search mode.
+const MODE_PREFIXES: &[&str] = &[
+    REGEX_PREFIX,
+    RUN_PREFIX,
+    calc::PREFIX,
+    files::ROOT_PREFIX,
+    files::HOME_PREFIX,
+    locate::PREFIX,
+    ssh::PREFIX,
+    systemd::PREFIX,
+    pass::PREFIX,
+    procs::PREFIX,
+    tmux::PREFIX,
+    symbols::PREFIX,
+    clipboard::PREFIX,
+    snippets::PREFIX,
+    // `:snooze` is a command within the emoji prefix, checked before it.
+    emoji::PREFIX,
+];
 
-    if let Err(err) = fs::write(path, payload) {
-        eprintln!("Failed to save usage data: {}", err);
-    }
+/// `MODE_PREFIXES` and each plugin's, which compete for the same queries.
+fn mode_prefixes(plugins: &[plugins::Plugin]) -> Vec<String> {
+    MODE_PREFIXES
+        .iter()
+        .map(|prefix| prefix.to_string())
+        .chain(plugins.iter().map(plugins::Plugin::prefix))
+        .collect()
 }
 
-fn record_usage(key: &str, usage: &mut UsageMap) {
-    let entry = usage.entry(key.to_string()).or_insert(UsageEntry {
-        count: 0,
-        last_used: 0,
-    });
-    entry.count = entry.count.saturating_add(1);
-    entry.last_used = now_unix();
+struct ViewState {
+    items: Vec<ResultItem>,
+    selected_index: Option<usize>,
+    /// Which of `LauncherState::providers` the items came from.
+    provider: usize,
+    /// Numbers the first app rows on screen for Alt+number.
+    number_hints: bool,
 }
 
-fn usage_key(app: &gio::AppInfo, name: &str) -> String {
-    app.id()
-        .map(|id| id.to_string())
-        .unwrap_or_else(|| name.to_string())
+/// Pixbufs that finished loading, keyed by the icon's serialized form, plus
+/// a count of lookups that missed `ICON_DEADLINE` and fell back.
+#[derive(Default)]
+struct IconCache {
+    /// Loaded icons by name, size and scale factor, at `size * scale`
+    /// pixels.
+    pixbufs: RefCell<HashMap<(String, i32, i32), gtk::gdk_pixbuf::Pixbuf>>,
+    stalls: Cell<u32>,
+    /// Pixels square, from `RowStyle::icon_size`; 0 leaves icons out.
+    size: i32,
+    /// Lookups still running, and what to do once none are.
+    pending: Cell<u32>,
+    on_settled: RefCell<Option<Box<dyn FnOnce()>>>,
 }
 
-fn score_match(name: &str, query: &str) -> Option<i64> {
-    let query = query.trim();
-    if query.is_empty() {
-        return Some(0);
+impl IconCache {
+    fn settle_one(&self) {
+        let pending = self.pending.get().saturating_sub(1);
+        self.pending.set(pending);
+        if pending == 0
+            && let Some(on_settled) = self.on_settled.take()
+        {
+            on_settled();
+        }
     }
+}
 
-    let name_l = name.to_lowercase();
-    let query_l = query.to_lowercase();
+/// How rows are laid out, from the config. Fixed once the launcher starts.
+#[derive(Clone, Copy)]
+struct RowStyle {
+    icon_size: i32,
+    /// Less padding between rows, and no descriptions.
+    compact: bool,
+    descriptions: bool,
+}
 
-    if name_l.contains(&query_l) {
-        let bonus = 1000i64;
-        let penalty = (name_l.len() as i64 - query_l.len() as i64).max(0);
-        return Some(bonus - penalty);
+impl RowStyle {
+    fn from_config(config: &config::Config) -> Self {
+        RowStyle {
+            icon_size: config.icon_size.max(0),
+            compact: config.compact,
+            descriptions: config.descriptions && !config.compact,
+        }
     }
 
-    let score = jaro_winkler(&name_l, &query_l);
-    if score < 0.75 {
-        return None;
+    /// Pixels square for icons, larger in the grid.
+    fn icon_pixels(&self, layout: config::Layout) -> i32 {
+        match layout {
+            config::Layout::List | config::Layout::Bar => self.icon_size,
+            config::Layout::Grid => self.icon_size * GRID_ICON_SCALE,
+        }
+    }
+
+    /// Roughly how tall a grid cell is: the icon over two lines of name.
+    fn cell_height(&self) -> i32 {
+        self.icon_size * GRID_ICON_SCALE + 2 * LINE_HEIGHT + ROW_PADDING
     }
 
-    Some((score * 1000.0) as i64)
+    /// Roughly how tall an app row is, for sizing the list.
+    fn row_height(&self) -> i32 {
+        let text = if self.descriptions {
+            LINE_HEIGHT + DESCRIPTION_HEIGHT
+        } else {
+            LINE_HEIGHT
+        };
+        let padding = if self.compact {
+            COMPACT_ROW_PADDING
+        } else {
+            ROW_PADDING
+        };
+        text.max(self.icon_size) + padding
+    }
 }
 
-fn load_apps() -> Vec<AppEntry> {
-    let mut apps: Vec<AppEntry> = gio::AppInfo::all()
-        .into_iter()
-        .filter(|app| app.should_show())
-        .filter_map(|app| {
-            let name = app.display_name().to_string();
-            if name.trim().is_empty() {
-                None
-            } else {
-                let icon = app.icon();
-                let key = usage_key(&app, &name);
-                Some(AppEntry {
-                    key,
-                    name,
-                    icon,
-                    app_info: app,
-                })
-            }
-        })
-        .collect();
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
 
-    apps.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-    apps
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Puts `usage_score` on the scale of match scores: a recent app with a
+/// few dozen launches gets a few hundred points, a third of a substring
+/// match.
+const USAGE_SCALE: f64 = 100.0;
+
+/// Recency never falls below this, so apps nobody has opened within the
+/// window still rank among themselves by how often they were used.
+const MIN_RECENCY: f64 = 0.1;
+
+/// What an app's history adds to its rank: recency times a count weight.
+///
+/// Recency falls linearly from 1 for an app used at `now` to
+/// `MIN_RECENCY` once it is `window_secs` old. The count weight is
+/// `ln(1 + count)`, so each launch matters less than the one before and a
+/// huge count can't outweigh years of disuse. An app used twice today
+/// beats one used twice last month, and with equal recency the more used
+/// app wins.
+fn usage_score(entry: &UsageEntry, now: u64, window_secs: u64) -> i64 {
+    let age = now.saturating_sub(entry.last_used) as f64;
+    let fresh = 1.0 - (age / window_secs.max(1) as f64).min(1.0);
+    let recency = MIN_RECENCY + (1.0 - MIN_RECENCY) * fresh;
+    let weight = (entry.count as f64).ln_1p();
+    (recency * weight * USAGE_SCALE) as i64
 }
 
-fn clear_listbox(listbox: &ListBox) {
-    for child in listbox.children() {
-        listbox.remove(&child);
-    }
+/// Which 4-hour slice of the local day a unix time falls in.
+fn time_bin(unix: u64) -> usize {
+    let hour = gtk::glib::DateTime::from_unix_local(unix as i64).map_or(0, |time| time.hour());
+    (hour as usize / (24 / TIME_BINS)).min(TIME_BINS - 1)
 }
 
-fn usage_label_text(app: &AppEntry, usage: &UsageMap, show_usage: bool) -> String {
-    if !show_usage {
-        return app.name.clone();
+/// Up to a tenth of `base`, scaled by the share of the app's launches that
+/// happened in the current slice of the day: enough to break ties between
+/// similar apps without reordering the list.
+fn time_of_day_boost(entry: &UsageEntry, now: u64, base: i64) -> i64 {
+    let total: u64 = entry.time_bins.iter().sum();
+    if total == 0 {
+        return 0;
     }
+    let in_bin = entry.time_bins[time_bin(now)];
+    base * in_bin as i64 / total as i64 / 10
+}
 
-    if let Some(entry) = usage.get(&app.key) {
-        format!("{}  ({} uses)", app.name, entry.count)
-    } else {
-        format!("{}  (0 uses)", app.name)
-    }
+/// `usage.json`, and the trash older versions kept beside it.
+const USAGE_FILES: &[&str] = &["usage.json", "usage-trash.json"];
+
+fn usage_dir() -> PathBuf {
+    profile::dir(gtk::glib::user_data_dir().join("hyperfind"))
 }
 
-fn build_result_row(app: &AppEntry, usage: &UsageMap, show_usage: bool) -> gtk::ListBoxRow {
-    let row = gtk::ListBoxRow::new();
-    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
-    if let Some(icon) = &app.icon {
-        let image = gtk::Image::from_gicon(icon, gtk::IconSize::Menu);
-        image.set_pixel_size(20);
-        row_box.pack_start(&image, false, false, 0);
+/// Older versions always wrote to `~/.local/share` regardless of
+/// `XDG_DATA_HOME`. Moves anything found there that the new location
+/// doesn't have yet.
+fn migrate_usage_files() {
+    // Profiles are newer than the move; their files were never elsewhere.
+    if profile::current().is_some() {
+        return;
+    }
+    let Some(home) = env::var_os("HOME") else {
+        return;
+    };
+    let legacy_dir = PathBuf::from(home)
+        .join(".local")
+        .join("share")
+        .join("hyperfind");
+    let dir = usage_dir();
+    if legacy_dir != dir {
+        move_usage_files(&legacy_dir, &dir);
     }
-    let label_text = usage_label_text(app, usage, show_usage);
-    let label = gtk::Label::new(Some(&label_text));
-    label.set_xalign(0.0);
-    row_box.pack_start(&label, true, true, 0);
-    row.add(&row_box);
-    row
 }
 
-fn first_selectable_row(listbox: &ListBox) -> Option<gtk::ListBoxRow> {
-    for child in listbox.children() {
-        if let Ok(row) = child.downcast::<gtk::ListBoxRow>() {
-            if row.is_selectable() {
-                return Some(row);
-            }
+/// Moves each usage file from `legacy_dir` into `dir`, unless `dir`
+/// already has one of that name.
+fn move_usage_files(legacy_dir: &Path, dir: &Path) {
+    for file_name in USAGE_FILES {
+        let legacy = legacy_dir.join(file_name);
+        let path = dir.join(file_name);
+        if !legacy.exists() || path.exists() {
+            continue;
+        }
+
+        let moved = fs::create_dir_all(dir).and_then(|_| {
+            // Rename fails across filesystems; fall back to copying.
+            fs::rename(&legacy, &path)
+                .or_else(|_| fs::copy(&legacy, &path).and_then(|_| fs::remove_file(&legacy)))
+        });
+        match moved {
+            Ok(()) => eprintln!("Moved {} to {}", legacy.display(), path.display()),
+            Err(err) => eprintln!("Failed to move {}: {}", legacy.display(), err),
         }
     }
-    None
 }
 
-fn build_section_row(title: &str) -> gtk::ListBoxRow {
-    let row = gtk::ListBoxRow::new();
-    row.set_selectable(false);
-    row.set_activatable(false);
-    let label = gtk::Label::new(None);
-    label.set_markup(&format!("<b>{}</b>", title));
-    label.set_xalign(0.0);
-    label.set_margin_top(0);
-    label.set_margin_bottom(0);
-    row.set_margin_top(0);
-    row.set_margin_bottom(0);
-    row.add(&label);
-    row
+fn ignored_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("ignored.txt"))
 }
 
-fn first_selectable_index(items: &[ViewItem]) -> Option<usize> {
-    items
-        .iter()
-        .position(|item| matches!(item, ViewItem::App(_)))
+fn parse_ignored(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
 }
 
-fn next_selectable_index(items: &[ViewItem], start: usize, direction: i32) -> Option<usize> {
-    let mut index = start as i32 + direction;
-    while index >= 0 && (index as usize) < items.len() {
-        if matches!(items[index as usize], ViewItem::App(_)) {
-            return Some(index as usize);
-        }
-        index += direction;
-    }
-    None
-}
+fn load_ignored() -> Vec<String> {
+    let Some(path) = ignored_path() else {
+        return Vec::new();
+    };
 
-fn ensure_visible(view_state: &mut ViewState) {
-    let Some(selected) = view_state.selected_index else {
-        view_state.offset = 0;
-        return;
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
     };
 
-    if view_state.items.is_empty() {
-        view_state.offset = 0;
+    parse_ignored(&contents)
+}
+
+fn append_ignored(key: &str) {
+    let Some(path) = ignored_path() else {
         return;
-    }
+    };
 
-    if view_state.items.len() <= MAX_RESULTS {
-        view_state.offset = 0;
+    if let Some(parent) = path.parent()
+        && let Err(err) = fs::create_dir_all(parent)
+    {
+        eprintln!("Failed to create config dir: {}", err);
         return;
     }
 
-    if selected < view_state.offset {
-        view_state.offset = selected;
-        return;
+    let file = fs::OpenOptions::new().create(true).append(true).open(path);
+    if let Err(err) = file.and_then(|mut file| writeln!(file, "{}", key)) {
+        eprintln!("Failed to update ignore list: {}", err);
     }
+}
 
-    loop {
-        let mut app_count = 0;
-        let mut last_visible = None;
-        for (idx, item) in view_state.items.iter().enumerate().skip(view_state.offset) {
-            if matches!(item, ViewItem::App(_)) {
-                if app_count >= MAX_RESULTS {
-                    break;
-                }
-                app_count += 1;
-            }
-            last_visible = Some(idx);
-            if app_count >= MAX_RESULTS {
-                break;
-            }
-        }
+/// Shell-style matching where `*` is any run of characters and `?` is any
+/// single character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
 
-        let Some(last_visible) = last_visible else {
-            break;
-        };
-        if selected <= last_visible {
-            break;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
         }
-        view_state.offset = view_state.offset.saturating_add(1);
     }
+
+    pattern[p..].iter().all(|c| *c == '*')
 }
 
-fn render_view(
-    listbox: &ListBox,
-    view_state: &ViewState,
-    results: &Rc<RefCell<Vec<Option<AppEntry>>>>,
-    usage: &UsageMap,
-    show_usage: bool,
-) {
-    clear_listbox(listbox);
+fn is_ignored(key: &str, ignored: &[String]) -> bool {
+    ignored.iter().any(|pattern| glob_match(pattern, key))
+}
 
-    let mut results_mut = results.borrow_mut();
-    results_mut.clear();
+/// `path` with `suffix` appended to its file name, e.g. `usage.json.bak`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
 
-    let mut app_count = 0;
-    let mut visible_indices = Vec::new();
-    for (idx, item) in view_state.items.iter().enumerate().skip(view_state.offset) {
-        if matches!(item, ViewItem::App(_)) && app_count >= MAX_RESULTS {
-            break;
-        }
-        match item {
-            ViewItem::Header(title) => {
-                results_mut.push(None);
-                let row = build_section_row(title);
-                listbox.add(&row);
-            }
-            ViewItem::App(app) => {
-                results_mut.push(Some(app.clone()));
-                let row = build_result_row(app, usage, show_usage);
-                listbox.add(&row);
-                app_count += 1;
-            }
-        }
-        visible_indices.push(idx);
+/// Replaces `path` without ever leaving it half-written: the contents go to
+/// a synced temp file that is renamed over the original. The previous file
+/// is kept as a `.bak` first.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let tmp_path = with_suffix(path, ".tmp");
+    let mut tmp = fs::File::create(&tmp_path)?;
+    tmp.write_all(contents)?;
+    tmp.sync_all()?;
+    drop(tmp);
+
+    if path.exists() {
+        fs::copy(path, with_suffix(path, ".bak"))?;
     }
+    fs::rename(&tmp_path, path)?;
 
-    listbox.show_all();
-    if let Some(selected) = view_state.selected_index {
-        if let Some(row_index) = visible_indices.iter().position(|idx| *idx == selected) {
-            if let Some(row) = listbox.row_at_index(row_index as i32) {
-                listbox.select_row(Some(&row));
-            }
-        }
+    // The rename itself is only durable once the directory is synced.
+    if let Some(parent) = path.parent() {
+        fs::File::open(parent)?.sync_all()?;
     }
+    Ok(())
 }
 
-fn build_view_items(apps: &[AppEntry], query: &str, usage: &UsageMap) -> Vec<ViewItem> {
-    if !query.trim().is_empty() {
-        let mut scored = score_apps(apps, query, usage);
-        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
-        return scored
-            .into_iter()
-            .map(|(_, app)| ViewItem::App(app.clone()))
-            .collect();
+/// The `usage.json` format. Version 1 was the bare map with no envelope;
+/// version 2 kept the trash in a file of its own.
+const USAGE_VERSION: u64 = 3;
+
+#[derive(Serialize)]
+struct UsageFile<'a> {
+    version: u64,
+    entries: &'a UsageMap,
+    trash: &'a UsageTrash,
+}
+
+/// What a usage file holds.
+#[derive(Default)]
+struct StoredUsage {
+    entries: UsageMap,
+    trash: UsageTrash,
+}
+
+fn usage_file_contents(
+    usage: &UsageMap,
+    trash: &UsageTrash,
+    pretty: bool,
+) -> serde_json::Result<String> {
+    let file = UsageFile {
+        version: USAGE_VERSION,
+        entries: usage,
+        trash,
+    };
+    if pretty {
+        serde_json::to_string_pretty(&file)
+    } else {
+        serde_json::to_string(&file)
     }
+}
 
-    let mut frequent: Vec<(i64, &AppEntry)> = apps
-        .iter()
-        .filter_map(|app| {
-            usage.get(&app.key).map(|entry| {
-                let score = (entry.count as i64 * 1000) + entry.last_used as i64;
-                (score, app)
-            })
-        })
-        .collect();
-    frequent.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
-    let frequent: Vec<&AppEntry> = frequent
-        .into_iter()
-        .map(|(_, app)| app)
-        .take(MAX_FREQUENT)
-        .collect();
+enum UsageFileError {
+    Io(io::Error),
+    Corrupt(serde_json::Error),
+    /// Written by a newer hyperfind than this one.
+    Unsupported(u64),
+}
 
-    let mut items = Vec::new();
-    if !frequent.is_empty() {
-        items.push(ViewItem::Header("Frequently Used".to_string()));
-        for app in &frequent {
-            items.push(ViewItem::App((*app).clone()));
+impl std::fmt::Display for UsageFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UsageFileError::Io(err) => write!(f, "{}", err),
+            UsageFileError::Corrupt(err) => write!(f, "{}", err),
+            UsageFileError::Unsupported(version) => write!(
+                f,
+                "format version {} is newer than this hyperfind supports ({})",
+                version, USAGE_VERSION
+            ),
         }
     }
+}
 
-    items.push(ViewItem::Header("All Apps".to_string()));
+/// Parses any known version of the usage file, migrating older ones.
+fn parse_usage(contents: &str) -> Result<StoredUsage, UsageFileError> {
+    let value: serde_json::Value =
+        serde_json::from_str(contents).map_err(UsageFileError::Corrupt)?;
+    // Version 1 maps app keys to entry objects, so a numeric `version`
+    // can only come from the envelope.
+    let version = match value.get("version") {
+        Some(version) => version.as_u64(),
+        None => Some(1),
+    };
 
-    let mut frequent_keys = HashSet::new();
-    for app in frequent {
-        frequent_keys.insert(app.key.clone());
+    match version {
+        Some(1) => Ok(StoredUsage {
+            entries: serde_json::from_value(value).map_err(UsageFileError::Corrupt)?,
+            trash: UsageTrash::new(),
+        }),
+        // Version 2 is version 3 without a trash.
+        Some(2 | USAGE_VERSION) => {
+            let section = |name| {
+                value
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::Value::Object(Default::default()))
+            };
+            Ok(StoredUsage {
+                entries: serde_json::from_value(section("entries"))
+                    .map_err(UsageFileError::Corrupt)?,
+                trash: serde_json::from_value(section("trash")).map_err(UsageFileError::Corrupt)?,
+            })
+        }
+        Some(version) if version > USAGE_VERSION => Err(UsageFileError::Unsupported(version)),
+        _ => Err(UsageFileError::Corrupt(serde::de::Error::custom(
+            "invalid format version",
+        ))),
     }
+}
+
+fn read_usage_file(path: &Path) -> Result<StoredUsage, UsageFileError> {
+    let contents = fs::read_to_string(path).map_err(UsageFileError::Io)?;
+    parse_usage(&contents)
+}
 
-    for app in apps.iter().filter(|app| !frequent_keys.contains(&app.key)) {
-        items.push(ViewItem::App(app.clone()));
+/// The usage in `path`, or in its backup when `path` is unreadable.
+fn load_usage_file(path: &Path) -> StoredUsage {
+    if !path.exists() {
+        return StoredUsage::default();
     }
 
-    items
+    match read_usage_file(path) {
+        Ok(usage) => usage,
+        Err(err @ UsageFileError::Unsupported(_)) => {
+            // Don't let the next save clobber a newer version's data.
+            let unsupported = with_suffix(path, ".unsupported");
+            eprintln!("Not loading {}: {}", path.display(), err);
+            match fs::copy(path, &unsupported) {
+                Ok(_) => eprintln!("Kept a copy at {}", unsupported.display()),
+                Err(err) => eprintln!("Failed to copy it to {}: {}", unsupported.display(), err),
+            }
+            StoredUsage::default()
+        }
+        Err(err) => {
+            let backup = with_suffix(path, ".bak");
+            eprintln!("Failed to read {}: {}", path.display(), err);
+            match read_usage_file(&backup) {
+                Ok(usage) => {
+                    eprintln!("Recovered usage data from {}", backup.display());
+                    usage
+                }
+                Err(err) => {
+                    eprintln!("Failed to read {}: {}", backup.display(), err);
+                    StoredUsage::default()
+                }
+            }
+        }
+    }
 }
 
-fn score_apps<'a>(apps: &'a [AppEntry], query: &str, usage: &UsageMap) -> Vec<(i64, &'a AppEntry)> {
-    if query.trim().is_empty() {
-        return apps
-            .iter()
-            .filter_map(|app| {
-                usage.get(&app.key).map(|entry| {
-                    let score = (entry.count as i64 * 1000) + entry.last_used as i64;
-                    (score, app)
-                })
-            })
-            .collect();
+fn report_save_error(result: io::Result<()>) {
+    if let Err(err) = result {
+        eprintln!("Failed to save usage data: {}", err);
     }
+}
 
-    apps.iter()
-        .filter_map(|app| {
-            let mut score = score_match(&app.name, query)?;
-            if let Some(entry) = usage.get(&app.key) {
-                score += entry.count as i64 * 10;
-            }
-            Some((score, app))
-        })
-        .collect()
+/// Like `report_save_error`, also keeping the message for the error bar.
+fn warn_save_error(result: io::Result<()>, warnings: &RefCell<Vec<String>>) {
+    if let Err(err) = result {
+        eprintln!("Failed to save usage data: {}", err);
+        warnings
+            .borrow_mut()
+            .push(format!("Failed to save usage data: {}", err));
+    }
 }
 
-fn update_results(listbox: &ListBox, state: &LauncherState, query: &str, show_usage: bool) {
-    let usage_borrow = state.usage.borrow();
-    let mut view_state = state.view.borrow_mut();
-    view_state.items = build_view_items(&state.apps, query, &usage_borrow);
-    view_state.offset = 0;
-    view_state.selected_index = first_selectable_index(&view_state.items);
-    render_view(
-        listbox,
-        &view_state,
-        &state.results,
-        &usage_borrow,
-        show_usage,
-    );
+fn pins_path() -> PathBuf {
+    usage_dir().join("pins.json")
 }
 
-fn move_selection(listbox: &ListBox, state: &LauncherState, direction: i32, show_usage: bool) {
-    let usage_borrow = state.usage.borrow();
-    let mut view_state = state.view.borrow_mut();
-    let Some(current) = view_state.selected_index else {
-        view_state.selected_index = first_selectable_index(&view_state.items);
-        ensure_visible(&mut view_state);
-        render_view(
-            listbox,
-            &view_state,
-            &state.results,
-            &usage_borrow,
-            show_usage,
-        );
-        return;
-    };
+fn frequent_excluded_path() -> PathBuf {
+    usage_dir().join("frequent-excluded.json")
+}
 
-    if let Some(next) = next_selectable_index(&view_state.items, current, direction) {
-        view_state.selected_index = Some(next);
-        ensure_visible(&mut view_state);
-        render_view(
-            listbox,
-            &view_state,
-            &state.results,
-            &usage_borrow,
-            show_usage,
-        );
-    }
+/// Per-app choices about the empty-query sections, each list kept in its
+/// own file next to the usage data.
+#[derive(Default)]
+struct Curation {
+    /// Keys shown under "Pinned", in the order they were pinned.
+    pinned: Vec<String>,
+    /// Keys kept out of Frequently Used; they can still be searched.
+    frequent_excluded: Vec<String>,
 }
 
-fn launch_from_index(
-    index: i32,
-    results: &Rc<RefCell<Vec<Option<AppEntry>>>>,
-    usage: &Rc<RefCell<UsageMap>>,
-) -> bool {
-    if index < 0 {
-        return false;
+fn load_curation() -> Curation {
+    Curation {
+        pinned: load_keys(&pins_path()),
+        frequent_excluded: load_keys(&frequent_excluded_path()),
     }
+}
 
-    let results = results.borrow();
-    let index = index as usize;
-    let Some(Some(app)) = results.get(index) else {
-        return false;
+fn load_keys(path: &Path) -> Vec<String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
     };
 
-    if let Err(err) = app
-        .app_info
-        .launch(&[], Option::<&gio::AppLaunchContext>::None)
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+fn save_keys(path: &Path, keys: &[String]) {
+    if let Some(parent) = path.parent()
+        && let Err(err) = fs::create_dir_all(parent)
     {
-        eprintln!("Failed to launch {}: {}", app.name, err);
-        return false;
+        eprintln!("Failed to create usage dir: {}", err);
+        return;
     }
 
-    {
-        let mut usage_mut = usage.borrow_mut();
-        record_usage(&app.key, &mut usage_mut);
-        save_usage(&usage_mut);
+    let Ok(payload) = serde_json::to_string(keys) else {
+        return;
+    };
+
+    if let Err(err) = write_atomic(path, payload.as_bytes()) {
+        eprintln!("Failed to save {}: {}", path.display(), err);
+    }
+}
+
+/// Adds a key at the end of the list, or removes it if already there.
+fn toggle_key(key: &str, keys: &mut Vec<String>) {
+    if let Some(index) = keys.iter().position(|listed| listed == key) {
+        keys.remove(index);
+    } else {
+        keys.push(key.to_string());
+    }
+}
+
+fn purge_trash(trash: &mut UsageTrash, now: u64) {
+    trash.retain(|_, trashed| now.saturating_sub(trashed.deleted_at) < TRASH_GRACE_SECS);
+}
+
+/// Moves usage for apps that aren't installed into the trash, once unused
+/// for `grace_secs`, so an app that is briefly missing (an unmounted
+/// flatpak, say) keeps its history. Usage of anything else is kept.
+/// Returns the removed keys, sorted.
+fn prune_usage(
+    usage: &mut UsageMap,
+    trash: &mut UsageTrash,
+    installed: &HashSet<&str>,
+    now: u64,
+    grace_secs: u64,
+) -> Vec<String> {
+    let mut removed: Vec<String> = usage
+        .iter()
+        .filter(|(key, entry)| {
+            is_app_usage_key(key)
+                && !installed.contains(action_parent(key))
+                && now.saturating_sub(entry.last_used) >= grace_secs
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+    removed.sort();
+    for key in &removed {
+        trash_usage(key, usage, trash, now);
     }
+    removed
+}
+
+/// How the usage keys of recent files, power actions, bookmarks, tmux
+/// targets, projects and settings panels start.
+const OTHER_USAGE_PREFIXES: &[&str] = &["file:", "power:", "bm:", "tmux:", "project:", "settings:"];
+
+/// Whether a usage key is an app's or one of its actions', rather than
+/// another source's, which the installed apps say nothing about.
+fn is_app_usage_key(key: &str) -> bool {
+    key != url::USAGE_KEY
+        && !OTHER_USAGE_PREFIXES
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+}
+
+/// Usage of a desktop action is kept under `<app key>#<action>`.
+fn action_key(parent_key: &str, action: &str) -> String {
+    format!("{}#{}", parent_key, action)
+}
+
+/// The app a usage key belongs to, looking through action keys.
+fn action_parent(key: &str) -> &str {
+    key.split_once('#').map_or(key, |(parent, _)| parent)
+}
+
+/// Moves a key's usage into the trash. Returns false if it had no usage.
+fn trash_usage(key: &str, usage: &mut UsageMap, trash: &mut UsageTrash, now: u64) -> bool {
+    let Some(entry) = usage.remove(key) else {
+        return false;
+    };
 
+    trash.insert(
+        key.to_string(),
+        TrashedUsage {
+            entry,
+            deleted_at: now,
+        },
+    );
     true
 }
 
-#[derive(Clone)]
-struct LauncherState {
-    apps: Rc<Vec<AppEntry>>,
-    results: Rc<RefCell<Vec<Option<AppEntry>>>>,
-    usage: Rc<RefCell<UsageMap>>,
-    view: Rc<RefCell<ViewState>>,
+/// Moves a key's usage back out of the trash, folding in anything recorded
+/// since it was deleted. Returns false if nothing was trashed for the key.
+fn restore_usage(key: &str, usage: &mut UsageMap, trash: &mut UsageTrash) -> bool {
+    let Some(trashed) = trash.remove(key) else {
+        return false;
+    };
+
+    let entry = usage.entry(key.to_string()).or_default();
+    entry.count = entry.count.saturating_add(trashed.entry.count);
+    entry.last_used = entry.last_used.max(trashed.entry.last_used);
+    add_daily(&mut entry.daily, &trashed.entry.daily);
+    add_time_bins(&mut entry.time_bins, &trashed.entry.time_bins);
+    entry.fail_count = entry.fail_count.saturating_add(trashed.entry.fail_count);
+    entry.last_failed = entry.last_failed.max(trashed.entry.last_failed);
+    true
 }
 
-impl LauncherState {
-    fn new() -> Self {
-        Self {
-            apps: Rc::new(load_apps()),
-            results: Rc::new(RefCell::new(Vec::new())),
-            usage: Rc::new(RefCell::new(load_usage())),
-            view: Rc::new(RefCell::new(ViewState {
-                items: Vec::new(),
-                offset: 0,
-                selected_index: None,
-            })),
-        }
+/// Whether a launch at `now` repeats the one recorded in `entry`, as when
+/// Enter is double-tapped or two signals fire for the same activation.
+fn is_duplicate_launch(entry: &UsageEntry, now: u64) -> bool {
+    entry.last_used != 0 && now.saturating_sub(entry.last_used) < DUPLICATE_LAUNCH_SECS
+}
+
+fn record_usage(key: &str, usage: &mut UsageMap, now: u64) {
+    let entry = usage.entry(key.to_string()).or_default();
+    let duplicate = is_duplicate_launch(entry, now);
+    entry.last_used = now;
+    if duplicate {
+        return;
     }
+    entry.count = entry.count.saturating_add(1);
+    *entry.daily.entry(local_date(now)).or_default() += 1;
+    entry.time_bins[time_bin(now)] += 1;
+    let oldest = local_date(now.saturating_sub(DAILY_DAYS * DAY_SECS));
+    entry.daily.retain(|date, _| *date > oldest);
 }
 
-fn configure_settings() {
-    if let Some(settings) = gtk::Settings::default() {
-        settings.set_property("gtk-error-bell", &false);
+fn record_failure(key: &str, usage: &mut UsageMap) {
+    let entry = usage.entry(key.to_string()).or_default();
+    entry.fail_count = entry.fail_count.saturating_add(1);
+    entry.last_failed = now_unix();
+}
+
+/// The local calendar date of a unix time as `YYYY-MM-DD`, which sorts
+/// chronologically as a string.
+fn local_date(unix: u64) -> String {
+    gtk::glib::DateTime::from_unix_local(unix as i64)
+        .and_then(|time| time.format("%F"))
+        .map(|date| date.to_string())
+        .unwrap_or_default()
+}
+
+fn add_time_bins(into: &mut [u64; TIME_BINS], from: &[u64; TIME_BINS]) {
+    for (into, from) in into.iter_mut().zip(from) {
+        *into += from;
     }
 }
 
-fn build_listbox() -> ListBox {
-    let listbox = ListBox::new();
-    listbox.set_selection_mode(gtk::SelectionMode::Single);
-    listbox
+fn add_daily(into: &mut BTreeMap<String, u64>, from: &BTreeMap<String, u64>) {
+    for (date, count) in from {
+        *into.entry(date.clone()).or_default() += count;
+    }
 }
 
-fn build_container(title: &gtk::Label, entry: &Entry, listbox: &ListBox) -> gtk::Box {
-    let container = gtk::Box::new(gtk::Orientation::Vertical, 6);
-    container.set_margin_top(8);
-    container.set_margin_bottom(8);
-    container.set_margin_start(10);
-    container.set_margin_end(10);
-    container.pack_start(title, false, false, 0);
-    container.pack_start(entry, false, false, 0);
-    container.pack_start(listbox, true, true, 0);
-    container
+/// Launches on the `days` local dates up to and including `now`'s.
+fn launches_within(entry: &UsageEntry, now: u64, days: u64) -> u64 {
+    let oldest = local_date(now.saturating_sub(days * DAY_SECS));
+    entry
+        .daily
+        .iter()
+        .filter(|(date, _)| **date > oldest)
+        .map(|(_, count)| count)
+        .sum()
 }
 
-fn apply_window_hints(window: &ApplicationWindow) {
-    window.set_type_hint(gdk::WindowTypeHint::PopupMenu);
-    window.set_skip_taskbar_hint(true);
-    window.set_skip_pager_hint(true);
-    window.set_accept_focus(true);
-    window.set_focus_on_map(true);
+fn usage_key(app: &gio::AppInfo, name: &str) -> String {
+    desktop_id(app).unwrap_or_else(|| name.to_string())
 }
 
-fn connect_override_redirect(window: &ApplicationWindow) {
-    window.connect_realize(|window| {
-        if let Some(gdk_window) = window.window() {
-            gdk_window.set_override_redirect(true);
-        }
-    });
+/// Entries read from a `--desktop-dir` have no id of their own, so their
+/// file name stands in, as it would for one found on the XDG path.
+fn desktop_id(app: &gio::AppInfo) -> Option<String> {
+    if let Some(id) = app.id() {
+        return Some(id.to_string());
+    }
+    let path = app.downcast_ref::<gio::DesktopAppInfo>()?.filename()?;
+    Some(path.file_name()?.to_string_lossy().into_owned())
 }
 
-fn connect_keyboard_grab(window: &ApplicationWindow) {
-    window.connect_map_event(|window, _| {
-        if let Some(gdk_window) = window.window() {
-            gdk_window.focus(gdk::ffi::GDK_CURRENT_TIME as u32);
-            if let Some(display) = gdk::Display::default() {
-                if let Some(seat) = display.default_seat() {
-                    let _ = seat.grab(
-                        &gdk_window,
-                        gdk::SeatCapabilities::KEYBOARD,
-                        true,
-                        None,
-                        None,
-                        None,
-                    );
-                }
-            }
-        }
-        gtk::glib::Propagation::Proceed
-    });
+fn is_hidden_entry(app: &gio::AppInfo) -> bool {
+    app.downcast_ref::<gio::DesktopAppInfo>()
+        .is_some_and(|desktop| desktop.is_hidden())
 }
 
-fn connect_keyboard_ungrab(window: &ApplicationWindow) {
-    window.connect_unmap_event(|_, _| {
-        if let Some(display) = gdk::Display::default() {
-            if let Some(seat) = display.default_seat() {
-                seat.ungrab();
+fn untranslated_name(app: &gio::AppInfo, name: &str) -> Option<String> {
+    let desktop = app.downcast_ref::<gio::DesktopAppInfo>()?;
+    let untranslated = desktop.string("Name")?.to_string();
+    if untranslated.trim().is_empty() || untranslated.to_lowercase() == name.to_lowercase() {
+        return None;
+    }
+    Some(untranslated)
+}
+
+/// Returns the missing program when a desktop entry can't run: its TryExec
+/// isn't installed or, with `strict_exec`, the program its Exec line starts
+/// isn't either.
+fn missing_program(app: &gio::AppInfo, strict_exec: bool) -> Option<String> {
+    let desktop = app.downcast_ref::<gio::DesktopAppInfo>()?;
+    let path_var = env::var_os("PATH");
+
+    let program = match desktop.string("TryExec") {
+        Some(try_exec) => try_exec.to_string(),
+        None if strict_exec => exec::exec_program(&desktop.string("Exec")?)?,
+        None => return None,
+    };
+
+    match exec::resolve_program(&program, path_var.as_deref()) {
+        Some(_) => None,
+        None => Some(program),
+    }
+}
+
+fn current_desktops() -> Vec<String> {
+    env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|desktop| !desktop.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn split_desktop_list(value: Option<gtk::glib::GString>) -> Vec<String> {
+    value
+        .map(|value| {
+            value
+                .split(';')
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn packaging(app: &gio::AppInfo) -> Option<&'static str> {
+    let desktop = app.downcast_ref::<gio::DesktopAppInfo>()?;
+    let path = desktop.filename()?;
+    let path = path.to_string_lossy();
+    if desktop.string("X-Flatpak").is_some() || path.contains("/flatpak/") {
+        Some("Flatpak")
+    } else if desktop.string("X-SnapInstanceName").is_some() || path.contains("/snapd/") {
+        Some("Snap")
+    } else {
+        None
+    }
+}
+
+/// Apps with the same key are one program installed more than once: the
+/// lowercased name plus the program it runs. Flatpaks and snaps run
+/// through a wrapper, so their app name stands in for the program.
+fn duplicate_key(app: &AppEntry) -> (String, String) {
+    let desktop = app.app_info.downcast_ref::<gio::DesktopAppInfo>();
+    let string = |key| desktop.and_then(|desktop| desktop.string(key));
+    let program = if let Some(id) = string("X-Flatpak") {
+        id.rsplit('.').next().unwrap_or_default().to_string()
+    } else if let Some(name) = string("X-SnapInstanceName") {
+        name.to_string()
+    } else {
+        let executable = app.app_info.executable();
+        executable
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+    };
+    (app.name.trim().to_lowercase(), program.to_lowercase())
+}
+
+/// Marks apps installed more than once so their packaging is shown, or
+/// keeps only one of each: the most launched, then a native install, then
+/// whichever sorts first.
+fn merge_duplicates(
+    mut apps: Vec<AppEntry>,
+    usage: &UsageMap,
+    mode: config::DuplicateApps,
+) -> Vec<AppEntry> {
+    let mut groups: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    for (index, app) in apps.iter().enumerate() {
+        groups.entry(duplicate_key(app)).or_default().push(index);
+    }
+    let groups = groups.into_values().filter(|group| group.len() > 1);
+
+    match mode {
+        config::DuplicateApps::Badge => {
+            for index in groups.flatten() {
+                apps[index].duplicate = true;
             }
+            apps
         }
-        gtk::glib::Propagation::Proceed
-    });
+        config::DuplicateApps::Collapse => {
+            let mut dropped = HashSet::new();
+            for group in groups {
+                let count =
+                    |index: usize| usage.get(&apps[index].key).map_or(0, |entry| entry.count);
+                let kept = group.iter().copied().max_by(|a, b| {
+                    count(*a)
+                        .cmp(&count(*b))
+                        .then_with(|| {
+                            apps[*b]
+                                .packaging
+                                .is_some()
+                                .cmp(&apps[*a].packaging.is_some())
+                        })
+                        .then_with(|| b.cmp(a))
+                });
+                dropped.extend(group.into_iter().filter(|index| Some(*index) != kept));
+            }
+            apps.into_iter()
+                .enumerate()
+                .filter(|(index, _)| !dropped.contains(index))
+                .map(|(_, app)| app)
+                .collect()
+        }
+    }
 }
 
-fn configure_window(window: &ApplicationWindow) {
-    apply_window_hints(window);
-    connect_override_redirect(window);
-    connect_keyboard_grab(window);
-    connect_keyboard_ungrab(window);
+/// Whether an app is aimed at some other desktop environment than the one
+/// running. Apps with no desktop hints are never foreign.
+fn is_foreign(app: &AppEntry, current: &[String]) -> bool {
+    if current.is_empty() {
+        return false;
+    }
+    let is_current = |desktop: &String| {
+        current
+            .iter()
+            .any(|running| running.eq_ignore_ascii_case(desktop))
+    };
+    if app.not_desktops.iter().any(is_current) {
+        return true;
+    }
+    !app.desktops.is_empty() && !app.desktops.iter().any(is_current)
 }
 
-fn build_window(app: &Application, container: &gtk::Box) -> ApplicationWindow {
-    let window = ApplicationWindow::builder()
-        .application(app)
-        .decorated(false)
-        .default_width(600)
-        .default_height(320)
-        .resizable(false)
-        .build();
+fn build_app_entry(app: gio::AppInfo) -> Option<AppEntry> {
+    let name = app.display_name().to_string();
+    if name.trim().is_empty() {
+        return None;
+    }
+    let key = usage_key(&app, &name);
+    let icon = app.icon();
+    let hidden = !app.should_show();
+    let untranslated_name = untranslated_name(&app, &name);
+    let packaging = packaging(&app);
+    let desktop = app.downcast_ref::<gio::DesktopAppInfo>();
+    let desktops = split_desktop_list(desktop.and_then(|desktop| desktop.string("OnlyShowIn")));
+    let not_desktops = split_desktop_list(desktop.and_then(|desktop| desktop.string("NotShowIn")));
+    let terminal = desktop.is_some_and(|desktop| desktop.boolean("Terminal"));
+    let dedicated_gpu = desktop.is_some_and(gpu::prefers_dedicated);
+    let description = app
+        .description()
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    Some(AppEntry {
+        key,
+        name,
+        untranslated_name,
+        sort_key: String::new(),
+        icon,
+        app_info: app,
+        hidden,
+        desktops,
+        not_desktops,
+        keywords: Vec::new(),
+        packaging,
+        duplicate: false,
+        terminal,
+        dedicated_gpu,
+        autostart: false,
+        running: false,
+        description,
+    })
+}
 
-    window.set_position(gtk::WindowPosition::Center);
-    window.add(container);
-    configure_window(&window);
+fn load_apps(ignored: &[String], show_hidden: bool, strict_exec: bool) -> Vec<AppEntry> {
+    let mut infos = gio::AppInfo::all();
+    // Extra directories take precedence over the XDG ones, as a directory
+    // earlier on `XDG_DATA_DIRS` would.
+    for (id, info) in desktop_dirs::load() {
+        infos.retain(|app| desktop_id(app).as_deref() != Some(id.as_str()));
+        infos.push(info);
+    }
+    let apps: Vec<AppEntry> = infos
+        .into_iter()
+        .filter(|app| {
+            if show_hidden {
+                !is_hidden_entry(app)
+            } else {
+                app.should_show()
+            }
+        })
+        .filter(|app| {
+            let Some(program) = missing_program(app, strict_exec) else {
+                return true;
+            };
+            eprintln!(
+                "Skipping {}: {} is not installed",
+                app.display_name(),
+                program
+            );
+            false
+        })
+        .filter_map(build_app_entry)
+        .chain(custom::load())
+        .filter(|entry| !is_ignored(&entry.key, ignored))
+        .collect();
+    let autostarted = autostart::load();
+    let apps = apps
+        .into_iter()
+        .map(|mut entry| {
+            entry.autostart = autostarted.contains(&autostart::file_name(&entry.key));
+            entry
+        })
+        .collect();
 
-    window
+    sort_apps(apps)
 }
 
-fn refresh_results(listbox: &ListBox, state: &LauncherState, show_usage: bool) {
-    update_results(listbox, state, "", show_usage);
+fn build_panel_entry(panel: settings::Panel, ignored: &[String]) -> Option<AppEntry> {
+    let key = panel.usage_key();
+    if is_ignored(&key, ignored) {
+        return None;
+    }
+    let app_info = gio::AppInfo::create_from_commandline(
+        &panel.command,
+        Some(&panel.name),
+        gio::AppInfoCreateFlags::NONE,
+    )
+    .ok()?;
+    let icons: Vec<&str> = panel.icons.iter().map(String::as_str).collect();
+    Some(AppEntry {
+        key,
+        sort_key: panel.name.to_lowercase(),
+        name: panel.name,
+        untranslated_name: None,
+        icon: Some(gio::ThemedIcon::from_names(&icons).upcast()),
+        app_info,
+        hidden: false,
+        desktops: Vec::new(),
+        not_desktops: Vec::new(),
+        keywords: panel.keywords,
+        packaging: None,
+        duplicate: false,
+        terminal: false,
+        dedicated_gpu: false,
+        autostart: false,
+        running: false,
+        description: None,
+    })
 }
 
-fn focus_entry_later(entry: &Entry) {
-    let entry_clone = entry.clone();
-    gtk::glib::idle_add_local_once(move || {
-        entry_clone.grab_focus();
-    });
+fn opened_panel(app: &AppEntry) -> Option<String> {
+    let commandline = app.app_info.commandline()?;
+    settings::panel_of(&commandline.to_string_lossy()).map(str::to_string)
 }
 
-fn connect_listbox_activation(listbox: &ListBox, state: &LauncherState, app: &Application) {
-    let results_for_activate = Rc::clone(&state.results);
-    let usage_for_activate = Rc::clone(&state.usage);
-    let app_for_activate = app.clone();
-    listbox.connect_row_activated(move |_, row| {
-        if launch_from_index(row.index(), &results_for_activate, &usage_for_activate) {
-            app_for_activate.quit();
+/// Adds settings panels to the apps in name order, leaving out those an
+/// installed desktop entry already opens.
+fn add_settings_panels(apps: &mut Vec<AppEntry>, panels: &[AppEntry]) {
+    let opened: HashSet<String> = apps.iter().filter_map(opened_panel).collect();
+    for panel in panels {
+        if opened_panel(panel).is_some_and(|id| opened.contains(&id)) {
+            continue;
         }
-    });
+        let index = apps.partition_point(|app| app.sort_key <= panel.sort_key);
+        apps.insert(index, panel.clone());
+    }
 }
 
-fn connect_entry_key_handler(
-    entry: &Entry,
-    listbox: &ListBox,
-    state: &LauncherState,
-    app: &Application,
-    show_usage: bool,
-) {
-    let entry_for_keys = entry.clone();
-    let listbox_for_keys = listbox.clone();
-    let results_for_keys = Rc::clone(&state.results);
-    let usage_for_keys = Rc::clone(&state.usage);
-    let state_for_keys = state.clone();
-    let app_for_keys = app.clone();
-    entry.connect_key_press_event(move |_, event| {
-        let key = event.keyval();
-        if key == gdk::keys::constants::Escape {
-            app_for_keys.quit();
-            return gtk::glib::Propagation::Stop;
-        }
-        if key == gdk::keys::constants::Down {
-            move_selection(&listbox_for_keys, &state_for_keys, 1, show_usage);
-            return gtk::glib::Propagation::Stop;
-        }
-        if key == gdk::keys::constants::Up {
-            move_selection(&listbox_for_keys, &state_for_keys, -1, show_usage);
-            return gtk::glib::Propagation::Stop;
-        }
-        if key == gdk::keys::constants::Return || key == gdk::keys::constants::KP_Enter {
-            let row = listbox_for_keys
-                .selected_row()
-                .or_else(|| first_selectable_row(&listbox_for_keys));
-            if let Some(row) = row {
-                if launch_from_index(row.index(), &results_for_keys, &usage_for_keys) {
-                    app_for_keys.quit();
-                }
-            }
-            entry_for_keys.grab_focus();
-            return gtk::glib::Propagation::Stop;
+/// Sorts apps by lowercased name, reusing the order and sort keys from the
+/// previous run when the desktop entry directories haven't changed.
+fn sort_apps(apps: Vec<AppEntry>) -> Vec<AppEntry> {
+    let hash = app_cache::desktop_dirs_hash();
+    let (apps, sorted) = order_apps(apps, app_cache::load(hash));
+    if sorted {
+        app_cache::save(&order_cache(hash, &apps));
+    }
+    apps
+}
+
+/// The apps in the cached order with the cached sort keys, or sorted
+/// afresh, and then true, when there's no cache or it doesn't cover every
+/// app.
+fn order_apps(
+    mut apps: Vec<AppEntry>,
+    cache: Option<app_cache::OrderCache>,
+) -> (Vec<AppEntry>, bool) {
+    if let Some(cache) = cache {
+        let keys: Vec<&str> = apps.iter().map(|app| app.key.as_str()).collect();
+        if let Some(order) = app_cache::apply(cache, &keys) {
+            let mut slots: Vec<Option<AppEntry>> = apps.into_iter().map(Some).collect();
+            let apps = order
+                .into_iter()
+                .filter_map(|(index, sort_key)| {
+                    let mut app = slots[index].take()?;
+                    app.sort_key = sort_key;
+                    Some(app)
+                })
+                .collect();
+            return (apps, false);
         }
-        gtk::glib::Propagation::Proceed
-    });
+    }
+
+    for app in &mut apps {
+        app.sort_key = app.name.to_lowercase();
+    }
+    apps.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+    (apps, true)
 }
 
-fn connect_entry_change_handler(
-    entry: &Entry,
-    listbox: &ListBox,
-    state: &LauncherState,
-    show_usage: bool,
-) {
-    let listbox_for_change = listbox.clone();
-    let state_for_change = state.clone();
-    entry.connect_changed(move |entry| {
-        let query = entry.text().to_string();
-        update_results(&listbox_for_change, &state_for_change, &query, show_usage);
-    });
+fn order_cache(hash: u64, apps: &[AppEntry]) -> app_cache::OrderCache {
+    app_cache::OrderCache {
+        hash,
+        entries: apps
+            .iter()
+            .map(|app| app_cache::OrderEntry {
+                key: app.key.clone(),
+                sort_key: app.sort_key.clone(),
+            })
+            .collect(),
+    }
 }
 
-fn connect_entry_handlers(
-    entry: &Entry,
-    listbox: &ListBox,
-    state: &LauncherState,
-    app: &Application,
-    show_usage: bool,
-) {
-    connect_entry_key_handler(entry, listbox, state, app, show_usage);
-    connect_entry_change_handler(entry, listbox, state, show_usage);
+fn clear_listbox(listbox: &ListBox) {
+    for child in listbox.children() {
+        listbox.remove(&child);
+    }
 }
 
-fn build_ui(app: &Application, show_usage: bool) {
-    configure_settings();
+/// How long ago `then` was, e.g. "3h ago". Times in the future, from a
+/// skewed clock or another machine's history, count as just now.
+fn relative_time(then: u64, now: u64) -> String {
+    if then == 0 {
+        return "never".to_string();
+    }
+    let age = now.saturating_sub(then);
+    if age < 60 {
+        "just now".to_string()
+    } else if age < 60 * 60 {
+        format!("{}m ago", age / 60)
+    } else if age < DAY_SECS {
+        format!("{}h ago", age / (60 * 60))
+    } else if age < 7 * DAY_SECS {
+        format!("{}d ago", age / DAY_SECS)
+    } else {
+        format!("{}w ago", age / (7 * DAY_SECS))
+    }
+}
 
-    let title = gtk::Label::new(Some("HyperFind"));
-    title.set_xalign(0.0);
+/// The dimmed text after an app's name: usage when `--usage` is on, and a
+/// marker for hidden entries.
+fn usage_suffix(app: &AppEntry, usage: &UsageMap, show_usage: bool, now: u64) -> Option<String> {
+    let mut parts = Vec::new();
+    if show_usage {
+        parts.push(match usage.get(&app.key) {
+            Some(entry) => format!(
+                "{} uses, {}",
+                entry.count,
+                relative_time(entry.last_used, now)
+            ),
+            None => "0 uses".to_string(),
+        });
+    }
+    if app.hidden {
+        parts.push("hidden".to_string());
+    }
+    (!parts.is_empty()).then(|| format!("({})", parts.join(", ")))
+}
 
-    let entry = Entry::builder().placeholder_text("Search…").build();
+/// Shows a pixbuf loaded at `scale` times the icon size as a surface of
+/// that scale, so it's drawn at the icon size without being stretched.
+fn set_scaled_pixbuf(image: &gtk::Image, pixbuf: &gtk::gdk_pixbuf::Pixbuf, scale: i32) {
+    match pixbuf.create_surface(scale, Option::<&gtk::gdk::Window>::None) {
+        Some(surface) => image.set_from_surface(Some(&surface)),
+        None => image.set_from_pixbuf(Some(pixbuf)),
+    }
+}
 
-    let state = LauncherState::new();
-    let listbox = build_listbox();
+/// The theme's file for `icon` at `size` pixels and `scale`. Only the
+/// lookup runs here, on the main thread where GTK's icon theme lives; the
+/// file is loaded with `load_icon_async`.
+fn lookup_icon(
+    theme: &gtk::IconTheme,
+    icon: &gio::Icon,
+    size: i32,
+    scale: i32,
+) -> Option<gtk::IconInfo> {
+    theme.lookup_by_gicon_for_scale(icon, size, scale, gtk::IconLookupFlags::FORCE_SIZE)
+}
 
-    connect_listbox_activation(&listbox, &state, app);
-    connect_entry_handlers(&entry, &listbox, &state, app, show_usage);
+fn build_icon_image(icon: &gio::Icon, icons: &Rc<IconCache>) -> gtk::Image {
+    let image = gtk::Image::new();
+    image.set_pixel_size(icons.size);
+    // Not on screen yet, so this is the display's scale.
+    let scale = image.scale_factor().max(1);
 
-    let container = build_container(&title, &entry, &listbox);
-    let window = build_window(app, &container);
+    // Icons that can't be written out as a string, like one made from a
+    // pixbuf, are already loaded.
+    let Some(key) = IconExt::to_string(icon).map(|key| key.to_string()) else {
+        image.set_from_gicon(icon, gtk::IconSize::Menu);
+        return image;
+    };
+    let cache_key = (key, icons.size, scale);
+    if let Some(pixbuf) = icons.pixbufs.borrow().get(&cache_key) {
+        set_scaled_pixbuf(&image, pixbuf, scale);
+        return image;
+    }
 
-    refresh_results(&listbox, &state, show_usage);
+    let Some(info) =
+        gtk::IconTheme::default().and_then(|theme| lookup_icon(&theme, icon, icons.size, scale))
+    else {
+        image.set_from_icon_name(Some(FALLBACK_ICON), gtk::IconSize::Menu);
+        return image;
+    };
 
-    window.show_all();
-    focus_entry_later(&entry);
-}
+    let resolved = Rc::new(Cell::new(false));
+    icons.pending.set(icons.pending.get() + 1);
 
-fn configure_command_line(app: &Application, show_usage: Rc<Cell<bool>>) {
-    app.connect_command_line(move |app, cmd| {
-        let args = cmd.arguments();
-        if args.iter().any(|arg| arg == "--usage") {
-            show_usage.set(true);
+    let image_for_deadline = image.clone();
+    let resolved_for_deadline = Rc::clone(&resolved);
+    let icons_for_deadline = Rc::clone(icons);
+    gtk::glib::timeout_add_local_once(ICON_DEADLINE, move || {
+        if resolved_for_deadline.get() {
+            return;
         }
-        app.activate();
-        0
+        let stalls = icons_for_deadline.stalls.get();
+        icons_for_deadline.stalls.set(stalls.saturating_add(1));
+        image_for_deadline.set_from_icon_name(Some(FALLBACK_ICON), gtk::IconSize::Menu);
     });
-}
-
-fn build_app() -> Application {
-    let app = Application::builder()
-        .application_id("com.example.hyperfind")
-        .flags(gio::ApplicationFlags::HANDLES_COMMAND_LINE)
-        .build();
-
-    let show_usage = Rc::new(Cell::new(false));
-    configure_command_line(&app, Rc::clone(&show_usage));
 
-    let show_usage = Rc::clone(&show_usage);
-    app.connect_activate(move |app| {
-        build_ui(app, show_usage.get());
+    let image_for_load = image.clone();
+    let icons_for_load = Rc::clone(icons);
+    info.load_icon_async(Option::<&gio::Cancellable>::None, move |loaded| {
+        resolved.set(true);
+        match loaded {
+            Ok(pixbuf) => {
+                set_scaled_pixbuf(&image_for_load, &pixbuf, scale);
+                icons_for_load
+                    .pixbufs
+                    .borrow_mut()
+                    .insert(cache_key, pixbuf);
+            }
+            Err(_) => image_for_load.set_from_icon_name(Some(FALLBACK_ICON), gtk::IconSize::Menu),
+        }
+        icons_for_load.settle_one();
     });
 
-    app
+    image
 }
 
-fn main() {
-    let app = build_app();
-    app.run();
+fn build_result_row(
+    app: &AppEntry,
+    usage: &UsageMap,
+    show_usage: bool,
+    now: u64,
+    icons: &Rc<IconCache>,
+    number: Option<u8>,
+    style: &RowStyle,
+) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    if let Some(icon) = &app.icon
+        && style.icon_size > 0
+    {
+        let image = build_icon_image(icon, icons);
+        row_box.pack_start(&image, false, false, 0);
+    }
+    let mut markup = gtk::glib::markup_escape_text(&app.name).to_string();
+    if app.duplicate
+        && let Some(packaging) = app.packaging
+    {
+        markup.push_str(&format!(" <span alpha=\"60%\">({})</span>", packaging));
+    }
+    if let Some(suffix) = usage_suffix(app, usage, show_usage, now) {
+        markup.push_str(&format!(
+            "  <span alpha=\"60%\">{}</span>",
+            gtk::glib::markup_escape_text(&suffix)
+        ));
+    }
+    let label = gtk::Label::new(None);
+    label.set_markup(&markup);
+    label.set_xalign(0.0);
+    if app.hidden {
+        label.style_context().add_class("dim-label");
+    }
+    match app.description.as_ref().filter(|_| style.descriptions) {
+        Some(description) => {
+            let lines = gtk::Box::new(gtk::Orientation::Vertical, 0);
+            lines.set_valign(gtk::Align::Center);
+            lines.pack_start(&label, false, false, 0);
+            let description_label = gtk::Label::new(None);
+            description_label.set_markup(&format!(
+                "<small>{}</small>",
+                gtk::glib::markup_escape_text(description)
+            ));
+            description_label.set_xalign(0.0);
+            description_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+            description_label.style_context().add_class("dim-label");
+            lines.pack_start(&description_label, false, false, 0);
+            row_box.pack_start(&lines, true, true, 0);
+        }
+        None => row_box.pack_start(&label, true, true, 0),
+    }
+    if app.terminal {
+        let glyph =
+            gtk::Image::from_icon_name(Some("utilities-terminal-symbolic"), gtk::IconSize::Menu);
+        glyph.set_tooltip_text(Some("Runs in a terminal"));
+        glyph.style_context().add_class("dim-label");
+        row_box.pack_end(&glyph, false, false, 0);
+    } else {
+        row.set_tooltip_text(Some("Shift+Enter runs it in a terminal"));
+    }
+    if let Some(number) = number {
+        let hint = gtk::Label::new(Some(&number.to_string()));
+        hint.set_tooltip_text(Some(&format!("Alt+{} launches it", number)));
+        hint.style_context().add_class("dim-label");
+        row_box.pack_end(&hint, false, false, 0);
+    }
+    if app.running {
+        let dot = gtk::Label::new(Some("•"));
+        dot.set_tooltip_text(Some("Running; Alt+Enter starts another"));
+        dot.style_context().add_class("dim-label");
+        row_box.pack_end(&dot, false, false, 0);
+    }
+    if app.autostart {
+        let glyph = gtk::Image::from_icon_name(Some("system-run-symbolic"), gtk::IconSize::Menu);
+        glyph.set_tooltip_text(Some("Starts when you log in"));
+        glyph.style_context().add_class("dim-label");
+        row_box.pack_end(&glyph, false, false, 0);
+    }
+    row.add(&row_box);
+    row
+}
+
+fn build_create_launcher_row(name: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("list-add"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(&format!("Create launcher for '{}'", name)));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_run_command_row(command: &str, in_terminal: bool) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let icon = if in_terminal {
+        "utilities-terminal"
+    } else {
+        "system-run"
+    };
+    let image = gtk::Image::from_icon_name(Some(icon), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let text = if in_terminal {
+        format!("Run in terminal: {}", command)
+    } else {
+        format!("Run: {}", command)
+    };
+    let label = gtk::Label::new(Some(&text));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_calc_row(expression: &str, value: Option<f64>) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("accessories-calculator"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let value = value.map_or_else(|| "…".to_string(), calc::format);
+    let label = gtk::Label::new(Some(&format!("{} = {}", expression, value)));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_file_row(file: &files::FileMatch, icons: &Rc<IconCache>) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let icon = if file.is_dir {
+        gio::ThemedIcon::new("folder").upcast()
+    } else {
+        let (content_type, _) = gio::content_type_guess(Some(&file.path), &[]);
+        gio::content_type_get_icon(&content_type)
+    };
+    if icons.size > 0 {
+        row_box.pack_start(&build_icon_image(&icon, icons), false, false, 0);
+    }
+    let label = gtk::Label::new(Some(&file.name));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_recent_row(file: &recent::RecentFile, icons: &Rc<IconCache>) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let icon = file
+        .icon
+        .clone()
+        .unwrap_or_else(|| gio::ThemedIcon::new("text-x-generic").upcast());
+    if icons.size > 0 {
+        row_box.pack_start(&build_icon_image(&icon, icons), false, false, 0);
+    }
+    let label = gtk::Label::new(Some(&file.name));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_web_search_row(engine: &str, query: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("web-browser"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(&format!("Search {} for '{}'", engine, query)));
+    label.set_xalign(0.0);
+    label.style_context().add_class("dim-label");
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_open_url_row(url: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("web-browser"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(&format!("Open {}", url)));
+    label.set_xalign(0.0);
+    label.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_bookmark_row(bookmark: &bookmarks::Bookmark) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("user-bookmarks"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(&bookmark.title));
+    label.set_xalign(0.0);
+    label.set_tooltip_text(Some(&bookmark.url));
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_project_row(project: &projects::Project) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("folder"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(&project.name));
+    label.set_xalign(0.0);
+    label.set_tooltip_text(Some(&project.path.to_string_lossy()));
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_line_row(text: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let label = gtk::Label::new(Some(text));
+    label.set_xalign(0.0);
+    row.add(&label);
+    row
+}
+
+fn build_clipboard_row(text: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let lines = text.trim().lines().count();
+    let mut markup = gtk::glib::markup_escape_text(&clipboard::preview(text)).to_string();
+    if lines > 1 {
+        markup.push_str(&format!("  <span alpha=\"60%\">{} lines</span>", lines));
+    }
+    let label = gtk::Label::new(None);
+    label.set_markup(&markup);
+    label.set_xalign(0.0);
+    row.add(&label);
+    row
+}
+
+fn build_snippet_row(snippet: &snippets::Snippet) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let label = gtk::Label::new(None);
+    label.set_markup(&format!(
+        "{}  <span alpha=\"60%\">{}</span>",
+        gtk::glib::markup_escape_text(&snippet.name),
+        gtk::glib::markup_escape_text(&clipboard::preview(&snippet.text)),
+    ));
+    label.set_xalign(0.0);
+    label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+    row.add(&label);
+    row
+}
+
+fn build_unit_row(unit: &systemd::Unit) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let color = if unit.active == "failed" {
+        "#c01c28"
+    } else if unit.is_active() {
+        "#26a269"
+    } else {
+        "#9a9996"
+    };
+    let markup = format!(
+        "<span foreground=\"{}\">●</span>  {}  <span alpha=\"60%\">{} · {}</span>",
+        color,
+        gtk::glib::markup_escape_text(&unit.name),
+        gtk::glib::markup_escape_text(&unit.sub),
+        gtk::glib::markup_escape_text(&unit.description),
+    );
+    let label = gtk::Label::new(None);
+    label.set_markup(&markup);
+    label.set_xalign(0.0);
+    row.add(&label);
+    row
+}
+
+fn build_pass_row(entry: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("dialog-password"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(entry));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_plugin_row(result: &plugins::PluginResult) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let icon = result.icon.as_deref().unwrap_or("system-run");
+    let image = gtk::Image::from_icon_name(Some(icon), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(&result.title));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_power_row(action: &power::Action, confirming: bool) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some(action.icon), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(None);
+    match action.confirm.filter(|_| confirming) {
+        Some(question) => label.set_markup(&format!("<b>{}</b>", question)),
+        None => label.set_text(action.name),
+    }
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_process_row(process: &procs::Process, confirming: bool) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let icon = if confirming {
+        "dialog-warning"
+    } else {
+        "utilities-system-monitor"
+    };
+    let image = gtk::Image::from_icon_name(Some(icon), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let name = gtk::glib::markup_escape_text(&process.name);
+    let markup = if confirming {
+        format!("<b>Really kill {}? It belongs to another user</b>", name)
+    } else {
+        let count = match process.pids.len() {
+            1 => format!("pid {}", process.pids[0]),
+            count => format!("×{}", count),
+        };
+        format!(
+            "{}  <span alpha=\"60%\">{} · {}</span>",
+            name,
+            count,
+            procs::format_memory(process.memory_kb)
+        )
+    };
+    let label = gtk::Label::new(None);
+    label.set_markup(&markup);
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_tmux_row(target: &tmux::Target) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("utilities-terminal"), gtk::IconSize::Menu);
+    if target.window.is_some() {
+        image.set_margin_start(16);
+    }
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(&target.label()));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_ssh_host_row(host: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("network-server"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(Some(host));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_emoji_row(emoji: &emoji::Emoji) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let glyph = gtk::Label::new(None);
+    glyph.set_markup(&format!("<span size=\"x-large\">{}</span>", emoji.emoji));
+    row_box.pack_start(&glyph, false, false, 0);
+    let label = gtk::Label::new(Some(emoji.name));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_symbol_row(symbol: &symbols::Symbol) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let glyph = gtk::Label::new(None);
+    glyph.set_markup(&format!(
+        "<span size=\"x-large\">{}</span>",
+        gtk::glib::markup_escape_text(&symbol.symbol.to_string())
+    ));
+    glyph.set_width_chars(2);
+    row_box.pack_start(&glyph, false, false, 0);
+    let label = gtk::Label::new(None);
+    label.set_markup(&format!(
+        "{}  <span alpha=\"60%\">{}</span>",
+        symbol.name,
+        symbol.codepoint()
+    ));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+fn build_path_bin_row(name: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let row_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let image = gtk::Image::from_icon_name(Some("utilities-terminal"), gtk::IconSize::Menu);
+    row_box.pack_start(&image, false, false, 0);
+    let label = gtk::Label::new(None);
+    label.set_markup(&format!(
+        "{}  <span alpha=\"60%\">(command)</span>",
+        gtk::glib::markup_escape_text(name)
+    ));
+    label.set_xalign(0.0);
+    row_box.pack_start(&label, true, true, 0);
+    row.add(&row_box);
+    row
+}
+
+/// An action row is indented to line up with the app names above it.
+fn build_action_row(label: &str, style: &RowStyle) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let label = gtk::Label::new(Some(label));
+    label.set_xalign(0.0);
+    label.set_margin_start(style.icon_size + 8);
+    row.add(&label);
+    row
+}
+
+/// A header row. `title` is plain text; headers quote queries, copied text
+/// and command output, none of which may be read as markup.
+fn build_section_row(title: &str) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    row.set_selectable(false);
+    row.set_activatable(false);
+    let label = gtk::Label::new(None);
+    label.set_markup(&format!("<b>{}</b>", gtk::glib::markup_escape_text(title)));
+    label.set_xalign(0.0);
+    label.set_margin_top(0);
+    label.set_margin_bottom(0);
+    row.set_margin_top(0);
+    row.set_margin_bottom(0);
+    row.add(&label);
+    row
+}
+
+/// The row selected when results change. Creating a launcher can be
+/// selected, but never by default, so Enter on a query that matched
+/// nothing doesn't open the form.
+fn first_selectable_index(items: &[ResultItem]) -> Option<usize> {
+    items
+        .iter()
+        .position(|item| item.is_selectable() && !matches!(item, ResultItem::CreateLauncher(_)))
+}
+
+/// The row `Enter` goes to: the selected one, or else the one that would
+/// be selected by default.
+fn enter_index(listbox: &ListBox, state: &LauncherState) -> Option<i32> {
+    match listbox.selected_row() {
+        Some(row) => Some(row.index()),
+        None => first_selectable_index(&state.view.borrow().items)
+            .and_then(|index| i32::try_from(index).ok()),
+    }
+}
+
+fn next_selectable_index(items: &[ResultItem], start: usize, direction: i32) -> Option<usize> {
+    let mut index = start as i32 + direction;
+    while index >= 0 && (index as usize) < items.len() {
+        if items[index as usize].is_selectable() {
+            return Some(index as usize);
+        }
+        index += direction;
+    }
+    None
+}
+
+/// Where each item shows, in the rows or the grid, as they last laid out.
+fn item_allocations(listbox: &ListBox, state: &LauncherState) -> Vec<gtk::Allocation> {
+    match &state.grid {
+        Some(grid) => grid.allocations(),
+        None => listbox
+            .children()
+            .iter()
+            .map(|row| row.allocation())
+            .collect(),
+    }
+}
+
+/// The first item scrolled into view, in the rows as they last laid out.
+fn first_visible_row(listbox: &ListBox, state: &LauncherState) -> usize {
+    let Some(adjustment) = listbox.adjustment() else {
+        return 0;
+    };
+    let top = adjustment.value() as i32;
+    item_allocations(listbox, state)
+        .iter()
+        .position(|allocation| {
+            let (start, length) = scroll_span(state, allocation);
+            start + length > top
+        })
+        .unwrap_or(0)
+}
+
+/// Where an item starts and how long it is along the way the results
+/// scroll: across in the bar, down everywhere else.
+fn scroll_span(state: &LauncherState, allocation: &gtk::Allocation) -> (i32, i32) {
+    if state.grid.as_ref().is_some_and(|grid| grid.is_bar()) {
+        (allocation.x(), allocation.width())
+    } else {
+        (allocation.y(), allocation.height())
+    }
+}
+
+/// Scrolls just far enough to show the selected item, once it has been
+/// laid out.
+fn scroll_to_selected_later(listbox: &ListBox, state: &LauncherState) {
+    let listbox = listbox.clone();
+    let state = state.clone();
+    gtk::glib::idle_add_local_once(move || {
+        let (Some(adjustment), Some(row)) = (listbox.adjustment(), listbox.selected_row()) else {
+            return;
+        };
+        let allocation = match &state.grid {
+            Some(grid) => usize::try_from(row.index())
+                .ok()
+                .and_then(|index| grid.allocation(index)),
+            None => Some(row.allocation()),
+        };
+        let Some(allocation) = allocation else {
+            return;
+        };
+        let (start, length) = scroll_span(&state, &allocation);
+        let value = scroll_to_show(
+            f64::from(start),
+            f64::from(length),
+            adjustment.value(),
+            adjustment.page_size(),
+        );
+        if value != adjustment.value() {
+            adjustment.set_value(value);
+        }
+    });
+}
+
+/// The scroll position nearest `value` that shows `length` from `start`
+/// on a page `page` long: unchanged when it's already in view, otherwise
+/// with it at the edge it came in from.
+fn scroll_to_show(start: f64, length: f64, value: f64, page: f64) -> f64 {
+    let end = start + length;
+    if start < value {
+        start
+    } else if end > value + page {
+        end - page
+    } else {
+        value
+    }
+}
+
+/// Selects the row for `view_state.selected_index` without rebuilding
+/// the rows, unless it hasn't been built yet.
+fn show_selection(listbox: &ListBox, state: &LauncherState, view_state: &ViewState) {
+    let row = view_state
+        .selected_index
+        .and_then(|index| i32::try_from(index).ok())
+        .and_then(|index| listbox.row_at_index(index));
+    let Some(row) = row else {
+        rerender(listbox, state, view_state);
+        return;
+    };
+    listbox.select_row(Some(&row));
+    scroll_to_selected_later(listbox, state);
+}
+
+/// Adds the rows for `view_state.items[range]` below the ones already
+/// built. `numbered` lists the items that get an Alt+number, in order.
+fn render_rows(
+    listbox: &ListBox,
+    state: &LauncherState,
+    view_state: &ViewState,
+    range: Range<usize>,
+    numbered: &[usize],
+) {
+    let usage = &*state.usage.borrow();
+    let show_usage = state.options.show_usage && state.snooze_deadline().is_none();
+    let now = state.now();
+    let icons = &state.icons;
+
+    for (idx, item) in view_state
+        .items
+        .iter()
+        .enumerate()
+        .take(range.end)
+        .skip(range.start)
+    {
+        let row = match item {
+            ResultItem::Header(title) => build_section_row(title),
+            ResultItem::App(app) => {
+                if let Some(grid) = &state.grid {
+                    let row = gtk::ListBoxRow::new();
+                    grid.add_app(app, icons, &row);
+                    listbox.add(&row);
+                    continue;
+                }
+                let number = numbered
+                    .iter()
+                    .position(|numbered| *numbered == idx)
+                    .and_then(|position| u8::try_from(position + 1).ok());
+                build_result_row(app, usage, show_usage, now, icons, number, &state.row_style)
+            }
+            ResultItem::CreateLauncher(name) => build_create_launcher_row(name),
+            ResultItem::Action { label, .. } => build_action_row(label, &state.row_style),
+            ResultItem::RunCommand {
+                command,
+                in_terminal,
+            } => build_run_command_row(command, *in_terminal),
+            ResultItem::Calc { expression, value } => build_calc_row(expression, *value),
+            ResultItem::Recent(file) => build_recent_row(file, icons),
+            ResultItem::Bookmark(bookmark) => build_bookmark_row(bookmark),
+            ResultItem::Project(project) => build_project_row(project),
+            ResultItem::File(file) => build_file_row(file, icons),
+            ResultItem::Power { action, confirming } => build_power_row(action, *confirming),
+            ResultItem::SshHost(host) => build_ssh_host_row(host),
+            ResultItem::Symbol(symbol) => build_symbol_row(symbol),
+            ResultItem::Emoji(emoji) => build_emoji_row(emoji),
+            ResultItem::WebSearch { engine, query, .. } => build_web_search_row(engine, query),
+            ResultItem::OpenUrl(url) => build_open_url_row(url),
+            ResultItem::Plugin(result) => build_plugin_row(result),
+            ResultItem::Process {
+                process,
+                confirming,
+            } => build_process_row(process, *confirming),
+            ResultItem::Tmux(target) => build_tmux_row(target),
+            ResultItem::PassEntry(entry) => build_pass_row(entry),
+            ResultItem::Unit(unit) => build_unit_row(unit),
+            ResultItem::ClipboardEntry(text) => build_clipboard_row(text),
+            ResultItem::Snippet(snippet) => build_snippet_row(snippet),
+            ResultItem::ClearClipboard => build_line_row("Clear clipboard history"),
+            ResultItem::Line { text, .. } => build_line_row(text),
+            ResultItem::PathBin(name) => build_path_bin_row(name),
+        };
+        add_row(listbox, state, &row);
+    }
+    listbox.show_all();
+    if let Some(grid) = &state.grid {
+        grid.grid.show_all();
+    }
+}
+
+/// Adds a row to the list, or with `layout = "grid"` moves what it shows
+/// into the grid and keeps the emptied row for selecting.
+fn add_row(listbox: &ListBox, state: &LauncherState, row: &gtk::ListBoxRow) {
+    if let Some(grid) = &state.grid {
+        grid.add_wide(row);
+    }
+    listbox.add(row);
+}
+
+/// The app items to number for Alt+number: the first ones from the top of
+/// the scrolled view down.
+fn numbered_items(listbox: &ListBox, state: &LauncherState, view_state: &ViewState) -> Vec<usize> {
+    if !view_state.number_hints {
+        return Vec::new();
+    }
+    let first_visible = first_visible_row(listbox, state);
+    view_state
+        .items
+        .iter()
+        .enumerate()
+        .skip(first_visible)
+        .filter(|(_, item)| matches!(item, ResultItem::App(_)))
+        .map(|(idx, _)| idx)
+        .take(usize::from(keymap::NUMBERED))
+        .collect()
+}
+
+/// Builds rows for the rest of the items a chunk at a time while the main
+/// loop is idle, until they're done or a newer render takes over.
+fn render_rest_later(listbox: &ListBox, state: &LauncherState, from: usize, numbered: Vec<usize>) {
+    let generation = state.render_generation.get();
+    let listbox = listbox.clone();
+    let state = state.clone();
+    let mut next = from;
+    gtk::glib::idle_add_local(move || {
+        if state.render_generation.get() != generation {
+            return gtk::glib::ControlFlow::Break;
+        }
+        let Ok(view_state) = state.view.try_borrow() else {
+            return gtk::glib::ControlFlow::Continue;
+        };
+        let end = view_state
+            .items
+            .len()
+            .min(MAX_RENDERED)
+            .min(next + RENDER_CHUNK);
+        render_rows(&listbox, &state, &view_state, next..end, &numbered);
+        next = end;
+        if next < view_state.items.len().min(MAX_RENDERED) {
+            gtk::glib::ControlFlow::Continue
+        } else {
+            gtk::glib::ControlFlow::Break
+        }
+    });
+}
+
+/// `>cmd` runs `cmd`; a trailing `;` runs it in the terminal instead.
+fn parse_run_command(query: &str) -> Option<(&str, bool)> {
+    let command = query.trim().strip_prefix(RUN_PREFIX)?.trim();
+    match command.strip_suffix(';') {
+        Some(command) => Some((command.trim_end(), true)),
+        None => Some((command, false)),
+    }
+}
+
+fn open_uri(uri: &str, name: &str, state: &LauncherState) -> Result<(), LaunchError> {
+    if state.launching.replace(true) {
+        return Err(LaunchError::Cancelled);
+    }
+    if state.options.demo {
+        println!("Would open {}", uri);
+        return Ok(());
+    }
+
+    if let Err(err) = gio::AppInfo::launch_default_for_uri(uri, launch_context(state).as_ref()) {
+        eprintln!("Failed to open {}: {}", uri, err);
+        state.launching.set(false);
+        return Err(LaunchError::Spawn {
+            verb: "open",
+            name: name.to_string(),
+            reason: err.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Replaces the search with the selected directory's path, listing what
+/// is inside it. Returns false if no directory is selected.
+fn descend_into_selected(entry: &Entry, listbox: &ListBox, state: &LauncherState) -> bool {
+    let completion = match selected_item(listbox, state) {
+        Some(ResultItem::File(file)) if file.is_dir => file.completion,
+        _ => return false,
+    };
+    replace_query(entry, state, &completion);
+    true
+}
+
+/// Puts `text` in the search with the cursor at its end, keeping what was
+/// there for Shift+Tab to bring back.
+fn replace_query(entry: &Entry, state: &LauncherState, text: &str) {
+    state.previous_query.replace(Some(entry.text().to_string()));
+    entry.set_text(text);
+    entry.set_position(-1);
+}
+
+/// Replaces the search with the selected app's name, which usually
+/// leaves it the only result. Returns false if no app is selected or the
+/// search already is its name.
+fn complete_selected(entry: &Entry, listbox: &ListBox, state: &LauncherState) -> bool {
+    let Some(app) = selected_app(listbox, state) else {
+        return false;
+    };
+    if entry.text() == app.name {
+        return false;
+    }
+    replace_query(entry, state, &app.name);
+    true
+}
+
+/// Brings back the search from before the last completion.
+fn restore_previous_query(entry: &Entry, state: &LauncherState) {
+    let Some(previous) = state.previous_query.take() else {
+        return;
+    };
+    entry.set_text(&previous);
+    entry.set_position(-1);
+}
+
+/// Starts `command` detached from hyperfind, parsed with shell quoting
+/// rules but not through a shell.
+fn run_command(command: &str, in_terminal: bool, state: &LauncherState) -> Result<(), LaunchError> {
+    if state.launching.replace(true) {
+        return Err(LaunchError::Cancelled);
+    }
+
+    let command_line = if in_terminal {
+        terminal::command_line(&state.terminal, command)
+    } else {
+        command.to_string()
+    };
+    if state.options.demo {
+        println!("Would run {}", command_line);
+        return Ok(());
+    }
+
+    if let Err(err) = gtk::glib::spawn_command_line_async(&command_line) {
+        eprintln!("Failed to run {}: {}", command_line, err);
+        state.launching.set(false);
+        return Err(LaunchError::Spawn {
+            verb: "run",
+            name: command.to_string(),
+            reason: err.to_string(),
+        });
+    }
+    Ok(())
+}
+
+enum QueryMatcher<'a> {
+    Fuzzy(&'a str),
+    Regex(Regex),
+}
+
+/// Picks how a query matches names. A leading `re:` compiles the rest of the
+/// query as a case-insensitive regex, once per keystroke.
+fn parse_query(query: &str) -> Result<QueryMatcher<'_>, regex::Error> {
+    match query.trim().strip_prefix(REGEX_PREFIX) {
+        Some(pattern) => RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map(QueryMatcher::Regex),
+        None => Ok(QueryMatcher::Fuzzy(query)),
+    }
+}
+
+/// Everything a search can turn up.
+struct Sources<'a> {
+    apps: &'a [AppEntry],
+    /// Empty unless `$PATH` scanning is on and has finished.
+    path_bins: &'a [String],
+    recent: &'a [recent::RecentFile],
+    /// Empty until the browsers' bookmarks have been read.
+    bookmarks: &'a [bookmarks::Bookmark],
+    projects: &'a [projects::Project],
+    /// Empty when the power menu is turned off.
+    power: &'static [power::Action],
+}
+
+fn build_view_items(
+    sources: &Sources,
+    query: &str,
+    usage: &UsageMap,
+    ranking: &Ranking,
+    now: u64,
+    snoozed: bool,
+    curation: &Curation,
+) -> Vec<ResultItem> {
+    let calc = calc::expression(query).map(|expression| ResultItem::Calc {
+        expression: expression.to_string(),
+        value: calc::evaluate(expression),
+    });
+
+    if !query.trim().is_empty() {
+        let Ok(matcher) = parse_query(query) else {
+            return vec![ResultItem::Header("Invalid regex".to_string())];
+        };
+        let mut scored: Vec<(i64, &str, ResultItem)> =
+            score_apps(sources.apps, &matcher, usage, ranking, now)
+                .into_iter()
+                .map(|(score, app)| (score, app.name.as_str(), ResultItem::App(app.clone())))
+                .collect();
+        let usage_bonus = |key: &str| {
+            usage.get(key).map_or(0, |entry| {
+                usage_score(entry, now, ranking.recency_window_secs)
+            })
+        };
+        scored.extend(
+            score_items(
+                sources.power,
+                |action| action.name,
+                |action| Some(action.usage_key()),
+                0,
+                &matcher,
+                &usage_bonus,
+            )
+            .into_iter()
+            .map(|(score, action)| {
+                let item = ResultItem::Power {
+                    action,
+                    confirming: false,
+                };
+                (score, action.name, item)
+            }),
+        );
+        scored.extend(
+            score_items(
+                sources.recent,
+                |file| &file.name,
+                |file| Some(file.usage_key()),
+                RECENT_FILE_PENALTY,
+                &matcher,
+                &usage_bonus,
+            )
+            .into_iter()
+            .map(|(score, file)| (score, file.name.as_str(), ResultItem::Recent(file.clone()))),
+        );
+        scored.extend(
+            score_items(
+                sources.bookmarks,
+                |bookmark| &bookmark.title,
+                |bookmark| Some(bookmark.usage_key()),
+                BOOKMARK_PENALTY,
+                &matcher,
+                &usage_bonus,
+            )
+            .into_iter()
+            .map(|(score, bookmark)| {
+                let item = ResultItem::Bookmark(bookmark.clone());
+                (score, bookmark.title.as_str(), item)
+            }),
+        );
+        scored.extend(
+            score_items(
+                sources.projects,
+                |project| &project.name,
+                |project| Some(project.usage_key()),
+                PROJECT_PENALTY,
+                &matcher,
+                &usage_bonus,
+            )
+            .into_iter()
+            .map(|(score, project)| {
+                let item = ResultItem::Project(project.clone());
+                (score, project.name.as_str(), item)
+            }),
+        );
+        // Commands on PATH have no usage of their own.
+        scored.extend(
+            score_items(
+                sources.path_bins,
+                String::as_str,
+                |_| None,
+                PATH_BIN_PENALTY,
+                &matcher,
+                &usage_bonus,
+            )
+            .into_iter()
+            .map(|(score, name)| (score, name.as_str(), ResultItem::PathBin(name.clone()))),
+        );
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        return calc
+            .into_iter()
+            .chain(scored.into_iter().map(|(_, _, item)| item))
+            .collect();
+    }
+
+    let apps = sources.apps;
+    let mut items = Vec::new();
+    let pinned = &curation.pinned;
+    let pinned_apps: Vec<&AppEntry> = pinned
+        .iter()
+        .filter_map(|key| apps.iter().find(|app| &app.key == key))
+        .collect();
+    if !pinned_apps.is_empty() {
+        items.push(ResultItem::Header("Pinned".to_string()));
+        for app in &pinned_apps {
+            items.push(ResultItem::App((*app).clone()));
+        }
+    }
+
+    let mut frequent: Vec<(i64, &AppEntry)> = apps
+        .iter()
+        .filter(|app| !pinned.contains(&app.key))
+        .filter(|app| !curation.frequent_excluded.contains(&app.key))
+        .filter_map(|app| {
+            // Entries that only ever failed to launch have no launches.
+            let min_count = ranking.frequent_min_count.max(1);
+            let entry = usage
+                .get(&app.key)
+                .filter(|entry| entry.count >= min_count)?;
+            let mut score = usage_score(entry, now, ranking.recency_window_secs);
+            if ranking.contextual {
+                score += time_of_day_boost(entry, now, score);
+            }
+            Some((score, app))
+        })
+        .collect();
+    frequent.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    // While snoozed every app stays in All Apps, in its usual place.
+    let frequent: Vec<&AppEntry> = frequent
+        .into_iter()
+        .map(|(_, app)| app)
+        .take(if snoozed { 0 } else { ranking.frequent_size })
+        .collect();
+
+    if !frequent.is_empty() {
+        items.push(ResultItem::Header("Frequently Used".to_string()));
+        for app in &frequent {
+            items.push(ResultItem::App((*app).clone()));
+        }
+    }
+
+    if ranking.recent_section && !snoozed && !sources.recent.is_empty() {
+        items.push(ResultItem::Header("Recent Files".to_string()));
+        for file in sources.recent.iter().take(RECENT_SECTION_SIZE) {
+            items.push(ResultItem::Recent(file.clone()));
+        }
+    }
+
+    items.push(ResultItem::Header("All Apps".to_string()));
+
+    let mut listed_keys = HashSet::new();
+    for app in pinned_apps.into_iter().chain(frequent) {
+        listed_keys.insert(app.key.clone());
+    }
+
+    for app in apps.iter().filter(|app| !listed_keys.contains(&app.key)) {
+        items.push(ResultItem::App(app.clone()));
+    }
+
+    items
+}
+
+/// Whether the query is exactly the app's desktop ID, ignoring case and the
+/// `.desktop` suffix, so scripts can select an app deterministically.
+fn matches_desktop_id(app: &AppEntry, query: &str) -> bool {
+    let Some(id) = app.app_info.id() else {
+        return false;
+    };
+    let id = id.to_lowercase();
+    let query = query.trim().to_lowercase();
+    id.strip_suffix(".desktop").unwrap_or(&id) == query.strip_suffix(".desktop").unwrap_or(&query)
+}
+
+/// The names an app can be found by; the best-matching one decides its
+/// score so an app never scores twice.
+fn app_names(app: &AppEntry) -> impl Iterator<Item = &str> {
+    std::iter::once(app.name.as_str())
+        .chain(app.untranslated_name.as_deref())
+        .chain(app.keywords.iter().map(String::as_str))
+}
+
+/// Scores each item whose name matches, adding the usage recorded under
+/// its key and subtracting `penalty`, so that it can rank below an app of
+/// the same name until it has been used a few times.
+fn score_items<'a, T>(
+    items: &'a [T],
+    name: impl Fn(&T) -> &str,
+    usage_key: impl Fn(&T) -> Option<String>,
+    penalty: i64,
+    matcher: &QueryMatcher,
+    usage_bonus: &dyn Fn(&str) -> i64,
+) -> Vec<(i64, &'a T)> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let name = name(item);
+            let score = match matcher {
+                QueryMatcher::Fuzzy(query) => matching::score(name, query)?,
+                QueryMatcher::Regex(regex) => regex.is_match(name).then_some(0)?,
+            };
+            let bonus = usage_key(item).map_or(0, |key| usage_bonus(&key));
+            Some((score + bonus - penalty, item))
+        })
+        .collect()
+}
+
+fn score_apps<'a>(
+    apps: &'a [AppEntry],
+    matcher: &QueryMatcher,
+    usage: &UsageMap,
+    ranking: &Ranking,
+    now: u64,
+) -> Vec<(i64, &'a AppEntry)> {
+    let query = match matcher {
+        QueryMatcher::Fuzzy(query) => *query,
+        QueryMatcher::Regex(regex) => {
+            return apps
+                .iter()
+                .filter(|app| app_names(app).any(|name| regex.is_match(name)))
+                .map(|app| {
+                    let score = usage.get(&app.key).map_or(0, |entry| {
+                        usage_score(entry, now, ranking.recency_window_secs)
+                    });
+                    (score, app)
+                })
+                .collect();
+        }
+    };
+
+    if query.trim().is_empty() {
+        return apps
+            .iter()
+            .filter_map(|app| {
+                let entry = usage.get(&app.key)?;
+                Some((usage_score(entry, now, ranking.recency_window_secs), app))
+            })
+            .collect();
+    }
+
+    apps.iter()
+        .filter_map(|app| {
+            if matches_desktop_id(app, query) {
+                return Some((EXACT_ID_SCORE, app));
+            }
+            let mut score = app_names(app)
+                .filter_map(|name| matching::score(name, query))
+                .max()?;
+            if let Some(entry) = usage.get(&app.key) {
+                let bonus = usage_score(entry, now, ranking.recency_window_secs);
+                score += bonus;
+                if ranking.contextual {
+                    score += time_of_day_boost(entry, now, bonus);
+                }
+            }
+            if is_foreign(app, &ranking.current_desktops) {
+                score -= ranking.foreign_desktop_penalty;
+            }
+            if usage
+                .get(&app.key)
+                .is_some_and(UsageEntry::last_launch_failed)
+            {
+                score -= FAILED_LAUNCH_PENALTY;
+            }
+            Some((score, app))
+        })
+        .collect()
+}
+
+/// A non-empty query that isn't a mode prefix, i.e. one that could name
+/// an app.
+fn is_app_query(query: &str) -> bool {
+    let query = query.trim();
+    !query.is_empty()
+        && !MODE_PREFIXES.iter().any(|prefix| query.starts_with(prefix))
+        && calc::expression(query).is_none()
+}
+
+/// Percent-encodes every byte of `text` but the unreserved URI characters,
+/// so spaces and non-ASCII survive in a query string.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// The engine of the web search offered when nothing matched.
+const WEB_ENGINE: &str = "the web";
+
+/// `template` with `{}` replaced by the percent-encoded query, or the
+/// query appended if it has no `{}`.
+fn web_search_url(template: &str, query: &str) -> String {
+    let encoded = percent_encode(query.trim());
+    if template.contains("{}") {
+        template.replace("{}", &encoded)
+    } else {
+        format!("{}{}", template, encoded)
+    }
+}
+
+fn update_results(listbox: &ListBox, state: &LauncherState, query: &str) {
+    let provider = providers::route(&state.providers, query);
+    for (index, other) in state.providers.iter().enumerate() {
+        if index != provider {
+            other.cancel(state);
+        }
+    }
+    let refresh: providers::Refresh = {
+        let listbox = listbox.clone();
+        let state = state.clone();
+        let query = query.to_string();
+        Rc::new(move || update_results(&listbox, &state, &query))
+    };
+    let mut items = state.providers[provider].query(state, query, &refresh);
+    let app_search = state.providers[provider].prefix().is_none();
+    if state.dmenu.is_none() && state.open_with.is_none() && state.dropped.borrow().is_none() {
+        add_extra_items(&mut items, listbox, state, query, provider);
+    }
+    // A blank window reads as broken, so say that nothing matched, once
+    // plugins, bangs and a typed URL have all had their turn.
+    if app_search && !query.trim().is_empty() && !items.iter().any(ResultItem::is_match) {
+        let index = items
+            .iter()
+            .position(ResultItem::is_selectable)
+            .unwrap_or(items.len());
+        items.insert(
+            index,
+            ResultItem::Header(format!("No matches for '{}'", query.trim())),
+        );
+    }
+    show_match_count(state, &items, app_search);
+    let mut view_state = state.view.borrow_mut();
+    view_state.provider = provider;
+    view_state.items = items;
+    view_state.selected_index = first_selectable_index(&view_state.items);
+    // New results start from the top.
+    if let Some(adjustment) = listbox.adjustment() {
+        adjustment.set_value(0.0);
+    }
+    rerender(listbox, state, &view_state);
+}
+
+/// "7 of 312" apps next to the title, or how many results other modes
+/// found.
+fn show_match_count(state: &LauncherState, items: &[ResultItem], app_search: bool) {
+    let text = if app_search && state.dmenu.is_none() {
+        let matched: HashSet<&str> = items
+            .iter()
+            .filter_map(|item| match item {
+                ResultItem::App(app) => Some(app.key.as_str()),
+                _ => None,
+            })
+            .collect();
+        format!("{} of {}", matched.len(), state.apps.borrow().len())
+    } else {
+        let found = items.iter().filter(|item| item.is_selectable()).count();
+        if found == 1 {
+            "1 result".to_string()
+        } else {
+            format!("{} results", found)
+        }
+    };
+    state
+        .match_count
+        .set_markup(&format!("<small>{}</small>", text));
+}
+
+/// Plugin results, the snooze indicator and notices above whatever the
+/// provider found, and the typed URL, create-launcher and web search rows
+/// below it.
+fn add_extra_items(
+    items: &mut Vec<ResultItem>,
+    listbox: &ListBox,
+    state: &LauncherState,
+    query: &str,
+    provider: usize,
+) {
+    // Plugins and bangs merge with the app search rather than another mode.
+    let app_search = state.providers[provider].prefix().is_none();
+    if app_search {
+        let plugin_items = plugin_items(listbox, state, query);
+        items.splice(0..0, plugin_items);
+    }
+    if app_search && let Some((bang, search, exact)) = bangs::parse(&state.bangs, query) {
+        let item = ResultItem::WebSearch {
+            engine: bang.name.clone(),
+            query: search.to_string(),
+            url: web_search_url(&bang.template, search),
+        };
+        // A keyword typed in other case might be the start of an app's
+        // name, so the apps keep Enter then.
+        if exact {
+            items.insert(0, item);
+        } else {
+            items.push(item);
+        }
+    }
+    if let Some(deadline) = state.snooze_deadline() {
+        items.insert(
+            0,
+            ResultItem::Header(snooze::indicator(deadline, now_unix())),
+        );
+    }
+    if let Some(notice) = state.notice.borrow_mut().take() {
+        items.insert(0, ResultItem::Header(notice));
+    }
+    let nothing_found = !items.iter().any(ResultItem::is_selectable);
+    let typed_url = is_app_query(query).then(|| url::parse(query)).flatten();
+    if let Some(url) = &typed_url {
+        items.push(ResultItem::OpenUrl(url.clone()));
+    }
+    // Only offered as a last resort, so Enter never goes to the web while
+    // something local matched.
+    if nothing_found
+        && typed_url.is_none()
+        && is_app_query(query)
+        && let Some(template) = &state.web_search_url
+    {
+        items.push(ResultItem::WebSearch {
+            engine: WEB_ENGINE.to_string(),
+            query: query.trim().to_string(),
+            url: web_search_url(template, query),
+        });
+    }
+    if !state.options.demo && is_app_query(query) {
+        items.push(ResultItem::CreateLauncher(query.trim().to_string()));
+    }
+}
+
+/// Files dragged onto the window. The app search lists only their
+/// handlers, which open them, until `Escape` drops them again.
+struct Dropped {
+    files: Vec<gio::File>,
+    /// "notes.txt", or "3 files".
+    name: String,
+    handlers: Vec<AppEntry>,
+}
+
+/// Takes files dropped on the window as the ones to open, listing the
+/// apps that handle all of them.
+fn connect_file_drop(
+    window: &ApplicationWindow,
+    entry: &Entry,
+    listbox: &ListBox,
+    state: &LauncherState,
+) {
+    if state.dmenu.is_some() || state.open_with.is_some() {
+        return;
+    }
+    let targets = [gtk::TargetEntry::new(
+        "text/uri-list",
+        gtk::TargetFlags::OTHER_APP,
+        0,
+    )];
+    window.drag_dest_set(gtk::DestDefaults::ALL, &targets, gdk::DragAction::COPY);
+
+    let entry = entry.clone();
+    let listbox = listbox.clone();
+    let state = state.clone();
+    window.connect_drag_data_received(move |_, _, _, _, data, _, _| {
+        let files: Vec<gio::File> = data
+            .uris()
+            .iter()
+            .map(|uri| gio::File::for_uri(uri))
+            .collect();
+        if files.is_empty() {
+            return;
+        }
+        let mut content_types = Vec::new();
+        for file in &files {
+            match open_with::content_type(file) {
+                Ok(content_type) => content_types.push(content_type),
+                Err(err) => {
+                    eprintln!("Can't open {}: {}", file.uri(), err.message());
+                    return;
+                }
+            }
+        }
+        let name = match files.as_slice() {
+            [file] => file.basename().map_or_else(
+                || file.uri().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            files => format!("{} files", files.len()),
+        };
+        let handlers = open_with::common_handlers(&content_types)
+            .into_iter()
+            .filter_map(build_app_entry)
+            .collect();
+        state.dropped.replace(Some(Dropped {
+            files,
+            name,
+            handlers,
+        }));
+        entry.set_text("");
+        update_results(&listbox, &state, "");
+        entry.grab_focus();
+    });
+}
+
+/// Forgets dropped files, going back to the usual search. Returns false
+/// if there were none.
+fn clear_dropped(entry: &Entry, listbox: &ListBox, state: &LauncherState) -> bool {
+    if state.dropped.take().is_none() {
+        return false;
+    }
+    update_results(listbox, state, &entry.text());
+    true
+}
+
+/// What a plugin printed for a query.
+struct PluginOutput {
+    query: String,
+    results: Vec<plugins::PluginResult>,
+}
+
+/// The section of the plugin the query is meant for, under a header named
+/// after it. The first time a query is seen, this starts the plugin and
+/// returns nothing; the results are shown once it has answered.
+fn plugin_items(listbox: &ListBox, state: &LauncherState, query: &str) -> Vec<ResultItem> {
+    let matched = state
+        .plugins
+        .iter()
+        .position(|plugin| plugin.argument(query).is_some());
+    let Some(index) = matched else {
+        // Whatever is still running was for an older query.
+        state
+            .plugin_generation
+            .set(state.plugin_generation.get().wrapping_add(1));
+        return Vec::new();
+    };
+
+    if let Some(output) = &*state.plugin_output.borrow()
+        && output.query == query
+    {
+        if output.results.is_empty() {
+            return Vec::new();
+        }
+        let header = ResultItem::Header(state.plugins[index].name.clone());
+        return std::iter::once(header)
+            .chain(output.results.iter().cloned().map(ResultItem::Plugin))
+            .collect();
+    }
+    run_plugin_later(listbox, state, index, query.to_string());
+    Vec::new()
+}
+
+/// Runs plugin `index` once typing pauses, off the main thread, then shows
+/// its results if the query hasn't changed in the meantime.
+fn run_plugin_later(listbox: &ListBox, state: &LauncherState, index: usize, query: String) {
+    let generation = state.plugin_generation.get().wrapping_add(1);
+    state.plugin_generation.set(generation);
+
+    let listbox = listbox.clone();
+    let state = state.clone();
+    gtk::glib::timeout_add_local_once(PLUGIN_DEBOUNCE, move || {
+        if state.plugin_generation.get() != generation {
+            return;
+        }
+        gtk::glib::MainContext::default().spawn_local(async move {
+            let plugin = &state.plugins[index];
+            let path = plugin.path.clone();
+            let argument = plugin.argument(&query).unwrap_or_default().to_string();
+            let results = gio::spawn_blocking(move || plugins::run(&path, &argument))
+                .await
+                .unwrap_or_else(|_| Err("panicked".to_string()));
+            if state.plugin_generation.get() != generation {
+                return;
+            }
+            let results = results.unwrap_or_else(|err| {
+                eprintln!("Plugin {} failed: {}", plugin.name, err);
+                Vec::new()
+            });
+            state.plugin_output.replace(Some(PluginOutput {
+                query: query.clone(),
+                results,
+            }));
+            update_results(&listbox, &state, &query);
+        });
+    });
+}
+
+/// Redraws the current view without rebuilding it, after the rows or the
+/// expanded app changed. The rows through the selection and a chunk past
+/// it are built now and the rest after, so the few hundred of an empty
+/// search don't hold up typing.
+fn rerender(listbox: &ListBox, state: &LauncherState, view_state: &ViewState) {
+    let numbered = numbered_items(listbox, state, view_state);
+    if let Some(grid) = &state.grid {
+        grid.clear();
+    }
+    clear_listbox(listbox);
+    state
+        .render_generation
+        .set(state.render_generation.get().wrapping_add(1));
+
+    let total = view_state.items.len().min(MAX_RENDERED);
+    let first = view_state
+        .selected_index
+        .map_or(0, |selected| selected + 1)
+        .saturating_add(RENDER_CHUNK)
+        .min(total);
+    render_rows(listbox, state, view_state, 0..first, &numbered);
+    if first < total {
+        render_rest_later(listbox, state, first, numbered);
+    }
+    if let Some(selected) = view_state.selected_index
+        && let Ok(selected) = i32::try_from(selected)
+        && let Some(row) = listbox.row_at_index(selected)
+    {
+        listbox.select_row(Some(&row));
+        scroll_to_selected_later(listbox, state);
+    }
+    fit_window_later(listbox, state);
+}
+
+/// Fits the window's height to what it shows, up to `max_height`, once
+/// the rows have stopped changing for a moment so typing doesn't make it
+/// flicker. The top edge stays put.
+fn fit_window_later(listbox: &ListBox, state: &LauncherState) {
+    let generation = state.fit_generation.get().wrapping_add(1);
+    state.fit_generation.set(generation);
+    let listbox = listbox.clone();
+    let state = state.clone();
+    gtk::glib::timeout_add_local_once(FIT_DELAY, move || {
+        if state.fit_generation.get() != generation {
+            return;
+        }
+        let Some(window) = listbox
+            .toplevel()
+            .and_then(|toplevel| toplevel.downcast::<gtk::Window>().ok())
+        else {
+            return;
+        };
+        let Some(child) = window.child() else {
+            return;
+        };
+        let (_, natural) = child.preferred_height();
+        let height = natural.min(state.max_height).max(1);
+        let (width, current) = window.size();
+        if height != current {
+            window.resize(width, height);
+        }
+    });
+}
+
+/// Moves the selection a result down, or up when `direction` is negative.
+/// In the grid that's a line, to the nearest column.
+fn move_selection(listbox: &ListBox, state: &LauncherState, direction: i32) {
+    let mut view_state = state.view.borrow_mut();
+    let Some(current) = view_state.selected_index else {
+        view_state.selected_index = first_selectable_index(&view_state.items);
+        show_selection(listbox, state, &view_state);
+        return;
+    };
+
+    let next = match &state.grid {
+        Some(grid) if !grid.is_bar() => grid.line_neighbor(current, direction, |index| {
+            view_state.items[index].is_selectable()
+        }),
+        _ => next_selectable_index(&view_state.items, current, direction),
+    };
+    // Past either end wraps around to the other, unless the config says not to.
+    let next = next.or_else(|| {
+        if !state.wrap_selection {
+            None
+        } else if direction > 0 {
+            first_selectable_index(&view_state.items)
+        } else {
+            view_state.items.iter().rposition(ResultItem::is_selectable)
+        }
+    });
+    if let Some(next) = next
+        && next != current
+    {
+        view_state.selected_index = Some(next);
+        show_selection(listbox, state, &view_state);
+    }
+}
+
+/// In the grid or the bar, moves the selection to the next result, or the
+/// previous one when `direction` is negative. False in the list, or at
+/// either end, so the key can move the cursor instead.
+fn move_selection_sideways(listbox: &ListBox, state: &LauncherState, direction: i32) -> bool {
+    if state.grid.is_none() {
+        return false;
+    }
+    let mut view_state = state.view.borrow_mut();
+    let Some(next) = view_state
+        .selected_index
+        .and_then(|current| next_selectable_index(&view_state.items, current, direction))
+    else {
+        return false;
+    };
+    view_state.selected_index = Some(next);
+    show_selection(listbox, state, &view_state);
+    true
+}
+
+/// Clears a non-empty search, which brings back the usual results, so a
+/// second Escape is needed to close. Off with `escape_clears = false`.
+fn clear_query_first(entry: &Entry, state: &LauncherState) -> bool {
+    if !state.escape_clears || entry.text().is_empty() {
+        return false;
+    }
+    entry.set_text("");
+    true
+}
+
+/// Deletes the word before the cursor, and the spaces after it, as
+/// readline's Ctrl+W does.
+fn delete_word_before_cursor(entry: &Entry) {
+    let cursor = entry.position();
+    let text = entry.text();
+    let before: Vec<char> = text.chars().take(cursor.max(0) as usize).collect();
+    let mut start = before.len();
+    while start > 0 && before[start - 1].is_whitespace() {
+        start -= 1;
+    }
+    while start > 0 && !before[start - 1].is_whitespace() {
+        start -= 1;
+    }
+    entry.delete_text(start as i32, cursor);
+}
+
+/// The row of the nth app on screen, counting from 1 and skipping
+/// headers and other kinds of rows, as Alt+number numbers them.
+fn nth_app_row(listbox: &ListBox, state: &LauncherState, n: u8) -> Option<i32> {
+    let first_visible = first_visible_row(listbox, state);
+    let view_state = state.view.borrow();
+    let (index, _) = view_state
+        .items
+        .iter()
+        .enumerate()
+        .skip(first_visible)
+        .filter(|(_, item)| matches!(item, ResultItem::App(_)))
+        .nth(usize::from(n).checked_sub(1)?)?;
+    i32::try_from(index).ok()
+}
+
+/// Numbers the app rows while Alt is held, unless the config keeps the
+/// numbers on anyway.
+fn set_number_hints(listbox: &ListBox, state: &LauncherState, shown: bool) {
+    if state.number_hints {
+        return;
+    }
+    let mut view_state = state.view.borrow_mut();
+    if view_state.number_hints == shown {
+        return;
+    }
+    view_state.number_hints = shown;
+    rerender(listbox, state, &view_state);
+}
+
+fn is_alt_key(key: &gdk::keys::Key) -> bool {
+    *key == gdk::keys::constants::Alt_L || *key == gdk::keys::constants::Alt_R
+}
+
+/// Selects the first result, scrolled back to the top.
+fn select_first(listbox: &ListBox, state: &LauncherState) {
+    let mut view_state = state.view.borrow_mut();
+    view_state.selected_index = first_selectable_index(&view_state.items);
+    if let Some(adjustment) = listbox.adjustment() {
+        adjustment.set_value(0.0);
+    }
+    show_selection(listbox, state, &view_state);
+}
+
+/// Selects the last app, or in modes without apps the last result,
+/// scrolling to it.
+fn select_last(listbox: &ListBox, state: &LauncherState) {
+    let mut view_state = state.view.borrow_mut();
+    let items = &view_state.items;
+    let last = items
+        .iter()
+        .rposition(|item| matches!(item, ResultItem::App(_)))
+        .or_else(|| items.iter().rposition(ResultItem::is_selectable));
+    if last.is_some() {
+        view_state.selected_index = last;
+        show_selection(listbox, state, &view_state);
+    }
+}
+
+/// The desktop actions an app's entry declares, such as "New Private
+/// Window", as (action, label) pairs.
+fn desktop_actions(app: &AppEntry) -> Vec<(String, String)> {
+    let Some(desktop) = app.app_info.downcast_ref::<gio::DesktopAppInfo>() else {
+        return Vec::new();
+    };
+    desktop
+        .list_actions()
+        .iter()
+        .map(|action| (action.to_string(), desktop.action_name(action).to_string()))
+        .collect()
+}
+
+/// Drops any expanded action rows, moving a selected action to its app.
+fn remove_actions(view_state: &mut ViewState) {
+    let items = &view_state.items;
+    let selected = view_state.selected_index.map(|mut index| {
+        while index > 0 && items[index].is_action() {
+            index -= 1;
+        }
+        index
+            - items[..index]
+                .iter()
+                .filter(|item| item.is_action())
+                .count()
+    });
+    view_state.items.retain(|item| !item.is_action());
+    view_state.selected_index = selected;
+}
+
+/// Lists the selected app's desktop actions under it, selecting the
+/// first. Returns false if the selection isn't an app with actions.
+fn expand_selected_actions(listbox: &ListBox, state: &LauncherState) -> bool {
+    let mut view_state = state.view.borrow_mut();
+    let Some(selected) = view_state.selected_index else {
+        return false;
+    };
+    let Some(ResultItem::App(app)) = view_state.items.get(selected) else {
+        return false;
+    };
+    let parent_key = app.key.clone();
+    let mut actions = desktop_actions(app);
+    if state.gpu_env.is_some() {
+        actions.push(gpu::action(app.dedicated_gpu));
+    }
+    if actions.is_empty() {
+        return false;
+    }
+
+    remove_actions(&mut view_state);
+    let Some(selected) = view_state.selected_index else {
+        return false;
+    };
+    let rows = actions
+        .into_iter()
+        .map(|(action, label)| ResultItem::Action {
+            parent_key: parent_key.clone(),
+            action,
+            label,
+        });
+    view_state.items.splice(selected + 1..selected + 1, rows);
+    view_state.selected_index = Some(selected + 1);
+    rerender(listbox, state, &view_state);
+    true
+}
+
+/// Folds expanded actions back into their app. Returns false if nothing
+/// was expanded.
+fn collapse_actions(listbox: &ListBox, state: &LauncherState) -> bool {
+    let mut view_state = state.view.borrow_mut();
+    if !view_state.items.iter().any(ResultItem::is_action) {
+        return false;
+    }
+    remove_actions(&mut view_state);
+    rerender(listbox, state, &view_state);
+    true
+}
+
+/// The item behind the row at `index`, unless that's a header.
+fn item_at(state: &LauncherState, index: i32) -> Option<ResultItem> {
+    let index = usize::try_from(index).ok()?;
+    state
+        .view
+        .borrow()
+        .items
+        .get(index)
+        .filter(|item| item.is_selectable())
+        .cloned()
+}
+
+fn selected_item(listbox: &ListBox, state: &LauncherState) -> Option<ResultItem> {
+    item_at(state, listbox.selected_row()?.index())
+}
+
+fn selected_app(listbox: &ListBox, state: &LauncherState) -> Option<AppEntry> {
+    match selected_item(listbox, state)? {
+        ResultItem::App(app) => Some(app),
+        _ => None,
+    }
+}
+
+fn quit_later(delay: Duration) {
+    gtk::glib::timeout_add_local_once(delay, || {
+        if let Some(app) = gio::Application::default() {
+            dismiss(&app);
+        }
+    });
+}
+
+/// What `Ctrl+C` and `Ctrl+Shift+C` copy from the selected app.
+#[derive(Clone, Copy)]
+enum AppDetail {
+    Command,
+    DesktopFile,
+}
+
+/// The app's command line or desktop file path, or the other one when the
+/// entry lacks it: custom entries have no desktop file, and some
+/// entries no command.
+fn app_detail(app: &AppEntry, detail: AppDetail) -> Option<String> {
+    let command = || {
+        app.app_info
+            .commandline()
+            .map(|command| command.to_string_lossy().into_owned())
+    };
+    let desktop_file = || {
+        app.app_info
+            .downcast_ref::<gio::DesktopAppInfo>()
+            .and_then(|desktop| desktop.filename())
+            .map(|path| path.display().to_string())
+    };
+    match detail {
+        AppDetail::Command => command().or_else(desktop_file),
+        AppDetail::DesktopFile => desktop_file().or_else(command),
+    }
+}
+
+/// Copies the selected app's command or desktop file path, says so at the
+/// top of the results and, unless configured not to, quits a moment
+/// later. Returns false if no app is selected.
+fn copy_app_detail(listbox: &ListBox, state: &LauncherState, detail: AppDetail) -> bool {
+    let Some(app) = selected_app(listbox, state) else {
+        return false;
+    };
+    let message = match app_detail(&app, detail) {
+        Some(text) => {
+            clipboard::copy(&text);
+            if state.quit_after_copy && !state.launching.replace(true) {
+                quit_later(COPY_CONFIRM_TIMEOUT);
+            }
+            format!("Copied {}", text)
+        }
+        None => format!("Nothing to copy for {}", app.name),
+    };
+    let mut view_state = state.view.borrow_mut();
+    view_state.items.insert(0, ResultItem::Header(message));
+    view_state.selected_index = view_state.selected_index.map(|index| index + 1);
+    rerender(listbox, state, &view_state);
+    true
+}
+
+/// Opens the selected app's desktop file in the default text editor.
+/// Entries without one get their command copied instead, saying so. Returns
+/// whether hyperfind should quit.
+fn edit_selected_desktop_file(
+    listbox: &ListBox,
+    state: &LauncherState,
+    error_bar: &ErrorBar,
+) -> bool {
+    let Some(app) = selected_app(listbox, state) else {
+        return false;
+    };
+    let path = app
+        .app_info
+        .downcast_ref::<gio::DesktopAppInfo>()
+        .and_then(|desktop| desktop.filename());
+    let Some(path) = path else {
+        let message = match app_detail(&app, AppDetail::Command) {
+            Some(command) => {
+                clipboard::copy(&command);
+                format!("{} has no desktop file; copied its command", app.name)
+            }
+            None => format!("{} has no desktop file", app.name),
+        };
+        let mut view_state = state.view.borrow_mut();
+        view_state.items.insert(0, ResultItem::Header(message));
+        view_state.selected_index = view_state.selected_index.map(|index| index + 1);
+        rerender(listbox, state, &view_state);
+        return false;
+    };
+    if state.launching.replace(true) {
+        return true;
+    }
+    if state.options.demo {
+        println!("Would edit {}", path.display());
+        return true;
+    }
+
+    let file = gio::File::for_path(&path);
+    let context = launch_context(state);
+    // The default for a desktop file may well be to run it.
+    let opened = match gio::AppInfo::default_for_type("text/plain", false) {
+        Some(editor) => editor.launch(&[file], context.as_ref()),
+        None => gio::AppInfo::launch_default_for_uri(&file.uri(), context.as_ref()),
+    };
+    if let Err(err) = opened {
+        eprintln!("Failed to open {}: {}", path.display(), err);
+        state.launching.set(false);
+        show_error(
+            error_bar,
+            &format!("Couldn't open {}: {}", path.display(), err),
+        );
+        return false;
+    }
+    true
+}
+
+fn ignore_selected_app(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let Some(app) = selected_app(listbox, state) else {
+        return;
+    };
+
+    if !state.options.demo {
+        append_ignored(&app.key);
+    }
+    state
+        .apps
+        .borrow_mut()
+        .retain(|candidate| candidate.key != app.key);
+    update_results(listbox, state, &entry.text());
+}
+
+fn reset_selected_usage(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let Some(app) = selected_app(listbox, state) else {
+        return;
+    };
+
+    {
+        let mut usage_mut = state.usage.borrow_mut();
+        let mut trash_mut = state.trash.borrow_mut();
+        if !trash_usage(&app.key, &mut usage_mut, &mut trash_mut, now_unix()) {
+            return;
+        }
+        if !state.options.demo {
+            // Only this key: saving the whole map would put this
+            // instance's stale copy over launches others recorded since.
+            let now = now_unix();
+            let trashed = state.store.borrow_mut().update(&mut |on_disk, trash| {
+                trash_usage(&app.key, on_disk, trash, now);
+            });
+            warn_save_error(trashed, &state.warnings);
+        }
+    }
+    update_results(listbox, state, &entry.text());
+}
+
+fn restore_selected_usage(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let Some(app) = selected_app(listbox, state) else {
+        return;
+    };
+
+    {
+        let mut usage_mut = state.usage.borrow_mut();
+        let mut trash_mut = state.trash.borrow_mut();
+        if !restore_usage(&app.key, &mut usage_mut, &mut trash_mut) {
+            return;
+        }
+        if !state.options.demo {
+            let restored = state.store.borrow_mut().update(&mut |on_disk, trash| {
+                restore_usage(&app.key, on_disk, trash);
+            });
+            warn_save_error(restored, &state.warnings);
+        }
+    }
+    update_results(listbox, state, &entry.text());
+}
+
+fn toggle_selected_pin(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let Some(app) = selected_app(listbox, state) else {
+        return;
+    };
+
+    {
+        let mut curation_mut = state.curation.borrow_mut();
+        toggle_key(&app.key, &mut curation_mut.pinned);
+        if !state.options.demo {
+            save_keys(&pins_path(), &curation_mut.pinned);
+        }
+    }
+    update_results(listbox, state, &entry.text());
+}
+
+/// Adds the selected app to the apps started at login, or takes it off
+/// them if it's there already.
+fn toggle_selected_autostart(
+    entry: &Entry,
+    listbox: &ListBox,
+    state: &LauncherState,
+    error_bar: &ErrorBar,
+) {
+    let Some(app) = selected_app(listbox, state) else {
+        return;
+    };
+    let enable = !app.autostart;
+    if state.options.demo {
+        println!("Would toggle autostart for {} ({})", app.name, app.key);
+        return;
+    }
+    let toggled = if enable {
+        autostart::enable(&app.key, &app.app_info)
+    } else {
+        autostart::disable(&app.key)
+    };
+    if let Err(err) = toggled {
+        eprintln!("{}", err);
+        show_error(error_bar, &err);
+        return;
+    }
+    for known in state.apps.borrow_mut().iter_mut() {
+        if known.key == app.key {
+            known.autostart = enable;
+        }
+    }
+    update_results(listbox, state, &entry.text());
+}
+
+fn toggle_selected_frequent_exclusion(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let Some(app) = selected_app(listbox, state) else {
+        return;
+    };
+
+    {
+        let mut curation_mut = state.curation.borrow_mut();
+        toggle_key(&app.key, &mut curation_mut.frequent_excluded);
+        if !state.options.demo {
+            save_keys(&frequent_excluded_path(), &curation_mut.frequent_excluded);
+        }
+    }
+    update_results(listbox, state, &entry.text());
+}
+
+/// Records a launch on another thread so the window can close without
+/// waiting on a slow home directory; `main` joins the writer before
+/// exiting. Nobody is looking at stderr by then, so failures go to the
+/// GLib log instead. Writers that have already finished are dropped, so a
+/// `--daemon` doesn't keep one for every launch.
+fn record_in_background(key: &str, saves: &PendingSaves) {
+    let key = key.to_string();
+    let save = thread::spawn(move || {
+        if let Err(err) = open_usage_store().record(&key) {
+            gtk::glib::g_warning!("hyperfind", "Failed to save usage data: {}", err);
+        }
+    });
+    let mut saves = saves.borrow_mut();
+    saves.retain(|save| !save.is_finished());
+    saves.push(save);
+}
+
+/// A launch context carrying the activating event's time, so window
+/// managers with focus-stealing prevention let the new window come to the
+/// front, and a startup notification for it.
+fn launch_context(state: &LauncherState) -> Option<gdk::AppLaunchContext> {
+    let display = gdk::Display::default()?;
+    let context = display.app_launch_context()?;
+    context.set_screen(&display.default_screen());
+    context.set_timestamp(state.event_time.get());
+    context.connect_launch_failed(|_, startup_id| {
+        eprintln!("Launch with startup id {} failed", startup_id);
+    });
+    Some(context)
+}
+
+/// `launch_context` with the variables configured for the app with
+/// `key` set, for that launch alone.
+fn app_launch_context(key: &str, state: &LauncherState) -> Option<gdk::AppLaunchContext> {
+    let context = launch_context(state)?;
+    for (name, value) in state.app_env.get(key).into_iter().flatten() {
+        context.setenv(name, value);
+    }
+    Some(context)
+}
+
+/// Called with each process `launch_uris_as_manager` starts.
+type PidCallback<'a> = &'a mut dyn FnMut(&gio::DesktopAppInfo, gtk::glib::Pid);
+
+/// Starts an app with `context`, in the configured terminal if it needs
+/// one; GIO would pick its own terminal, or none.
+fn spawn_app(
+    app: &AppEntry,
+    files: &[gio::File],
+    context: Option<&gdk::AppLaunchContext>,
+    state: &LauncherState,
+) -> Result<(), String> {
+    if app.terminal
+        && let Some(exec) = app.app_info.commandline()
+    {
+        // The terminal is started like any other app, so it gets the same
+        // environment, scope and detaching, and the files are already on
+        // its command line.
+        let command = terminal::exec_command(&exec.to_string_lossy(), files);
+        let command_line = terminal::command_line(&state.terminal, &command);
+        let info = gio::AppInfo::create_from_commandline(
+            &command_line,
+            Some(&app.name),
+            gio::AppInfoCreateFlags::NONE,
+        )
+        .map_err(|err| err.to_string())?;
+        return launch_with(&info, &[], context, app, state);
+    }
+    launch_with(&app.app_info, files, context, app, state)
+}
+
+/// Starts `info` for `app` with `context`, cut loose from hyperfind and
+/// moved into its own scope when configured.
+fn launch_with(
+    info: &gio::AppInfo,
+    files: &[gio::File],
+    context: Option<&gdk::AppLaunchContext>,
+    app: &AppEntry,
+    state: &LauncherState,
+) -> Result<(), String> {
+    if app.dedicated_gpu
+        && let (Some(context), Some(env)) = (context, &state.gpu_env)
+    {
+        for (name, value) in env.iter() {
+            context.setenv(name, value);
+        }
+    }
+    let Some(desktop) = info.downcast_ref::<gio::DesktopAppInfo>() else {
+        return info.launch(files, context).map_err(|err| err.to_string());
+    };
+    // Launched by hand rather than through `AppInfo::launch`, so the app
+    // can be cut loose from hyperfind before it starts.
+    let uris: Vec<String> = files.iter().map(|file| file.uri().to_string()).collect();
+    let uris: Vec<&str> = uris.iter().map(String::as_str).collect();
+    let mut on_pid = |_: &gio::DesktopAppInfo, child: gtk::glib::Pid| {
+        detach::move_to_scope(&app.key, child);
+    };
+    let pid_callback: Option<PidCallback> = state.systemd_scope.then_some(&mut on_pid);
+    desktop
+        .launch_uris_as_manager(
+            &uris,
+            context,
+            gtk::glib::SpawnFlags::SEARCH_PATH,
+            Some(Box::new(detach::child_setup)),
+            pid_callback,
+        )
+        .map_err(|err| err.to_string())
+}
+
+/// Why an activation didn't start anything.
+enum LaunchError {
+    /// Starting it failed; `verb` is what was being done to `name`.
+    Spawn {
+        verb: &'static str,
+        name: String,
+        reason: String,
+    },
+    /// The app, by key, has gone away since the results were listed.
+    NotFound(String),
+    /// Another activation is already launching something.
+    Cancelled,
+}
+
+impl std::fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LaunchError::Spawn { verb, name, reason } => {
+                write!(f, "Couldn't {} {}: {}", verb, name, reason)
+            }
+            LaunchError::NotFound(key) => write!(f, "{} is no longer installed", key),
+            LaunchError::Cancelled => write!(f, "Already launching"),
+        }
+    }
+}
+
+/// What a launch context said about the app launched with it.
+#[derive(Clone, Copy, PartialEq)]
+enum Startup {
+    Launched,
+    Failed,
+}
+
+/// Listens to a launch context's `launched` and `launch-failed` signals.
+/// GIO emits them during the launch for apps it spawns, and may not at all
+/// for apps it activates over D-Bus, so the first one is kept until
+/// `wait_for_startup` takes over.
+#[derive(Default)]
+struct StartupWatch {
+    heard: Cell<Option<Startup>>,
+    then: RefCell<Option<OnStartup>>,
+}
+
+type OnStartup = Box<dyn FnOnce(Startup)>;
+
+impl StartupWatch {
+    fn connect(context: &gdk::AppLaunchContext) -> Rc<StartupWatch> {
+        let watch = Rc::new(StartupWatch::default());
+        let watch_for_launched = Rc::clone(&watch);
+        context.connect_launched(move |_, _, _| watch_for_launched.hear(Startup::Launched));
+        let watch_for_failed = Rc::clone(&watch);
+        context.connect_launch_failed(move |_, _| watch_for_failed.hear(Startup::Failed));
+        watch
+    }
+
+    fn hear(&self, startup: Startup) {
+        if self.heard.get().is_some() {
+            return;
+        }
+        self.heard.set(Some(startup));
+        if let Some(then) = self.then.take() {
+            then(startup);
+        }
+    }
+}
+
+/// Launches an app, passing it `files` to open. With `--wait-for-startup`
+/// the launcher hides and `awaiting_startup` holds off the quit.
+fn launch_app(
+    app: &AppEntry,
+    files: &[gio::File],
+    state: &LauncherState,
+) -> Result<(), LaunchError> {
+    // The key handler and row-activated can both fire before the quit
+    // lands; only the first one spawns the app.
+    if state.launching.replace(true) {
+        return Err(LaunchError::Cancelled);
+    }
+
+    if state.options.demo {
+        println!("Would launch {} ({})", app.name, app.key);
+        return Ok(());
+    }
+
+    let context = app_launch_context(&app.key, state);
+    let watch = context
+        .as_ref()
+        .filter(|_| state.startup_wait.is_some())
+        .map(StartupWatch::connect);
+    let mut spawned = spawn_app(app, files, context.as_ref(), state);
+    if spawned.is_ok()
+        && watch.as_ref().and_then(|watch| watch.heard.get()) == Some(Startup::Failed)
+    {
+        spawned = Err("it failed to start".to_string());
+    }
+    if let Err(err) = spawned {
+        eprintln!("Failed to launch {}: {}", app.name, err);
+        record_launch_failure(app, &err, state);
+        return Err(LaunchError::Spawn {
+            verb: "launch",
+            name: app.name.clone(),
+            reason: err,
+        });
+    }
+    if let (Some(watch), Some(wait)) = (watch, state.startup_wait)
+        && watch.heard.get().is_none()
+    {
+        wait_for_startup(app, watch, wait, state);
+        return Ok(());
+    }
+    record_launch(app, state);
+    Ok(())
+}
+
+fn record_launch_failure(app: &AppEntry, err: &str, state: &LauncherState) {
+    events::emit(
+        &events::Event::LaunchFailed {
+            key: &app.key,
+            name: &app.name,
+            error: err.to_string(),
+        },
+        now_unix(),
+    );
+    if !state.options.private {
+        let mut usage_mut = state.usage.borrow_mut();
+        record_failure(&app.key, &mut usage_mut);
+        warn_save_error(
+            state.store.borrow_mut().record_failure(&app.key),
+            &state.warnings,
+        );
+    }
+    state.launching.set(false);
+}
+
+/// Hides the launcher, keeping it around for `wait` to hear how the launch
+/// went. `launch-failed` brings the launcher back saying so; `launched`,
+/// or hearing nothing in that time, quits.
+fn wait_for_startup(
+    app: &AppEntry,
+    watch: Rc<StartupWatch>,
+    wait: Duration,
+    state: &LauncherState,
+) {
+    let windows = gio::Application::default()
+        .and_then(|app| app.downcast::<Application>().ok())
+        .map(|app| app.windows())
+        .unwrap_or_default();
+    // Unmapping lets go of the keyboard grab until the window comes back.
+    for window in &windows {
+        window.hide();
+    }
+    state.awaiting_startup.set(true);
+
+    let app = app.clone();
+    let state_for_heard = state.clone();
+    watch.then.replace(Some(Box::new(move |startup| {
+        let state = &state_for_heard;
+        if startup == Startup::Launched {
+            record_launch(&app, state);
+            quit_later(Duration::ZERO);
+            return;
+        }
+        let err = "it failed to start";
+        eprintln!("{} failed to start", app.name);
+        record_launch_failure(&app, err, state);
+        state
+            .warnings
+            .borrow_mut()
+            .push(format!("{} didn't start: {}", app.name, err));
+        state.awaiting_startup.set(false);
+        for window in &windows {
+            window.present();
+        }
+    })));
+
+    gtk::glib::timeout_add_local_once(wait, move || watch.hear(Startup::Launched));
+}
+
+fn record_launch(app: &AppEntry, state: &LauncherState) {
+    events::emit(
+        &events::Event::Launched {
+            key: &app.key,
+            name: &app.name,
+        },
+        now_unix(),
+    );
+
+    // Hidden entries are one-off launches; keep them out of Frequently Used.
+    if !app.hidden && !state.options.private {
+        let mut usage_mut = state.usage.borrow_mut();
+        record_usage(&app.key, &mut usage_mut, now_unix());
+        record_in_background(&app.key, &state.saves);
+    }
+}
+
+/// Shown at the top of the results while an elevated launch waits for the
+/// password.
+const ELEVATE_WAITING: &str = "Waiting for authentication…";
+
+/// Starts the app's command through `elevate_command`, returning the pid
+/// to watch; it isn't reaped until that watch is added.
+fn spawn_elevated(app: &AppEntry, state: &LauncherState) -> Result<gtk::glib::Pid, String> {
+    let exec = app.app_info.commandline().ok_or("it has no command line")?;
+    let command = format!(
+        "{} {}",
+        state.elevate_command,
+        terminal::exec_command(&exec.to_string_lossy(), &[])
+    );
+    let info = gio::AppInfo::create_from_commandline(
+        &command,
+        Some(&app.name),
+        gio::AppInfoCreateFlags::NONE,
+    )
+    .map_err(|err| err.to_string())?;
+    let desktop = info
+        .downcast::<gio::DesktopAppInfo>()
+        .map_err(|_| "it can't be started as a desktop entry".to_string())?;
+    let mut pid = None;
+    let mut keep_pid = |_: &gio::DesktopAppInfo, child: gtk::glib::Pid| pid = Some(child);
+    desktop
+        .launch_uris_as_manager(
+            &[],
+            launch_context(state).as_ref(),
+            gtk::glib::SpawnFlags::SEARCH_PATH | gtk::glib::SpawnFlags::DO_NOT_REAP_CHILD,
+            Some(Box::new(detach::child_setup)),
+            Some(&mut keep_pid),
+        )
+        .map_err(|err| err.to_string())?;
+    pid.ok_or_else(|| "it didn't start".to_string())
+}
+
+/// Runs the selected app as root through `elevate_command` (pkexec unless
+/// configured otherwise). A row says so while the password dialog is up;
+/// hyperfind records the launch and quits once the command is running,
+/// and stays open with an error if authentication is cancelled or fails.
+fn launch_elevated(app: AppEntry, state: &LauncherState, listbox: &ListBox, error_bar: &ErrorBar) {
+    if state.launching.replace(true) {
+        return;
+    }
+    if state.options.demo {
+        println!("Would launch {} ({}) as root", app.name, app.key);
+        quit_later(Duration::ZERO);
+        return;
+    }
+    let pid = match spawn_elevated(&app, state) {
+        Ok(pid) => pid,
+        Err(err) => {
+            state.launching.set(false);
+            show_error(
+                error_bar,
+                &format!("Couldn't launch {} as root: {}", app.name, err),
+            );
+            return;
+        }
+    };
+    {
+        let mut view_state = state.view.borrow_mut();
+        view_state
+            .items
+            .insert(0, ResultItem::Header(ELEVATE_WAITING.to_string()));
+        view_state.selected_index = view_state.selected_index.map(|index| index + 1);
+        rerender(listbox, state, &view_state);
+    }
+
+    let program = state
+        .elevate_command
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let finished = Rc::new(Cell::new(false));
+    let app = Rc::new(app);
+
+    let finished_for_exit = Rc::clone(&finished);
+    let app_for_exit = Rc::clone(&app);
+    let program_for_exit = program.clone();
+    let state_for_exit = state.clone();
+    let listbox = listbox.clone();
+    let error_bar = error_bar.clone();
+    gtk::glib::child_watch_add_local(pid, move |_, status| {
+        let state = &state_for_exit;
+        if finished_for_exit.replace(true) {
+            return;
+        }
+        if elevate::succeeded(status) {
+            record_launch(&app_for_exit, state);
+            quit_later(Duration::ZERO);
+            return;
+        }
+        state.launching.set(false);
+        let mut view_state = state.view.borrow_mut();
+        if matches!(view_state.items.first(), Some(ResultItem::Header(text)) if text == ELEVATE_WAITING)
+        {
+            view_state.items.remove(0);
+            view_state.selected_index = view_state
+                .selected_index
+                .map(|index| index.saturating_sub(1));
+            rerender(&listbox, state, &view_state);
+        }
+        show_error(&error_bar, &elevate::failure(&program_for_exit, status));
+    });
+
+    let state = state.clone();
+    gtk::glib::timeout_add_local(elevate::POLL, move || {
+        if finished.get() {
+            return gtk::glib::ControlFlow::Break;
+        }
+        if !elevate::started(pid.0, &program) {
+            return gtk::glib::ControlFlow::Continue;
+        }
+        finished.set(true);
+        record_launch(&app, &state);
+        quit_later(Duration::ZERO);
+        gtk::glib::ControlFlow::Break
+    });
+}
+
+/// Marks the apps that have a window open.
+fn mark_running(apps: &mut [AppEntry]) {
+    let windows = running::list();
+    if windows.is_empty() {
+        return;
+    }
+    for app in apps {
+        app.running = running::find(&windows, &app.app_info, &app.key).is_some();
+    }
+}
+
+/// Brings the app's topmost window to the front, if it has one open and
+/// the config allows it. Returns whether it did, in place of a launch.
+fn switch_to_running(app: &AppEntry, state: &LauncherState) -> bool {
+    if !state.activate_running || !app.running {
+        return false;
+    }
+    let windows = running::list();
+    let Some(window) = running::find(&windows, &app.app_info, &app.key) else {
+        return false;
+    };
+    if state.launching.replace(true) {
+        return true;
+    }
+    // Sends `_NET_ACTIVE_WINDOW` for another client's window.
+    window.focus(state.event_time.get());
+    if !app.hidden && !state.options.private {
+        let mut usage_mut = state.usage.borrow_mut();
+        record_usage(&app.key, &mut usage_mut, now_unix());
+        record_in_background(&app.key, &state.saves);
+    }
+    true
+}
+
+/// Launches an app in the terminal whether or not its entry asks for one,
+/// for command-line tools whose entries leave out `Terminal=true`.
+fn launch_in_terminal(mut app: AppEntry, state: &LauncherState) -> Result<(), LaunchError> {
+    app.terminal = true;
+    launch_app(&app, &[], state)
+}
+
+/// Launches an app on the dedicated GPU, or on the default one, whichever
+/// its entry doesn't ask for.
+fn launch_on_gpu(
+    parent_key: &str,
+    dedicated: bool,
+    state: &LauncherState,
+) -> Result<(), LaunchError> {
+    let app = state
+        .apps
+        .borrow()
+        .iter()
+        .find(|app| app.key == parent_key)
+        .cloned();
+    let Some(mut app) = app else {
+        return Err(LaunchError::NotFound(parent_key.to_string()));
+    };
+    app.dedicated_gpu = dedicated;
+    launch_app(&app, &[], state)
+}
+
+/// Runs one of an app's desktop actions. GIO doesn't report whether the
+/// action started, so this only fails if the app has gone away.
+fn launch_desktop_action(
+    parent_key: &str,
+    action: &str,
+    label: &str,
+    state: &LauncherState,
+) -> Result<(), LaunchError> {
+    let desktop = state
+        .apps
+        .borrow()
+        .iter()
+        .find(|app| app.key == parent_key)
+        .and_then(|app| app.app_info.downcast_ref::<gio::DesktopAppInfo>().cloned());
+    let Some(desktop) = desktop else {
+        return Err(LaunchError::NotFound(parent_key.to_string()));
+    };
+
+    if state.launching.replace(true) {
+        return Err(LaunchError::Cancelled);
+    }
+
+    let key = action_key(parent_key, action);
+    if state.options.demo {
+        println!("Would launch {} ({})", label, key);
+        return Ok(());
+    }
+
+    desktop.launch_action(action, app_launch_context(parent_key, state).as_ref());
+    events::emit(
+        &events::Event::Launched {
+            key: &key,
+            name: label,
+        },
+        now_unix(),
+    );
+    if !state.options.private {
+        let mut usage_mut = state.usage.borrow_mut();
+        record_usage(&key, &mut usage_mut, now_unix());
+        record_in_background(&key, &state.saves);
+    }
+    Ok(())
+}
+
+/// Quits, or under `--daemon` closes the launcher and keeps running for
+/// the next one.
+fn dismiss(app: &impl IsA<gio::Application>) {
+    if !daemon::running() {
+        app.quit();
+        return;
+    }
+    if let Some(app) = app.dynamic_cast_ref::<Application>() {
+        for window in app.windows() {
+            window.close();
+        }
+    }
+}
+
+/// Runs `f` if the application shuts down while `window` is open. Under
+/// `--daemon` windows come and go, so the handler goes with its window.
+fn connect_shutdown_while_open(
+    app: &Application,
+    window: &ApplicationWindow,
+    f: impl Fn() + 'static,
+) {
+    let handler = Cell::new(Some(app.connect_shutdown(move |_| f())));
+    let app = app.clone();
+    window.connect_destroy(move |_| {
+        if let Some(handler) = handler.take() {
+            app.disconnect(handler);
+        }
+    });
+}
+
+/// Adds each text copied from now on to `history`.
+fn watch_clipboard(history: &ClipboardHistory) {
+    let history = Rc::clone(history);
+    let clipboard = gtk::Clipboard::get(&gdk::SELECTION_CLIPBOARD);
+    // gtk-rs has no binding for `owner-change`, whose event argument isn't
+    // needed anyway.
+    let clipboard_for_change = clipboard.clone();
+    clipboard.connect_local("owner-change", false, move |_| {
+        if history.borrow().paused() {
+            return None;
+        }
+        let history = Rc::clone(&history);
+        // Password managers mark what they copy; their secrets are the
+        // last thing a history should keep.
+        let targets = gdk::Atom::intern("TARGETS");
+        clipboard_for_change.request_contents(&targets, move |clipboard, data| {
+            let secret = data
+                .targets()
+                .unwrap_or_default()
+                .iter()
+                .any(|target| target.name().as_str() == clipboard::PASSWORD_HINT);
+            if secret {
+                return;
+            }
+            clipboard.request_text(move |_, text| {
+                if let Some(text) = text {
+                    history.borrow_mut().push(text);
+                }
+            });
+        });
+        None
+    });
+}
+
+/// Hands the row at `index` to the provider that produced it, or opens
+/// the create-launcher form when that row is the one activated. Returns
+/// whether hyperfind should quit.
+fn activate_index(
+    index: i32,
+    state: &LauncherState,
+    form: &CreateForm,
+    listbox: &ListBox,
+    error_bar: &ErrorBar,
+) -> bool {
+    let Some(item) = item_at(state, index) else {
+        return false;
+    };
+    // A second Enter, or row-activated right behind the key press, can
+    // arrive before the first activation's quit lands. The launch functions
+    // guard against it too; this covers every kind of row at once.
+    if state.launching.get() {
+        return false;
+    }
+    if let ResultItem::CreateLauncher(name) = &item {
+        open_create_form(form, listbox, name);
+        return false;
+    }
+    let provider = state.view.borrow().provider;
+    let outcome = state.providers[provider].activate(state, &item, listbox, error_bar);
+    finish_activation(outcome, state, error_bar)
+}
+
+/// Shift+Enter on the row at `index`: whatever its provider does there
+/// instead, or None when it does nothing else.
+fn activate_index_secondary(
+    index: i32,
+    state: &LauncherState,
+    listbox: &ListBox,
+    error_bar: &ErrorBar,
+) -> Option<bool> {
+    let item = item_at(state, index)?;
+    if state.launching.get() {
+        return Some(false);
+    }
+    let provider = state.view.borrow().provider;
+    let outcome = state.providers[provider].activate_secondary(state, &item, listbox, error_bar)?;
+    Some(finish_activation(outcome, state, error_bar))
+}
+
+/// Whether hyperfind should quit after an activation, showing why not if
+/// it failed.
+fn finish_activation(
+    outcome: providers::ActivateOutcome,
+    state: &LauncherState,
+    error_bar: &ErrorBar,
+) -> bool {
+    match outcome {
+        // `--wait-for-startup` quits, or brings the launcher back, itself.
+        providers::ActivateOutcome::Quit => !state.awaiting_startup.get(),
+        providers::ActivateOutcome::Stay => false,
+        providers::ActivateOutcome::Failed(message) => {
+            state.warnings.borrow_mut().insert(0, message);
+            show_warnings(state, error_bar);
+            false
+        }
+    }
+}
+
+/// An error strip above the results. It stays until the next key press,
+/// which may be the one that fixes the problem, or until closed.
+#[derive(Clone)]
+struct ErrorBar {
+    bar: gtk::InfoBar,
+    label: gtk::Label,
+}
+
+fn build_error_bar() -> ErrorBar {
+    let bar = gtk::InfoBar::new();
+    bar.set_message_type(gtk::MessageType::Error);
+    bar.set_show_close_button(true);
+    bar.connect_response(|bar, _| bar.hide());
+    let label = gtk::Label::new(None);
+    label.set_xalign(0.0);
+    label.set_line_wrap(true);
+    bar.content_area().add(&label);
+    bar.set_no_show_all(true);
+    ErrorBar { bar, label }
+}
+
+fn show_error(error_bar: &ErrorBar, message: &str) {
+    error_bar.label.set_text(message);
+    error_bar.label.show();
+    error_bar.bar.show();
+}
+
+/// Shows the problems that came up away from the error bar, one per line,
+/// and forgets them.
+fn show_warnings(state: &LauncherState, error_bar: &ErrorBar) {
+    let warnings = std::mem::take(&mut *state.warnings.borrow_mut());
+    if !warnings.is_empty() {
+        show_error(error_bar, &warnings.join("\n"));
+    }
+}
+
+/// Shows pending warnings whenever the window appears: at startup, and
+/// when `--wait-for-startup` brings it back.
+fn connect_warnings_on_map(
+    window: &ApplicationWindow,
+    state: &LauncherState,
+    error_bar: &ErrorBar,
+) {
+    let state = state.clone();
+    let error_bar = error_bar.clone();
+    window.connect_map_event(move |_, _| {
+        show_warnings(&state, &error_bar);
+        gtk::glib::Propagation::Proceed
+    });
+}
+
+/// The inline form behind "Create launcher for …", shown in place of the
+/// results while it is open.
+#[derive(Clone)]
+struct CreateForm {
+    container: gtk::Box,
+    name: Entry,
+    command: Entry,
+    icon: Entry,
+    error: gtk::Label,
+}
+
+fn build_create_form() -> CreateForm {
+    let container = gtk::Box::new(gtk::Orientation::Vertical, 6);
+    let name = Entry::builder().placeholder_text("Name").build();
+    let command = Entry::builder().placeholder_text("Command").build();
+    let icon = Entry::builder()
+        .placeholder_text("Icon name (optional)")
+        .build();
+    let error = gtk::Label::new(None);
+    error.set_xalign(0.0);
+    error.set_no_show_all(true);
+    let hint = gtk::Label::new(Some("Enter to save, Escape to cancel"));
+    hint.set_xalign(0.0);
+    hint.style_context().add_class("dim-label");
+
+    container.pack_start(&name, false, false, 0);
+    container.pack_start(&command, false, false, 0);
+    container.pack_start(&icon, false, false, 0);
+    container.pack_start(&error, false, false, 0);
+    container.pack_start(&hint, false, false, 0);
+    container.set_no_show_all(true);
+
+    CreateForm {
+        container,
+        name,
+        command,
+        icon,
+        error,
+    }
+}
+
+/// The scrolled window around the results, hidden with them.
+fn results_scroller(listbox: &ListBox) -> gtk::Widget {
+    listbox
+        .ancestor(gtk::ScrolledWindow::static_type())
+        .unwrap_or_else(|| listbox.clone().upcast())
+}
+
+fn open_create_form(form: &CreateForm, listbox: &ListBox, name: &str) {
+    form.name.set_text(name);
+    form.command.set_text("");
+    form.icon.set_text("");
+    form.error.hide();
+    results_scroller(listbox).hide();
+    form.container.set_no_show_all(false);
+    form.container.show_all();
+    form.command.grab_focus();
+}
+
+fn close_create_form(form: &CreateForm, entry: &Entry, listbox: &ListBox) {
+    form.container.hide();
+    results_scroller(listbox).show();
+    entry.grab_focus();
+}
+
+fn save_create_form(form: &CreateForm, entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let (path, info) =
+        match launcher_file::write(&form.name.text(), &form.command.text(), &form.icon.text()) {
+            Ok(created) => created,
+            Err(err) => {
+                form.error.set_text(&err);
+                form.error.show();
+                return;
+            }
+        };
+
+    let Some(mut app) = build_app_entry(info.upcast()) else {
+        return;
+    };
+    // Loaded by path, the entry has no desktop ID yet; use the one the next
+    // reload will give it.
+    if let Some(file_name) = path.file_name() {
+        app.key = file_name.to_string_lossy().into_owned();
+    }
+    app.sort_key = app.name.to_lowercase();
+    let name = app.name.clone();
+    {
+        let mut apps = state.apps.borrow_mut();
+        apps.retain(|candidate| candidate.key != app.key);
+        let position = apps.partition_point(|candidate| candidate.sort_key <= app.sort_key);
+        apps.insert(position, app);
+    }
+
+    close_create_form(form, entry, listbox);
+    if entry.text() == name {
+        update_results(listbox, state, &name);
+    } else {
+        entry.set_text(&name);
+    }
+}
+
+fn connect_create_form_handlers(
+    form: &CreateForm,
+    entry: &Entry,
+    listbox: &ListBox,
+    state: &LauncherState,
+) {
+    for field in [&form.name, &form.command, &form.icon] {
+        let form_for_save = form.clone();
+        let entry_for_save = entry.clone();
+        let listbox_for_save = listbox.clone();
+        let state_for_save = state.clone();
+        field.connect_activate(move |_| {
+            save_create_form(
+                &form_for_save,
+                &entry_for_save,
+                &listbox_for_save,
+                &state_for_save,
+            );
+        });
+
+        let form_for_cancel = form.clone();
+        let entry_for_cancel = entry.clone();
+        let listbox_for_cancel = listbox.clone();
+        field.connect_key_press_event(move |_, event| {
+            if event.keyval() != gdk::keys::constants::Escape {
+                return gtk::glib::Propagation::Proceed;
+            }
+            close_create_form(&form_for_cancel, &entry_for_cancel, &listbox_for_cancel);
+            gtk::glib::Propagation::Stop
+        });
+    }
+}
+
+#[derive(Clone)]
+struct LauncherState {
+    apps: Rc<RefCell<Vec<AppEntry>>>,
+    usage: Rc<RefCell<UsageMap>>,
+    trash: Rc<RefCell<UsageTrash>>,
+    store: Rc<RefCell<Box<dyn UsageStore>>>,
+    curation: Rc<RefCell<Curation>>,
+    view: Rc<RefCell<ViewState>>,
+    icons: Rc<IconCache>,
+    bindings: Rc<Vec<keymap::Binding>>,
+    notice: Rc<RefCell<Option<String>>>,
+    /// Set once the notice has pointed out key binding conflicts, which a
+    /// `--daemon` then doesn't repeat in every window.
+    conflicts_noted: Rc<Cell<bool>>,
+    /// The search before Tab last replaced it.
+    previous_query: Rc<RefCell<Option<String>>>,
+    /// Problems for the error bar from code that has no hold of it: a
+    /// config that didn't parse, usage that couldn't be saved.
+    warnings: Rc<RefCell<Vec<String>>>,
+    options: Options,
+    monitors: Rc<RefCell<Vec<gio::FileMonitor>>>,
+    ranking: Rc<Ranking>,
+    /// When a snooze of the usage display ends, in wall-clock seconds.
+    snooze_until: Rc<Cell<Option<u64>>>,
+    /// Set once an app has been launched and hyperfind is about to quit.
+    launching: Rc<Cell<bool>>,
+    /// Set while `--wait-for-startup` watches a launched app, which quits
+    /// or brings the launcher back when it's done.
+    awaiting_startup: Rc<Cell<bool>>,
+    /// When the key press or click that activated a row happened, passed
+    /// on to launched apps. 0, GDK's "now", until one is.
+    event_time: Rc<Cell<u32>>,
+    /// Moves each launched app into a systemd scope, from the config.
+    systemd_scope: bool,
+    /// Runs the command after it as root, e.g. `pkexec` or `sudo -A`.
+    elevate_command: Rc<str>,
+    quit_after_copy: bool,
+    /// Raises a running app's window rather than launching it again.
+    activate_running: bool,
+    /// Numbers stay on the app rows, rather than showing while Alt is held.
+    number_hints: bool,
+    /// Up on the first result selects the last, and Down on the last the
+    /// first.
+    wrap_selection: bool,
+    /// Escape clears the search before it closes the launcher.
+    escape_clears: bool,
+    /// Clicking another window, or losing the keyboard grab, closes the
+    /// launcher.
+    close_on_focus_loss: bool,
+    /// Icon size, padding and descriptions for the rows.
+    row_style: RowStyle,
+    /// Shows the results with `layout = "grid"`.
+    grid: Option<Rc<grid::GridView>>,
+    /// How long to watch a launched app, with `--wait-for-startup`.
+    startup_wait: Option<Duration>,
+    /// What starts an app on the dedicated GPU, or None when there's only
+    /// one GPU.
+    gpu_env: Option<Rc<gpu::Env>>,
+    /// Variables set for particular apps' launches, from the config.
+    app_env: Rc<config::AppEnv>,
+    saves: PendingSaves,
+    /// Prefix that runs a command in a terminal, from `terminal::resolve`.
+    terminal: Rc<str>,
+    ssh_hosts: Rc<Vec<String>>,
+    power_menu: bool,
+    /// Replacements for the default power commands, by action id.
+    power_commands: Rc<HashMap<String, String>>,
+    path_bins: Rc<RefCell<Vec<String>>>,
+    recent: Rc<RefCell<Vec<recent::RecentFile>>>,
+    /// Whether to search `$PATH` executables, from `--path-bins` or the
+    /// config.
+    scan_path_bins: bool,
+    bookmarks: Rc<RefCell<Vec<bookmarks::Bookmark>>>,
+    /// Whether to read browser bookmarks, from the config.
+    scan_bookmarks: bool,
+    projects: Rc<Vec<projects::Project>>,
+    /// GNOME or KDE settings panels, listed in the background and kept
+    /// for when the apps are reloaded.
+    settings_panels: Rc<RefCell<Vec<AppEntry>>>,
+    scan_settings_panels: bool,
+    /// Opens a project, with `{}` where its path goes.
+    project_command: Rc<str>,
+    duplicate_apps: config::DuplicateApps,
+    /// User services for `svc `, listed the first time the mode is used
+    /// and again after each start or stop.
+    units: Rc<RefCell<Option<systemd::Listing>>>,
+    /// Entries from the password store, when it is turned on.
+    pass_entries: Option<Rc<Vec<String>>>,
+    plugins: Rc<Vec<plugins::Plugin>>,
+    /// The latest plugin answer, kept until the next one arrives.
+    plugin_output: Rc<RefCell<Option<PluginOutput>>>,
+    /// Shows how many results the search found, next to the title.
+    match_count: gtk::Label,
+    /// Bumped whenever a plugin run is started or no longer wanted, so a
+    /// late answer for an older query is dropped.
+    plugin_generation: Rc<Cell<u32>>,
+    /// Bumped on every render so rows still being built for an older one
+    /// stop.
+    render_generation: Rc<Cell<u32>>,
+    /// Bumped for every change the window might need resizing for, so only
+    /// the last of a burst resizes it.
+    fit_generation: Rc<Cell<u32>>,
+    /// The tallest the window grows, in pixels.
+    max_height: i32,
+    /// Where the window opens, from the command line or the config.
+    position: placement::Position,
+    y_offset: i32,
+    monitor: placement::Monitor,
+    /// Set by `--dmenu`, whose lines replace every other source.
+    dmenu: Option<Rc<dmenu::Dmenu>>,
+    /// Runs `find ` searches, with the query as its last argument.
+    locate_command: Rc<Vec<String>>,
+    file_search: Rc<RefCell<Option<locate::Search>>>,
+    /// Set by `--open`, which lists only the apps that can open the file.
+    open_with: Option<Rc<open_with::OpenWith>>,
+    dropped: Rc<RefCell<Option<Dropped>>>,
+    /// Types a picked emoji as well as copying it, from the config.
+    emoji_type_command: Option<Rc<str>>,
+    /// Search engine URL template, or None with the web search turned off.
+    web_search_url: Option<Rc<str>>,
+    bangs: Rc<Vec<bangs::Bang>>,
+    /// Kept by the `--daemon` process, and shared with each window it
+    /// opens.
+    clipboard: ClipboardHistory,
+    /// Whether `clip ` lists the history, from the config.
+    clipboard_history: bool,
+    snippets: Rc<Vec<snippets::Snippet>>,
+    /// Every kind of search, from `providers::registry`.
+    providers: Rc<Vec<Box<dyn providers::Provider>>>,
+}
+
+/// Usage writes still running when the main loop exits.
+type PendingSaves = Rc<RefCell<Vec<thread::JoinHandle<()>>>>;
+
+type ClipboardHistory = Rc<RefCell<clipboard::History>>;
+
+impl LauncherState {
+    fn new(
+        options: Options,
+        saves: PendingSaves,
+        dmenu: Option<Rc<dmenu::Dmenu>>,
+        open_with: Option<Rc<open_with::OpenWith>>,
+        clipboard: ClipboardHistory,
+        conflicts_noted: Rc<Cell<bool>>,
+    ) -> Self {
+        let (config, config_warnings) = config::load_reporting();
+        let warnings = RefCell::new(config_warnings);
+        // Nothing of the user's is read for a picker or a demo.
+        let demo = options.demo || dmenu.is_some();
+        // Only the file's handlers are listed when opening a file, though
+        // their usage still counts.
+        let sources = !demo && open_with.is_none();
+        let frequent = options.frequent;
+        let mut store: Box<dyn UsageStore> = if demo {
+            Box::new(usage_store::JsonStore::new(usage_dir()))
+        } else {
+            usage_store::open(config.usage_backend)
+        };
+        let (apps, mut usage, mut trash, curation, snooze_until) =
+            if let Some(open_with) = &open_with {
+                (
+                    open_with::handlers(&open_with.content_type)
+                        .into_iter()
+                        .filter_map(build_app_entry)
+                        .collect(),
+                    if demo { HashMap::new() } else { store.load() },
+                    HashMap::new(),
+                    Curation::default(),
+                    None,
+                )
+            } else if dmenu.is_some() {
+                (
+                    Vec::new(),
+                    HashMap::new(),
+                    HashMap::new(),
+                    Curation::default(),
+                    None,
+                )
+            } else if demo {
+                (
+                    demo::apps(),
+                    demo::usage(),
+                    HashMap::new(),
+                    Curation::default(),
+                    None,
+                )
+            } else {
+                (
+                    load_apps(&load_ignored(), options.show_hidden, options.strict_exec),
+                    store.load(),
+                    store.load_trash(),
+                    load_curation(),
+                    snooze::load(now_unix()),
+                )
+            };
+
+        if sources {
+            let installed: HashSet<&str> = apps.iter().map(|app| app.key.as_str()).collect();
+            let grace_secs = config.usage_prune_days * DAY_SECS;
+            let now = now_unix();
+            if !prune_usage(&mut usage, &mut trash, &installed, now, grace_secs).is_empty() {
+                let pruned = store.update(&mut |usage, trash| {
+                    prune_usage(usage, trash, &installed, now, grace_secs);
+                });
+                warn_save_error(pruned, &warnings);
+            }
+        }
+        // After pruning, so usage of a collapsed duplicate is kept.
+        let mut apps = if !sources {
+            apps
+        } else {
+            merge_duplicates(apps, &usage, config.duplicate_apps)
+        };
+        if sources && config.activate_running {
+            mark_running(&mut apps);
+        }
+
+        let ranking = Ranking {
+            current_desktops: current_desktops(),
+            foreign_desktop_penalty: config.foreign_desktop_penalty,
+            contextual: config.contextual_ranking,
+            recency_window_secs: config.recency_window_days * DAY_SECS,
+            frequent_size: frequent.size.unwrap_or(config.frequent_size),
+            frequent_min_count: frequent.min_count.unwrap_or(config.frequent_min_count),
+            recent_section: config.recent_files_section,
+        };
+
+        let row_style = RowStyle::from_config(&config);
+        let mut state = Self {
+            apps: Rc::new(RefCell::new(apps)),
+            usage: Rc::new(RefCell::new(usage)),
+            trash: Rc::new(RefCell::new(trash)),
+            store: Rc::new(RefCell::new(store)),
+            curation: Rc::new(RefCell::new(curation)),
+            view: Rc::new(RefCell::new(ViewState {
+                items: Vec::new(),
+                selected_index: None,
+                provider: 0,
+                number_hints: config.number_hints,
+            })),
+            icons: Rc::new(IconCache {
+                size: row_style.icon_pixels(config.layout),
+                ..IconCache::default()
+            }),
+            bindings: Rc::new(keymap::default_bindings()),
+            notice: Rc::new(RefCell::new(None)),
+            conflicts_noted,
+            previous_query: Rc::new(RefCell::new(None)),
+            warnings: Rc::new(warnings),
+            options,
+            monitors: Rc::new(RefCell::new(Vec::new())),
+            ranking: Rc::new(ranking),
+            snooze_until: Rc::new(Cell::new(snooze_until)),
+            launching: Rc::new(Cell::new(false)),
+            awaiting_startup: Rc::new(Cell::new(false)),
+            event_time: Rc::new(Cell::new(0)),
+            systemd_scope: config.systemd_scope,
+            elevate_command: config.elevate_command.into(),
+            quit_after_copy: config.quit_after_copy,
+            activate_running: config.activate_running,
+            number_hints: config.number_hints,
+            wrap_selection: config.wrap_selection,
+            escape_clears: config.escape_clears,
+            close_on_focus_loss: config.close_on_focus_loss,
+            row_style,
+            grid: match config.layout {
+                config::Layout::List => None,
+                config::Layout::Grid => Some(Rc::new(grid::GridView::new(config.grid_columns))),
+                config::Layout::Bar => Some(Rc::new(grid::GridView::bar())),
+            },
+            startup_wait: options
+                .wait_for_startup
+                .then(|| Duration::from_secs(config.startup_wait_secs)),
+            app_env: Rc::new(config.app_env),
+            gpu_env: if demo {
+                None
+            } else {
+                gpu::offload_env(config.dedicated_gpu_env).map(Rc::new)
+            },
+            saves,
+            terminal: terminal::resolve(config.terminal.as_deref()).into(),
+            ssh_hosts: Rc::new(if config.ssh_hosts && sources {
+                ssh::load(config.ssh_known_hosts)
+            } else {
+                Vec::new()
+            }),
+            power_menu: config.power_menu,
+            power_commands: Rc::new(config.power_commands),
+            path_bins: Rc::new(RefCell::new(Vec::new())),
+            recent: Rc::new(RefCell::new(if config.recent_files && sources {
+                recent::load()
+            } else {
+                Vec::new()
+            })),
+            scan_path_bins: (options.path_bins || config.path_bins) && sources,
+            bookmarks: Rc::new(RefCell::new(Vec::new())),
+            scan_bookmarks: config.bookmarks && sources,
+            projects: Rc::new(if !sources {
+                Vec::new()
+            } else {
+                projects::load(&config.project_roots, &config.projects)
+            }),
+            project_command: config.project_command.into(),
+            settings_panels: Rc::new(RefCell::new(Vec::new())),
+            scan_settings_panels: config.settings_panels && sources,
+            duplicate_apps: config.duplicate_apps,
+            units: Rc::new(RefCell::new(None)),
+            pass_entries: (config.pass && sources).then(|| Rc::new(pass::list())),
+            plugins: Rc::new(if !sources {
+                Vec::new()
+            } else {
+                plugins::discover()
+            }),
+            plugin_output: Rc::new(RefCell::new(None)),
+            match_count: build_match_count(),
+            plugin_generation: Rc::new(Cell::new(0)),
+            render_generation: Rc::new(Cell::new(0)),
+            fit_generation: Rc::new(Cell::new(0)),
+            max_height: config.max_height.max(1),
+            position: options.position.unwrap_or(config.position),
+            y_offset: config.y_offset,
+            monitor: options.monitor.unwrap_or(config.monitor),
+            dmenu,
+            locate_command: Rc::new(config.locate_command),
+            file_search: Rc::new(RefCell::new(None)),
+            open_with,
+            dropped: Rc::new(RefCell::new(None)),
+            emoji_type_command: config.emoji_type_command.map(Rc::from),
+            web_search_url: config
+                .web_search
+                .then(|| Rc::from(config.web_search_url.as_str())),
+            bangs: Rc::new(bangs::load(config.bangs)),
+            clipboard,
+            clipboard_history: config.clipboard_history && sources,
+            snippets: Rc::new(if !sources {
+                Vec::new()
+            } else {
+                snippets::load(config.snippets)
+            }),
+            providers: Rc::new(Vec::new()),
+        };
+        state.providers = Rc::new(providers::registry(&state));
+        state
+    }
+
+    /// The time ranking and labels are computed at; fixed in demo mode.
+    fn now(&self) -> u64 {
+        if self.options.demo {
+            demo::now()
+        } else {
+            now_unix()
+        }
+    }
+
+    /// The end of the current snooze, or None when usage is shown.
+    fn snooze_deadline(&self) -> Option<u64> {
+        self.snooze_until
+            .get()
+            .filter(|deadline| *deadline > now_unix())
+    }
+}
+
+/// Starts or ends a snooze and arranges for the results to refresh when it
+/// runs out. The caller refreshes them now.
+fn set_snooze(entry: &Entry, listbox: &ListBox, state: &LauncherState, deadline: Option<u64>) {
+    state.snooze_until.set(deadline);
+    if !state.options.demo {
+        snooze::save(deadline);
+    }
+    schedule_snooze_end(entry, listbox, state);
+}
+
+fn schedule_snooze_end(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let Some(deadline) = state.snooze_deadline() else {
+        return;
+    };
+
+    let entry = entry.clone();
+    let listbox = listbox.clone();
+    let state_for_timeout = state.clone();
+    let remaining = Duration::from_secs(deadline.saturating_sub(now_unix()));
+    gtk::glib::timeout_add_local_once(remaining, move || {
+        // A later snooze may have replaced this one.
+        if state_for_timeout.snooze_deadline().is_none() {
+            update_results(&listbox, &state_for_timeout, &entry.text());
+        }
+    });
+}
+
+fn configure_settings() {
+    if let Some(settings) = gtk::Settings::default() {
+        settings.set_property("gtk-error-bell", false);
+    }
+}
+
+fn build_match_count() -> gtk::Label {
+    let label = gtk::Label::new(None);
+    label.set_xalign(1.0);
+    label.style_context().add_class("dim-label");
+    label
+}
+
+fn build_listbox() -> ListBox {
+    let listbox = ListBox::new();
+    listbox.set_selection_mode(gtk::SelectionMode::Single);
+    listbox
+}
+
+/// Takes the theme's padding off result rows, for `compact`.
+fn apply_compact_rows() {
+    let provider = gtk::CssProvider::new();
+    if let Err(err) = provider.load_from_data(b"list row { padding-top: 0; padding-bottom: 0; }") {
+        eprintln!("Failed to load the compact row style: {}", err);
+        return;
+    }
+    if let Some(screen) = gdk::Screen::default() {
+        gtk::StyleContext::add_provider_for_screen(
+            &screen,
+            &provider,
+            gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+        );
+    }
+}
+
+fn build_container(
+    title: &gtk::Box,
+    entry: &Entry,
+    error_bar: &ErrorBar,
+    listbox: &ListBox,
+    form: &CreateForm,
+    style: &RowStyle,
+    grid: Option<&grid::GridView>,
+) -> gtk::Box {
+    let (spacing, margin) = if style.compact { (2, 4) } else { (6, 8) };
+    let container = gtk::Box::new(gtk::Orientation::Vertical, spacing);
+    container.set_margin_top(margin);
+    container.set_margin_bottom(margin);
+    container.set_margin_start(margin + 2);
+    container.set_margin_end(margin + 2);
+    if style.compact {
+        apply_compact_rows();
+    }
+    if let Some(grid) = grid.filter(|grid| grid.is_bar()) {
+        return build_bar_container(container, title, entry, error_bar, listbox, form, grid);
+    }
+    container.pack_start(title, false, false, 0);
+    container.pack_start(entry, false, false, 0);
+    container.pack_start(&error_bar.bar, false, false, 0);
+    let scroller = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vscrollbar_policy(gtk::PolicyType::Automatic)
+        .propagate_natural_height(true)
+        .max_content_height(match grid {
+            Some(_) => GRID_LINES * style.cell_height(),
+            None => MAX_RESULTS as i32 * style.row_height(),
+        })
+        .build();
+    match grid {
+        // The list stays, hidden, to hold the selection.
+        Some(grid) => {
+            let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+            content.pack_start(&grid.grid, false, false, 0);
+            listbox.set_no_show_all(true);
+            content.pack_start(listbox, false, false, 0);
+            scroller.add(&content);
+        }
+        None => scroller.add(listbox),
+    }
+    listbox.set_adjustment(Some(&scroller.vadjustment()));
+    container.pack_start(&scroller, true, true, 0);
+    container.pack_start(&form.container, false, false, 0);
+    container
+}
+
+/// The bar's one line: prompt and search on the left, the results
+/// scrolling sideways to their right, with errors and the create form
+/// under it when they show.
+fn build_bar_container(
+    container: gtk::Box,
+    title: &gtk::Box,
+    entry: &Entry,
+    error_bar: &ErrorBar,
+    listbox: &ListBox,
+    form: &CreateForm,
+    grid: &grid::GridView,
+) -> gtk::Box {
+    let line = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    line.pack_start(title, false, false, 0);
+    entry.set_width_chars(BAR_ENTRY_CHARS);
+    line.pack_start(entry, false, false, 0);
+    let scroller = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::External)
+        .vscrollbar_policy(gtk::PolicyType::Never)
+        .build();
+    let content = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+    content.pack_start(&grid.grid, false, false, 0);
+    listbox.set_no_show_all(true);
+    content.pack_start(listbox, false, false, 0);
+    scroller.add(&content);
+    listbox.set_adjustment(Some(&scroller.hadjustment()));
+    line.pack_start(&scroller, true, true, 0);
+    container.pack_start(&line, false, false, 0);
+    container.pack_start(&error_bar.bar, false, false, 0);
+    container.pack_start(&form.container, false, false, 0);
+    container
+}
+
+/// Opens the window where the config and command line say. Override
+/// redirect leaves placing it to us, so this goes by the monitor
+/// geometry rather than the window manager.
+fn place_window(window: &ApplicationWindow, state: &LauncherState) {
+    if state.grid.as_ref().is_some_and(|grid| grid.is_bar()) {
+        place_bar(window, state.monitor);
+        return;
+    }
+    let (width, _) = window.default_size();
+    let (_, height) = window.preferred_height();
+    placement::place(
+        window.upcast_ref(),
+        (width, height),
+        state.position,
+        state.y_offset,
+        state.monitor,
+    );
+}
+
+/// Stretches the bar across the top of the chosen monitor.
+fn place_bar(window: &ApplicationWindow, choice: placement::Monitor) {
+    let Some(display) = gdk::Display::default() else {
+        return;
+    };
+    let Some(monitor) = placement::monitor(&display, choice) else {
+        return;
+    };
+    let geometry = monitor.geometry();
+    window.set_position(gtk::WindowPosition::None);
+    window.set_default_size(geometry.width(), -1);
+    window.set_size_request(geometry.width(), -1);
+    window.move_(geometry.x(), geometry.y());
+}
+
+fn apply_window_hints(window: &ApplicationWindow) {
+    window.set_type_hint(gdk::WindowTypeHint::PopupMenu);
+    window.set_skip_taskbar_hint(true);
+    window.set_skip_pager_hint(true);
+    window.set_accept_focus(true);
+    window.set_focus_on_map(true);
+}
+
+fn connect_override_redirect(window: &ApplicationWindow) {
+    window.connect_realize(|window| {
+        if let Some(gdk_window) = window.window() {
+            gdk_window.set_override_redirect(true);
+        }
+    });
+}
+
+/// Grabs the keyboard once the window is up, and with `pointer` the mouse
+/// too, so clicks outside the window come to it instead of the window
+/// under them.
+fn connect_keyboard_grab(window: &ApplicationWindow, pointer: bool) {
+    let capabilities = if pointer {
+        gdk::SeatCapabilities::ALL
+    } else {
+        gdk::SeatCapabilities::KEYBOARD
+    };
+    window.connect_map_event(move |window, _| {
+        if let Some(gdk_window) = window.window() {
+            gdk_window.focus(gdk::ffi::GDK_CURRENT_TIME as u32);
+            if let Some(display) = gdk::Display::default()
+                && let Some(seat) = display.default_seat()
+            {
+                let _ = seat.grab(&gdk_window, capabilities, true, None, None, None);
+            }
+        }
+        gtk::glib::Propagation::Proceed
+    });
+}
+
+/// Lets go of the keyboard and pointer, e.g. while the window is hidden.
+fn ungrab_seat() {
+    if let Some(display) = gdk::Display::default()
+        && let Some(seat) = display.default_seat()
+    {
+        seat.ungrab();
+        display.flush();
+    }
+}
+
+fn connect_keyboard_ungrab(window: &ApplicationWindow) {
+    window.connect_unmap_event(|_, _| {
+        ungrab_seat();
+        gtk::glib::Propagation::Proceed
+    });
+}
+
+/// Releases the grabs before a panic takes the process down, so the
+/// desktop isn't left unusable while it does.
+fn ungrab_on_panic() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if gtk::is_initialized_main_thread() {
+            ungrab_seat();
+        }
+        previous(info);
+    }));
+}
+
+/// With the pointer grabbed, a click anywhere else on the screen is
+/// reported to the launcher, which closes for it.
+fn connect_click_outside(window: &ApplicationWindow, state: &LauncherState, app: &Application) {
+    if !state.close_on_focus_loss {
+        return;
+    }
+    let state = state.clone();
+    let app = app.clone();
+    window.connect_button_press_event(move |window, event| {
+        let Some(gdk_window) = window.window() else {
+            return gtk::glib::Propagation::Proceed;
+        };
+        let (_, left, top) = gdk_window.origin();
+        let (x, y) = event.root();
+        let inside = x >= f64::from(left)
+            && y >= f64::from(top)
+            && x < f64::from(left + gdk_window.width())
+            && y < f64::from(top + gdk_window.height());
+        if inside || state.launching.get() {
+            return gtk::glib::Propagation::Proceed;
+        }
+        dismiss(&app);
+        gtk::glib::Propagation::Stop
+    });
+}
+
+fn connect_visibility_events(window: &ApplicationWindow) {
+    window.connect_map_event(|_, _| {
+        events::emit(&events::Event::Shown, now_unix());
+        gtk::glib::Propagation::Proceed
+    });
+    window.connect_unmap_event(|_, _| {
+        events::emit(&events::Event::Hidden, now_unix());
+        gtk::glib::Propagation::Proceed
+    });
+    // Quitting tears the process down without unmapping the window first.
+    if let Some(app) = window.application() {
+        let window_for_shutdown = window.clone();
+        connect_shutdown_while_open(&app, window, move || {
+            if window_for_shutdown.is_mapped() {
+                events::emit(&events::Event::Hidden, now_unix());
+            }
+        });
+    }
+}
+
+/// Closes the launcher once focus goes to another window, or something
+/// else takes the keyboard grab. Only armed by the first focus-in after
+/// each map, so focus still settling while the window appears doesn't
+/// close it.
+fn connect_close_on_focus_loss(
+    window: &ApplicationWindow,
+    state: &LauncherState,
+    app: &Application,
+) {
+    if !state.close_on_focus_loss {
+        return;
+    }
+    let armed = Rc::new(Cell::new(false));
+    let armed_for_focus_in = Rc::clone(&armed);
+    window.connect_focus_in_event(move |_, _| {
+        armed_for_focus_in.set(true);
+        gtk::glib::Propagation::Proceed
+    });
+    let armed_for_unmap = Rc::clone(&armed);
+    window.connect_unmap_event(move |_, _| {
+        armed_for_unmap.set(false);
+        gtk::glib::Propagation::Proceed
+    });
+    // Hidden to wait for an app, or already launching one, is not a reason
+    // to close.
+    let close = {
+        let state = state.clone();
+        let app = app.clone();
+        move |window: &ApplicationWindow| {
+            if armed.get() && window.is_visible() && !state.launching.get() {
+                armed.set(false);
+                dismiss(&app);
+            }
+        }
+    };
+    let close_for_grab = close.clone();
+    window.connect_focus_out_event(move |window, _| {
+        close(window);
+        gtk::glib::Propagation::Proceed
+    });
+    window.connect_grab_broken_event(move |window, _| {
+        close_for_grab(window);
+        gtk::glib::Propagation::Proceed
+    });
+}
+
+fn configure_window(window: &ApplicationWindow, grab_pointer: bool) {
+    apply_window_hints(window);
+    connect_override_redirect(window);
+    connect_keyboard_grab(window, grab_pointer);
+    connect_keyboard_ungrab(window);
+    connect_visibility_events(window);
+}
+
+fn build_window(app: &Application, container: &gtk::Box, grab_pointer: bool) -> ApplicationWindow {
+    let window = ApplicationWindow::builder()
+        .application(app)
+        .decorated(false)
+        .default_width(600)
+        // Not by the user; it starts at its natural height and
+        // `fit_window_later` keeps it fitting the results.
+        .resizable(true)
+        .build();
+
+    window.set_position(gtk::WindowPosition::Center);
+    window.add(container);
+    configure_window(&window, grab_pointer);
+
+    window
+}
+
+fn refresh_results(listbox: &ListBox, state: &LauncherState) {
+    update_results(listbox, state, "");
+}
+
+fn connect_icon_stall_report(app: &Application, window: &ApplicationWindow, state: &LauncherState) {
+    let icons = Rc::clone(&state.icons);
+    connect_shutdown_while_open(app, window, move || {
+        let stalls = icons.stalls.get();
+        if stalls > 0 {
+            eprintln!(
+                "{} icon lookups took longer than {}ms; run `hyperfind --doctor` to find the slow icons",
+                stalls,
+                ICON_DEADLINE.as_millis()
+            );
+        }
+    });
+}
+
+fn connect_app_reload(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    if state.options.demo || state.dmenu.is_some() || state.open_with.is_some() {
+        return;
+    }
+
+    let entry = entry.clone();
+    let listbox = listbox.clone();
+    let state_for_reload = state.clone();
+    let monitors = watch::watch(&watch::watched_dirs(), move || {
+        let state = &state_for_reload;
+        let apps = load_apps(
+            &load_ignored(),
+            state.options.show_hidden,
+            state.options.strict_exec,
+        );
+        let mut apps = merge_duplicates(apps, &state.usage.borrow(), state.duplicate_apps);
+        if state.activate_running {
+            mark_running(&mut apps);
+        }
+        add_settings_panels(&mut apps, &state.settings_panels.borrow());
+        state.apps.replace(apps);
+        update_results(&listbox, state, &entry.text());
+    });
+    state.monitors.replace(monitors);
+}
+
+/// Scans `$PATH` on a worker thread and refreshes the results once the
+/// executables are in, so startup doesn't wait on /usr/bin.
+fn load_path_bins_later(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    if !state.scan_path_bins || state.options.demo {
+        return;
+    }
+
+    let entry = entry.clone();
+    let listbox = listbox.clone();
+    let state = state.clone();
+    gtk::glib::MainContext::default().spawn_local(async move {
+        let Ok(bins) = gio::spawn_blocking(path_bins::scan).await else {
+            return;
+        };
+        state.path_bins.replace(bins);
+        update_results(&listbox, &state, &entry.text());
+    });
+}
+
+/// Reads browser bookmarks off the main thread, after the window is up,
+/// since the Firefox database has to be copied first.
+fn load_bookmarks_later(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    if !state.scan_bookmarks || state.options.demo {
+        return;
+    }
+
+    let entry = entry.clone();
+    let listbox = listbox.clone();
+    let state = state.clone();
+    gtk::glib::MainContext::default().spawn_local(async move {
+        let Ok(bookmarks) = gio::spawn_blocking(bookmarks::load).await else {
+            return;
+        };
+        state.bookmarks.replace(bookmarks);
+        update_results(&listbox, &state, &entry.text());
+    });
+}
+
+/// Lists the desktop's settings panels off the main thread, since that
+/// runs the settings app, and adds them to the apps.
+fn load_settings_panels_later(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    if !state.scan_settings_panels {
+        return;
+    }
+
+    let entry = entry.clone();
+    let listbox = listbox.clone();
+    let state = state.clone();
+    let desktops = state.ranking.current_desktops.clone();
+    gtk::glib::MainContext::default().spawn_local(async move {
+        let Ok(panels) = gio::spawn_blocking(move || settings::list(&desktops)).await else {
+            return;
+        };
+        let ignored = load_ignored();
+        let panels: Vec<AppEntry> = panels
+            .into_iter()
+            .filter_map(|panel| build_panel_entry(panel, &ignored))
+            .collect();
+        add_settings_panels(&mut state.apps.borrow_mut(), &panels);
+        state.settings_panels.replace(panels);
+        update_results(&listbox, &state, &entry.text());
+    });
+}
+
+fn focus_entry_later(entry: &Entry) {
+    let entry_clone = entry.clone();
+    gtk::glib::idle_add_local_once(move || {
+        entry_clone.grab_focus();
+    });
+}
+
+/// Marks the grid cell of whichever row the hidden list selects.
+fn connect_grid_selection(listbox: &ListBox, state: &LauncherState) {
+    let Some(grid) = state.grid.clone() else {
+        return;
+    };
+    listbox.connect_row_selected(move |_, row| {
+        grid.check(row.and_then(|row| usize::try_from(row.index()).ok()));
+    });
+}
+
+fn connect_listbox_activation(
+    listbox: &ListBox,
+    state: &LauncherState,
+    form: &CreateForm,
+    error_bar: &ErrorBar,
+    app: &Application,
+) {
+    let state_for_activate = state.clone();
+    let form_for_activate = form.clone();
+    let error_bar_for_activate = error_bar.clone();
+    let app_for_activate = app.clone();
+    listbox.connect_row_activated(move |listbox, row| {
+        state_for_activate.event_time.set(gtk::current_event_time());
+        if activate_index(
+            row.index(),
+            &state_for_activate,
+            &form_for_activate,
+            listbox,
+            &error_bar_for_activate,
+        ) {
+            dismiss(&app_for_activate);
+        }
+    });
+}
+
+fn connect_entry_key_handler(
+    entry: &Entry,
+    listbox: &ListBox,
+    state: &LauncherState,
+    form: &CreateForm,
+    error_bar: &ErrorBar,
+    app: &Application,
+) {
+    let entry_for_keys = entry.clone();
+    let listbox_for_keys = listbox.clone();
+    let state_for_keys = state.clone();
+    let form_for_keys = form.clone();
+    let error_bar_for_keys = error_bar.clone();
+    let app_for_keys = app.clone();
+    entry.connect_key_press_event(move |_, event| {
+        error_bar_for_keys.bar.hide();
+        if is_alt_key(&event.keyval()) {
+            set_number_hints(&listbox_for_keys, &state_for_keys, true);
+        }
+        let Some(action) = keymap::lookup(&state_for_keys.bindings, event.keyval(), event.state())
+        else {
+            return gtk::glib::Propagation::Proceed;
+        };
+        state_for_keys.event_time.set(event.time());
+
+        match action {
+            keymap::Action::Quit => {
+                if !collapse_actions(&listbox_for_keys, &state_for_keys)
+                    && !clear_dropped(&entry_for_keys, &listbox_for_keys, &state_for_keys)
+                    && !clear_query_first(&entry_for_keys, &state_for_keys)
+                {
+                    dismiss(&app_for_keys);
+                }
+            }
+            keymap::Action::ExpandActions => {
+                if !descend_into_selected(&entry_for_keys, &listbox_for_keys, &state_for_keys)
+                    && !move_selection_sideways(&listbox_for_keys, &state_for_keys, 1)
+                    && !expand_selected_actions(&listbox_for_keys, &state_for_keys)
+                {
+                    return gtk::glib::Propagation::Proceed;
+                }
+            }
+            // Like ExpandActions, but the app's name comes first, and focus
+            // never moves on from the entry.
+            keymap::Action::Complete => {
+                if !descend_into_selected(&entry_for_keys, &listbox_for_keys, &state_for_keys)
+                    && !complete_selected(&entry_for_keys, &listbox_for_keys, &state_for_keys)
+                {
+                    expand_selected_actions(&listbox_for_keys, &state_for_keys);
+                }
+            }
+            keymap::Action::RestoreQuery => {
+                restore_previous_query(&entry_for_keys, &state_for_keys);
+            }
+            keymap::Action::CollapseActions => {
+                if !collapse_actions(&listbox_for_keys, &state_for_keys)
+                    && !move_selection_sideways(&listbox_for_keys, &state_for_keys, -1)
+                {
+                    return gtk::glib::Propagation::Proceed;
+                }
+            }
+            keymap::Action::SelectNext => {
+                move_selection(&listbox_for_keys, &state_for_keys, 1);
+            }
+            keymap::Action::SelectPrevious => {
+                move_selection(&listbox_for_keys, &state_for_keys, -1);
+            }
+            keymap::Action::DeleteWord => {
+                delete_word_before_cursor(&entry_for_keys);
+            }
+            keymap::Action::ClearQuery => {
+                entry_for_keys.set_text("");
+            }
+            keymap::Action::SelectFirst => {
+                select_first(&listbox_for_keys, &state_for_keys);
+            }
+            keymap::Action::SelectLast => {
+                select_last(&listbox_for_keys, &state_for_keys);
+            }
+            keymap::Action::IgnoreSelected => {
+                ignore_selected_app(&entry_for_keys, &listbox_for_keys, &state_for_keys);
+            }
+            keymap::Action::ResetUsage => {
+                reset_selected_usage(&entry_for_keys, &listbox_for_keys, &state_for_keys);
+            }
+            keymap::Action::RestoreUsage => {
+                restore_selected_usage(&entry_for_keys, &listbox_for_keys, &state_for_keys);
+            }
+            keymap::Action::TogglePin => {
+                toggle_selected_pin(&entry_for_keys, &listbox_for_keys, &state_for_keys);
+            }
+            keymap::Action::ToggleFrequent => {
+                toggle_selected_frequent_exclusion(
+                    &entry_for_keys,
+                    &listbox_for_keys,
+                    &state_for_keys,
+                );
+            }
+            keymap::Action::ToggleAutostart => {
+                toggle_selected_autostart(
+                    &entry_for_keys,
+                    &listbox_for_keys,
+                    &state_for_keys,
+                    &error_bar_for_keys,
+                );
+            }
+            keymap::Action::EditDesktopFile => {
+                if edit_selected_desktop_file(
+                    &listbox_for_keys,
+                    &state_for_keys,
+                    &error_bar_for_keys,
+                ) {
+                    dismiss(&app_for_keys);
+                }
+            }
+            keymap::Action::ToggleSnooze => {
+                let deadline = match state_for_keys.snooze_deadline() {
+                    Some(_) => None,
+                    None => Some(now_unix() + snooze::DEFAULT_MINUTES * 60),
+                };
+                set_snooze(
+                    &entry_for_keys,
+                    &listbox_for_keys,
+                    &state_for_keys,
+                    deadline,
+                );
+                update_results(&listbox_for_keys, &state_for_keys, &entry_for_keys.text());
+            }
+            keymap::Action::LaunchNew => {
+                let Some(app) = selected_app(&listbox_for_keys, &state_for_keys) else {
+                    return gtk::glib::Propagation::Proceed;
+                };
+                match launch_app(&app, &[], &state_for_keys) {
+                    Ok(()) if !state_for_keys.awaiting_startup.get() => dismiss(&app_for_keys),
+                    Ok(()) | Err(LaunchError::Cancelled) => {}
+                    Err(err) => show_error(&error_bar_for_keys, &err.to_string()),
+                }
+            }
+            keymap::Action::LaunchNth(n) => {
+                let Some(index) = nth_app_row(&listbox_for_keys, &state_for_keys, n) else {
+                    return gtk::glib::Propagation::Proceed;
+                };
+                if activate_index(
+                    index,
+                    &state_for_keys,
+                    &form_for_keys,
+                    &listbox_for_keys,
+                    &error_bar_for_keys,
+                ) {
+                    dismiss(&app_for_keys);
+                }
+            }
+            keymap::Action::RunElevated => {
+                if let Some(app) = selected_app(&listbox_for_keys, &state_for_keys) {
+                    launch_elevated(app, &state_for_keys, &listbox_for_keys, &error_bar_for_keys);
+                }
+            }
+            keymap::Action::CopyCommand | keymap::Action::CopyDesktopFile => {
+                let detail = if action == keymap::Action::CopyCommand {
+                    AppDetail::Command
+                } else {
+                    AppDetail::DesktopFile
+                };
+                // Selected text in the search copies as usual.
+                if entry_for_keys.selection_bounds().is_some()
+                    || !copy_app_detail(&listbox_for_keys, &state_for_keys, detail)
+                {
+                    return gtk::glib::Propagation::Proceed;
+                }
+            }
+            keymap::Action::RunInTerminal => {
+                let Some(index) = enter_index(&listbox_for_keys, &state_for_keys) else {
+                    return gtk::glib::Propagation::Proceed;
+                };
+                let Some(quit) = activate_index_secondary(
+                    index,
+                    &state_for_keys,
+                    &listbox_for_keys,
+                    &error_bar_for_keys,
+                ) else {
+                    return gtk::glib::Propagation::Proceed;
+                };
+                if quit {
+                    dismiss(&app_for_keys);
+                }
+            }
+            keymap::Action::Launch => {
+                if let Some(minutes) = snooze::parse_command(&entry_for_keys.text()) {
+                    let deadline = (minutes > 0).then(|| now_unix() + minutes * 60);
+                    set_snooze(
+                        &entry_for_keys,
+                        &listbox_for_keys,
+                        &state_for_keys,
+                        deadline,
+                    );
+                    entry_for_keys.set_text("");
+                    return gtk::glib::Propagation::Stop;
+                }
+                if let Some(index) = enter_index(&listbox_for_keys, &state_for_keys)
+                    && activate_index(
+                        index,
+                        &state_for_keys,
+                        &form_for_keys,
+                        &listbox_for_keys,
+                        &error_bar_for_keys,
+                    )
+                {
+                    dismiss(&app_for_keys);
+                }
+                if !form_for_keys.container.is_visible() {
+                    entry_for_keys.grab_focus();
+                }
+            }
+        }
+        show_warnings(&state_for_keys, &error_bar_for_keys);
+        gtk::glib::Propagation::Stop
+    });
+}
+
+fn connect_entry_key_release_handler(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let listbox = listbox.clone();
+    let state = state.clone();
+    entry.connect_key_release_event(move |_, event| {
+        if is_alt_key(&event.keyval()) {
+            set_number_hints(&listbox, &state, false);
+        }
+        gtk::glib::Propagation::Proceed
+    });
+}
+
+fn connect_entry_change_handler(entry: &Entry, listbox: &ListBox, state: &LauncherState) {
+    let listbox_for_change = listbox.clone();
+    let state_for_change = state.clone();
+    entry.connect_changed(move |entry| {
+        let query = entry.text().to_string();
+        if state_for_change.options.emit_queries {
+            events::emit(&events::Event::QueryChanged { query: &query }, now_unix());
+        }
+        update_results(&listbox_for_change, &state_for_change, &query);
+    });
+}
+
+fn connect_entry_handlers(
+    entry: &Entry,
+    listbox: &ListBox,
+    state: &LauncherState,
+    form: &CreateForm,
+    error_bar: &ErrorBar,
+    app: &Application,
+) {
+    connect_entry_key_handler(entry, listbox, state, form, error_bar, app);
+    connect_entry_key_release_handler(entry, listbox, state);
+    connect_entry_change_handler(entry, listbox, state);
+}
+
+fn build_ui(
+    app: &Application,
+    options: Options,
+    saves: PendingSaves,
+    dmenu: Option<Rc<dmenu::Dmenu>>,
+    open_with: Option<Rc<open_with::OpenWith>>,
+    clipboard: ClipboardHistory,
+    conflicts_noted: Rc<Cell<bool>>,
+) {
+    configure_settings();
+
+    let title = match &open_with {
+        Some(open_with) => format!("Open {} with", open_with.name),
+        None if options.private => "HyperFind (private)".to_string(),
+        None => "HyperFind".to_string(),
+    };
+    let title = gtk::Label::new(Some(&title));
+    title.set_xalign(0.0);
+    let title_line = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    title_line.pack_start(&title, true, true, 0);
+
+    let placeholder = dmenu
+        .as_ref()
+        .and_then(|dmenu| dmenu.prompt.clone())
+        .unwrap_or_else(|| "Search…".to_string());
+    let entry = Entry::builder().placeholder_text(placeholder).build();
+
+    let state = LauncherState::new(options, saves, dmenu, open_with, clipboard, conflicts_noted);
+    let prefixes = mode_prefixes(&state.plugins);
+    let prefixes: Vec<&str> = prefixes.iter().map(String::as_str).collect();
+    if !state.conflicts_noted.get()
+        && !keymap::find_conflicts(&state.bindings, &prefixes).is_empty()
+    {
+        state.notice.replace(Some(
+            "Key binding conflicts found, see hyperfind --dump-keymap".to_string(),
+        ));
+        state.conflicts_noted.set(true);
+    }
+    let listbox = build_listbox();
+    let form = build_create_form();
+    let error_bar = build_error_bar();
+
+    connect_listbox_activation(&listbox, &state, &form, &error_bar, app);
+    connect_grid_selection(&listbox, &state);
+    connect_app_reload(&entry, &listbox, &state);
+    load_path_bins_later(&entry, &listbox, &state);
+    load_bookmarks_later(&entry, &listbox, &state);
+    load_settings_panels_later(&entry, &listbox, &state);
+    connect_entry_handlers(&entry, &listbox, &state, &form, &error_bar, app);
+    connect_create_form_handlers(&form, &entry, &listbox, &state);
+
+    title_line.pack_end(&state.match_count, false, false, 0);
+    let container = build_container(
+        &title_line,
+        &entry,
+        &error_bar,
+        &listbox,
+        &form,
+        &state.row_style,
+        state.grid.as_deref(),
+    );
+    // Only a launcher that closes on clicks elsewhere takes the pointer.
+    let window = build_window(app, &container, state.close_on_focus_loss);
+    connect_icon_stall_report(app, &window, &state);
+    connect_file_drop(&window, &entry, &listbox, &state);
+    place_window(&window, &state);
+
+    refresh_results(&listbox, &state);
+    schedule_snooze_end(&entry, &listbox, &state);
+
+    connect_warnings_on_map(&window, &state, &error_bar);
+    connect_close_on_focus_loss(&window, &state, app);
+    connect_click_outside(&window, &state, app);
+    show_when_icons_loaded(&window, &entry, &state);
+}
+
+/// Shows the window once the first rows' icons have loaded, so they don't
+/// pop in a moment later, or at `ICON_DEADLINE` with the fallback in
+/// place of those still loading. A theme that slow is pointed out.
+fn show_when_icons_loaded(window: &ApplicationWindow, entry: &Entry, state: &LauncherState) {
+    let shown = Rc::new(Cell::new(false));
+    let show = {
+        let window = window.clone();
+        let entry = entry.clone();
+        let icons = Rc::clone(&state.icons);
+        let warnings = Rc::clone(&state.warnings);
+        Rc::new(move || {
+            if shown.replace(true) {
+                return;
+            }
+            icons.on_settled.take();
+            // Past the deadline, every lookup still running is one that
+            // missed it.
+            let stalls = icons.stalls.get().max(icons.pending.get());
+            if stalls > 0 {
+                warnings.borrow_mut().push(format!(
+                    "{} icons took over {}ms to load, so the icon theme may be broken; \
+                     hyperfind --doctor lists the slow ones",
+                    stalls,
+                    ICON_DEADLINE.as_millis()
+                ));
+            }
+            window.show_all();
+            focus_entry_later(&entry);
+        })
+    };
+    if state.icons.pending.get() == 0 {
+        show();
+        return;
+    }
+    let show_once_loaded = Rc::clone(&show);
+    state
+        .icons
+        .on_settled
+        .replace(Some(Box::new(move || show_once_loaded())));
+    gtk::glib::timeout_add_local_once(ICON_DEADLINE, move || show());
+}
+
+fn run_doctor() -> i32 {
+    // Run before any application exists, so GTK isn't set up yet.
+    if let Err(err) = gtk::init() {
+        eprintln!("Failed to initialize GTK: {}", err);
+        return 1;
+    }
+    let apps = load_apps(&load_ignored(), false, false);
+    println!("apps: {}", apps.len());
+    println!("usage file: {}", open_usage_store().location().display());
+
+    let Some(theme) = gtk::IconTheme::default() else {
+        println!("icon theme: unavailable");
+        return 1;
+    };
+
+    // Rows fall back to this icon, so it has to come through the same
+    // lookup and asynchronous load they use.
+    let fallback = gio::ThemedIcon::new(FALLBACK_ICON).upcast::<gio::Icon>();
+    let fallback_loaded = lookup_icon(&theme, &fallback, ICON_SIZE, 1).is_some_and(|info| {
+        gtk::glib::MainContext::default()
+            .block_on(info.load_icon_future())
+            .is_ok()
+    });
+    if !fallback_loaded {
+        println!("fallback icon {}: missing", FALLBACK_ICON);
+        return 1;
+    }
+    println!("fallback icon {}: ok", FALLBACK_ICON);
+
+    let mut looked_up = 0;
+    let mut missing = 0;
+    let mut slow: Vec<(Duration, &str)> = Vec::new();
+    for app in &apps {
+        let Some(icon) = &app.icon else {
+            continue;
+        };
+        looked_up += 1;
+        let started = Instant::now();
+        let loaded =
+            lookup_icon(&theme, icon, ICON_SIZE, 1).is_some_and(|info| info.load_icon().is_ok());
+        let elapsed = started.elapsed();
+        if !loaded {
+            missing += 1;
+        }
+        if elapsed > ICON_DEADLINE {
+            slow.push((elapsed, &app.name));
+        }
+    }
+
+    println!("icons missing: {} of {}", missing, looked_up);
+    println!(
+        "icon lookups over {}ms: {} of {}",
+        ICON_DEADLINE.as_millis(),
+        slow.len(),
+        looked_up
+    );
+    slow.sort_by_key(|(elapsed, _)| std::cmp::Reverse(*elapsed));
+    for (elapsed, name) in slow.iter().take(10) {
+        println!("  {:>6}ms  {}", elapsed.as_millis(), name);
+    }
+    if !slow.is_empty() {
+        println!("the icon theme looks slow; try rebuilding its cache with gtk-update-icon-cache");
+    }
+
+    0
+}
+
+/// Returns the argument following `flag`, as in `--flag value`.
+fn flag_value(args: &[std::ffi::OsString], flag: &str) -> Option<String> {
+    let position = args.iter().position(|arg| arg == flag)?;
+    args.get(position + 1)
+        .map(|value| value.to_string_lossy().into_owned())
+}
+
+fn open_usage_store() -> Box<dyn UsageStore> {
+    usage_store::open(config::load().usage_backend)
+}
+
+fn run_restore_usage(key: &str) -> i32 {
+    let mut store = open_usage_store();
+    let mut restored = false;
+    report_save_error(store.update(&mut |usage, trash| {
+        restored = restore_usage(key, usage, trash);
+    }));
+    if !restored {
+        eprintln!("No deleted usage found for {}", key);
+        return 1;
+    }
+
+    println!("Restored usage for {}", key);
+    0
+}
+
+/// Drops usage for every app that isn't installed, however recently used,
+/// and lists what was removed.
+fn run_prune_usage() -> i32 {
+    let apps = load_apps(&[], true, false);
+    let installed: HashSet<&str> = apps.iter().map(|app| app.key.as_str()).collect();
+    let mut store = open_usage_store();
+    let now = now_unix();
+    let mut removed = Vec::new();
+    let pruned = store.update(&mut |usage, trash| {
+        removed = prune_usage(usage, trash, &installed, now, 0);
+    });
+    if let Err(err) = pruned {
+        eprintln!("Failed to save usage data: {}", err);
+        return 1;
+    }
+    if removed.is_empty() {
+        println!("No usage to prune");
+        return 0;
+    }
+
+    for key in &removed {
+        println!("Removed usage for {}", key);
+    }
+    println!("Restore any of them with hyperfind --restore-usage <key>");
+    0
+}
+
+/// Prints a table of launch counts, busiest over the last 30 days first.
+fn run_stats() -> i32 {
+    let apps = load_apps(&[], true, false);
+    let names: HashMap<&str, &str> = apps
+        .iter()
+        .map(|app| (app.key.as_str(), app.name.as_str()))
+        .collect();
+    let usage = open_usage_store().load();
+    let now = now_unix();
+
+    let mut rows: Vec<(&str, &UsageEntry, u64, u64)> = usage
+        .iter()
+        .map(|(key, entry)| {
+            let name = names.get(key.as_str()).copied().unwrap_or(key);
+            (
+                name,
+                entry,
+                launches_within(entry, now, 7),
+                launches_within(entry, now, 30),
+            )
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.3.cmp(&a.3)
+            .then_with(|| b.1.count.cmp(&a.1.count))
+            .then_with(|| a.0.cmp(b.0))
+    });
+
+    println!(
+        "{:<32} {:>7} {:>7} {:>7}  last used",
+        "app", "total", "7 days", "30 days"
+    );
+    for (name, entry, week, month) in rows {
+        println!(
+            "{:<32} {:>7} {:>7} {:>7}  {}",
+            name,
+            entry.count,
+            week,
+            month,
+            local_date(entry.last_used)
+        );
+    }
+    0
+}
+
+/// Clears all usage after copying the current file to a timestamped backup.
+fn run_reset_usage() -> i32 {
+    let mut store = open_usage_store();
+    let path = store.location();
+    if path.exists() {
+        let backup = with_suffix(&path, &format!(".{}.bak", now_unix()));
+        if let Err(err) = fs::copy(&path, &backup) {
+            eprintln!("Failed to back up {}: {}", path.display(), err);
+            return 1;
+        }
+        println!("Backed up usage to {}", backup.display());
+    }
+
+    if let Err(err) = store.replace(&HashMap::new()) {
+        eprintln!("Failed to save usage data: {}", err);
+        return 1;
+    }
+    println!("Usage reset");
+    0
+}
+
+fn run_export_usage(path: &Path) -> i32 {
+    let usage = open_usage_store().load();
+    let payload = match usage_file_contents(&usage, &UsageTrash::new(), true) {
+        Ok(payload) => payload,
+        Err(err) => {
+            eprintln!("Failed to serialize usage data: {}", err);
+            return 1;
+        }
+    };
+
+    if let Err(err) = fs::write(path, payload) {
+        eprintln!("Failed to write {}: {}", path.display(), err);
+        return 1;
+    }
+    println!(
+        "Exported usage for {} apps to {}",
+        usage.len(),
+        path.display()
+    );
+    0
+}
+
+/// Adds an exported map's launches to this one. The two maps are separate
+/// histories, so counts are summed.
+fn import_usage(usage: &mut UsageMap, imported: UsageMap) {
+    for (key, entry) in imported {
+        usage
+            .entry(key)
+            .and_modify(|existing| {
+                existing.count += entry.count;
+                existing.last_used = existing.last_used.max(entry.last_used);
+                add_daily(&mut existing.daily, &entry.daily);
+                add_time_bins(&mut existing.time_bins, &entry.time_bins);
+            })
+            .or_insert(entry);
+    }
+}
+
+fn run_import_usage(path: &Path) -> i32 {
+    let imported = match read_usage_file(path) {
+        Ok(imported) => imported.entries,
+        Err(err) => {
+            eprintln!("Failed to read {}: {}", path.display(), err);
+            return 1;
+        }
+    };
+
+    let count = imported.len();
+    let mut store = open_usage_store();
+    let mut usage = store.load();
+    import_usage(&mut usage, imported);
+    if let Err(err) = store.replace(&usage) {
+        eprintln!("Failed to save usage data: {}", err);
+        return 1;
+    }
+    println!("Imported usage for {} apps from {}", count, path.display());
+    0
+}
+
+/// Reports keymap conflicts on stderr. Returns false when they should stop
+/// the launcher from starting.
+fn check_bindings(strict: bool) -> bool {
+    let prefixes = mode_prefixes(&plugins::discover());
+    let prefixes: Vec<&str> = prefixes.iter().map(String::as_str).collect();
+    let conflicts = keymap::find_conflicts(&keymap::default_bindings(), &prefixes);
+    for conflict in &conflicts {
+        eprintln!("Key binding conflict: {}", conflict);
+    }
+    !strict || conflicts.is_empty()
+}
+
+/// Holds the application so it outlives its windows, and starts
+/// watching the clipboard when the history is turned on.
+fn start_daemon(app: &Application, clipboard: &ClipboardHistory) -> i32 {
+    if !daemon::start() {
+        return 0;
+    }
+    // Never released: the daemon runs until it's killed or `app.quit()`.
+    std::mem::forget(app.hold());
+    let config = config::load();
+    if config.clipboard_history {
+        clipboard.borrow_mut().start(
+            config.clipboard_history_size,
+            config.clipboard_history_gpg_id,
+        );
+        watch_clipboard(clipboard);
+    }
+    0
+}
+
+/// Runs a command-line flag that prints an answer and exits, or None for
+/// a command line that opens the launcher. These run in main rather
+/// than the command-line handler, which is in the primary instance when
+/// one is running, writing to its terminal rather than the caller's, and
+/// which `--listen` would freeze.
+fn run_cli_command(args: &[std::ffi::OsString]) -> Option<i32> {
+    let flag = |name| args.iter().any(|arg| arg == name);
+    if flag("--listen") {
+        return Some(events::listen());
+    }
+    if flag("--doctor") {
+        return Some(run_doctor());
+    }
+    if let Some(key) = flag_value(args, "--restore-usage") {
+        return Some(run_restore_usage(&key));
+    }
+    if let Some(path) = flag_value(args, "--export-usage") {
+        return Some(run_export_usage(Path::new(&path)));
+    }
+    if let Some(path) = flag_value(args, "--import-usage") {
+        return Some(run_import_usage(Path::new(&path)));
+    }
+    if flag("--stats") {
+        return Some(run_stats());
+    }
+    if flag("--reset-usage") {
+        return Some(run_reset_usage());
+    }
+    if flag("--prune-usage") {
+        return Some(run_prune_usage());
+    }
+    if flag("--dump-keymap") {
+        let prefixes = mode_prefixes(&plugins::discover());
+        let prefixes: Vec<&str> = prefixes.iter().map(String::as_str).collect();
+        keymap::dump(&keymap::default_bindings(), &prefixes);
+        return Some(0);
+    }
+    None
+}
+
+/// The launcher options on a command line, or what is wrong with one.
+fn parse_options(args: &[std::ffi::OsString]) -> Result<Options, String> {
+    let flag = |name| args.iter().any(|arg| arg == name);
+    let mut parsed = Options {
+        show_usage: flag("--usage"),
+        show_hidden: flag("--show-hidden"),
+        strict_exec: flag("--strict-exec"),
+        emit_queries: flag("--emit-queries"),
+        demo: flag("--demo"),
+        private: flag("--private"),
+        path_bins: flag("--path-bins"),
+        wait_for_startup: flag("--wait-for-startup"),
+        frequent: FrequentOverrides::default(),
+        position: None,
+        monitor: None,
+    };
+    if let Some(value) = flag_value(args, "--position") {
+        let Some(position) = placement::parse_position(&value) else {
+            return Err(format!(
+                "Invalid --position: {}, expected center or top",
+                value
+            ));
+        };
+        parsed.position = Some(position);
+    }
+    if let Some(value) = flag_value(args, "--monitor") {
+        let Some(monitor) = placement::parse_monitor(&value) else {
+            return Err(format!(
+                "Invalid --monitor: {}, expected active, primary, cursor or a number",
+                value
+            ));
+        };
+        parsed.monitor = Some(monitor);
+    }
+    if let Some(value) = flag_value(args, "--frequent-size") {
+        let Ok(size) = value.parse() else {
+            return Err(format!("Invalid --frequent-size: {}", value));
+        };
+        parsed.frequent.size = Some(size);
+    }
+    if let Some(value) = flag_value(args, "--frequent-min-count") {
+        let Ok(min_count) = value.parse() else {
+            return Err(format!("Invalid --frequent-min-count: {}", value));
+        };
+        parsed.frequent.min_count = Some(min_count);
+    }
+    Ok(parsed)
+}
+
+fn configure_command_line(
+    app: &Application,
+    options: Rc<Cell<Options>>,
+    clipboard: ClipboardHistory,
+) {
+    app.connect_command_line(move |app, cmd| {
+        let args = cmd.arguments();
+        if args.iter().any(|arg| arg == "--daemon") {
+            return start_daemon(app, &clipboard);
+        }
+        // Checked in main before the command line was sent here.
+        let Ok(parsed) = parse_options(&args) else {
+            return 1;
+        };
+        options.set(parsed);
+        app.activate();
+        0
+    });
+}
+
+fn build_app(
+    saves: &PendingSaves,
+    dmenu: Option<Rc<dmenu::Dmenu>>,
+    open_with: Option<Rc<open_with::OpenWith>>,
+) -> Application {
+    let mut flags = gio::ApplicationFlags::HANDLES_COMMAND_LINE;
+    // A picker has to run in this process, which holds the lines and the
+    // stdout the choice is printed to. So does opening a file, which was
+    // looked up relative to this process's working directory.
+    if dmenu.is_some() || open_with.is_some() {
+        flags |= gio::ApplicationFlags::NON_UNIQUE;
+    }
+    let app = Application::builder()
+        .application_id(profile::application_id("com.example.hyperfind"))
+        .flags(flags)
+        .build();
+
+    let options = Rc::new(Cell::new(Options::default()));
+    let clipboard = ClipboardHistory::default();
+    configure_command_line(&app, Rc::clone(&options), Rc::clone(&clipboard));
+    let saves = Rc::clone(saves);
+    let conflicts_noted = Rc::new(Cell::new(false));
+    app.connect_activate(move |app| {
+        build_ui(
+            app,
+            options.get(),
+            Rc::clone(&saves),
+            dmenu.clone(),
+            open_with.clone(),
+            Rc::clone(&clipboard),
+            Rc::clone(&conflicts_noted),
+        );
+    });
+
+    app
+}
+
+fn main() {
+    // Picked before the application exists, since it decides the
+    // application id as well as where usage is kept.
+    let args: Vec<_> = env::args_os().collect();
+    if let Some(name) = flag_value(&args, "--profile") {
+        if let Err(err) = profile::validate(&name) {
+            eprintln!("Invalid --profile: {}", err);
+            std::process::exit(1);
+        }
+        profile::set(&name);
+    }
+    desktop_dirs::set(desktop_dirs::from_args(&args));
+    if let Some(code) = run_cli_command(&args) {
+        std::process::exit(code);
+    }
+    if let Err(err) = parse_options(&args) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+    if !check_bindings(args.iter().any(|arg| arg == "--strict-bindings")) {
+        std::process::exit(1);
+    }
+
+    let dmenu = dmenu::from_args(&args).map(Rc::new);
+    let open_with = match open_with::from_args(&args) {
+        Some(Ok(open_with)) => Some(Rc::new(open_with)),
+        Some(Err(err)) => {
+            eprintln!("Can't open {}", err);
+            std::process::exit(1);
+        }
+        None => None,
+    };
+
+    ungrab_on_panic();
+    let saves = PendingSaves::default();
+    let app = build_app(&saves, dmenu.clone(), open_with);
+    app.run();
+    for save in saves.take() {
+        let _ = save.join();
+    }
+    if let Some(dmenu) = dmenu
+        && !dmenu.picked.get()
+    {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(key: &str, name: &str) -> AppEntry {
+        let app_info = gio::AppInfo::create_from_commandline(
+            "true",
+            Some(name),
+            gio::AppInfoCreateFlags::NONE,
+        )
+        .unwrap();
+        AppEntry {
+            key: key.to_string(),
+            name: name.to_string(),
+            untranslated_name: None,
+            sort_key: String::new(),
+            icon: None,
+            app_info,
+            hidden: false,
+            desktops: Vec::new(),
+            not_desktops: Vec::new(),
+            keywords: Vec::new(),
+            packaging: None,
+            duplicate: false,
+            terminal: false,
+            dedicated_gpu: false,
+            autostart: false,
+            running: false,
+            description: None,
+        }
+    }
+
+    fn names(apps: &[AppEntry]) -> Vec<&str> {
+        apps.iter().map(|app| app.name.as_str()).collect()
+    }
+
+    #[test]
+    fn order_apps_reuses_a_covering_cache() {
+        let apps = vec![app("a", "Alpha"), app("z", "Zed"), app("b", "Beta")];
+        let cached = order_cache(1, &order_apps(apps.clone(), None).0);
+        let (ordered, sorted) = order_apps(apps, Some(cached));
+        assert!(!sorted);
+        assert_eq!(names(&ordered), ["Alpha", "Beta", "Zed"]);
+        assert_eq!(ordered[1].sort_key, "beta");
+    }
+
+    #[test]
+    fn order_apps_sorts_afresh_when_the_cache_is_stale() {
+        // Written before "Beta" was installed, with Zed first.
+        let stale = app_cache::OrderCache {
+            hash: 1,
+            entries: vec![
+                app_cache::OrderEntry {
+                    key: "z".to_string(),
+                    sort_key: "zed".to_string(),
+                },
+                app_cache::OrderEntry {
+                    key: "a".to_string(),
+                    sort_key: "alpha".to_string(),
+                },
+            ],
+        };
+        let apps = vec![app("z", "Zed"), app("b", "Beta"), app("a", "Alpha")];
+        let (ordered, sorted) = order_apps(apps, Some(stale));
+        assert!(sorted);
+        assert_eq!(names(&ordered), ["Alpha", "Beta", "Zed"]);
+    }
+
+    /// `cargo test --release -- --ignored --nocapture bench_` to compare a
+    /// fresh sort of a large app set with reusing its cached order. The
+    /// cached side includes hashing this machine's desktop dirs, as
+    /// `load_apps` does before it can use the cache.
+    #[test]
+    #[ignore]
+    fn bench_order_apps() {
+        let apps: Vec<AppEntry> = (0..5000)
+            .map(|index| {
+                let name = format!("App {:04} {}", (index * 7919) % 5000, index);
+                app(&format!("app-{}.desktop", index), &name)
+            })
+            .collect();
+
+        let started = Instant::now();
+        let (fresh, _) = order_apps(apps.clone(), None);
+        let fresh_time = started.elapsed();
+        let cache = order_cache(app_cache::desktop_dirs_hash(), &fresh);
+
+        let started = Instant::now();
+        let hash = app_cache::desktop_dirs_hash();
+        let hash_time = started.elapsed();
+        let (cached, sorted) = order_apps(apps, (cache.hash == hash).then_some(cache));
+        let cached_time = started.elapsed();
+
+        assert!(!sorted);
+        assert_eq!(names(&cached), names(&fresh));
+        println!(
+            "5000 apps: sorted in {:?}, from the cache in {:?} ({:?} of it hashing)",
+            fresh_time, cached_time, hash_time
+        );
+    }
+
+    fn args(list: &[&str]) -> Vec<std::ffi::OsString> {
+        list.iter().map(|arg| arg.into()).collect()
+    }
+
+    #[test]
+    fn default_keymap_and_mode_prefixes_pass_strict_bindings() {
+        assert!(check_bindings(true));
+    }
+
+    #[test]
+    fn launcher_flags_are_not_cli_commands() {
+        assert_eq!(
+            run_cli_command(&args(&["hyperfind", "--strict-bindings", "--demo"])),
+            None
+        );
+    }
+
+    fn used(count: u64, last_used: u64) -> UsageEntry {
+        UsageEntry {
+            count,
+            last_used,
+            ..UsageEntry::default()
+        }
+    }
+
+    #[test]
+    fn trashed_usage_can_be_restored() {
+        let mut entry = used(4, 100);
+        entry.fail_count = 2;
+        entry.last_failed = 150;
+        let mut usage = UsageMap::from([("a.desktop".to_string(), entry)]);
+        let mut trash = UsageTrash::new();
+        assert!(trash_usage("a.desktop", &mut usage, &mut trash, 200));
+        assert!(!trash_usage("a.desktop", &mut usage, &mut trash, 200));
+        assert!(usage.is_empty());
+
+        // Launches since the reset are added to what comes back.
+        usage.insert("a.desktop".to_string(), used(1, 300));
+        assert!(restore_usage("a.desktop", &mut usage, &mut trash));
+        assert_eq!(usage["a.desktop"].count, 5);
+        assert_eq!(usage["a.desktop"].last_used, 300);
+        assert_eq!(usage["a.desktop"].fail_count, 2);
+        assert_eq!(usage["a.desktop"].last_failed, 150);
+        assert!(!restore_usage("a.desktop", &mut usage, &mut trash));
+    }
+
+    #[test]
+    fn purge_trash_keeps_entries_for_the_grace_period() {
+        let mut usage = UsageMap::from([
+            ("old.desktop".to_string(), used(1, 0)),
+            ("new.desktop".to_string(), used(1, 0)),
+        ]);
+        let mut trash = UsageTrash::new();
+        trash_usage("old.desktop", &mut usage, &mut trash, 1000);
+        trash_usage("new.desktop", &mut usage, &mut trash, 2000);
+        purge_trash(&mut trash, 1000 + TRASH_GRACE_SECS);
+        assert!(!trash.contains_key("old.desktop"));
+        assert!(trash.contains_key("new.desktop"));
+    }
+
+    /// An empty directory of its own for a test that touches files.
+    pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("hyperfind-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_usage(path: &Path, usage: &UsageMap) {
+        let contents = usage_file_contents(usage, &UsageTrash::new(), false).unwrap();
+        write_atomic(path, contents.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn write_atomic_keeps_the_previous_file_as_a_backup() {
+        let path = scratch_dir("write-atomic").join("usage.json");
+        write_atomic(&path, b"one").unwrap();
+        assert!(!with_suffix(&path, ".bak").exists());
+        write_atomic(&path, b"two").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two");
+        assert_eq!(
+            fs::read_to_string(with_suffix(&path, ".bak")).unwrap(),
+            "one"
+        );
+        assert!(!with_suffix(&path, ".tmp").exists());
+    }
+
+    #[test]
+    fn corrupt_usage_is_recovered_from_the_backup() {
+        let path = scratch_dir("corrupt-usage").join("usage.json");
+        write_usage(
+            &path,
+            &UsageMap::from([("a.desktop".to_string(), used(2, 10))]),
+        );
+        write_usage(
+            &path,
+            &UsageMap::from([("a.desktop".to_string(), used(3, 20))]),
+        );
+        fs::write(&path, "{\"version\": 3, \"entries\": {\"a.des").unwrap();
+
+        let usage = load_usage_file(&path).entries;
+        assert_eq!(usage["a.desktop"].count, 2);
+
+        fs::write(with_suffix(&path, ".bak"), "").unwrap();
+        assert!(load_usage_file(&path).entries.is_empty());
+    }
+
+    #[test]
+    fn exported_usage_reads_back_the_same() {
+        let mut entry = used(3, 1_700_000_000);
+        entry.daily.insert("2026-10-01".to_string(), 3);
+        entry.time_bins[2] = 3;
+        entry.fail_count = 1;
+        let usage = UsageMap::from([("a.desktop".to_string(), entry)]);
+
+        let exported = usage_file_contents(&usage, &UsageTrash::new(), true).unwrap();
+        let Ok(imported) = parse_usage(&exported) else {
+            panic!("export didn't parse");
+        };
+        assert_eq!(
+            serde_json::to_value(&imported.entries).unwrap(),
+            serde_json::to_value(&usage).unwrap()
+        );
+    }
+
+    #[test]
+    fn import_usage_adds_to_existing_history() {
+        let mut usage = UsageMap::from([("a.desktop".to_string(), used(2, 50))]);
+        usage.get_mut("a.desktop").unwrap().time_bins[0] = 2;
+        let mut imported_entry = used(3, 40);
+        imported_entry.time_bins[0] = 1;
+        let imported = UsageMap::from([
+            ("a.desktop".to_string(), imported_entry),
+            ("b.desktop".to_string(), used(1, 60)),
+        ]);
+
+        import_usage(&mut usage, imported);
+        assert_eq!(usage["a.desktop"].count, 5);
+        assert_eq!(usage["a.desktop"].last_used, 50);
+        assert_eq!(usage["a.desktop"].time_bins[0], 3);
+        assert_eq!(usage["b.desktop"].count, 1);
+    }
+
+    #[test]
+    fn parse_options_reads_flags_and_values() {
+        let Ok(options) = parse_options(&args(&[
+            "hyperfind",
+            "--private",
+            "--position",
+            "top",
+            "--monitor",
+            "1",
+            "--frequent-size",
+            "4",
+        ])) else {
+            panic!("options didn't parse");
+        };
+        assert!(options.private && !options.demo);
+        assert!(matches!(options.position, Some(placement::Position::Top)));
+        assert!(matches!(
+            options.monitor,
+            Some(placement::Monitor::Index(1))
+        ));
+        assert_eq!(options.frequent.size, Some(4));
+        assert_eq!(options.frequent.min_count, None);
+    }
+
+    #[test]
+    fn parse_options_rejects_bad_values() {
+        for bad in [
+            ["--position", "left"],
+            ["--monitor", "middle"],
+            ["--frequent-size", "lots"],
+            ["--frequent-min-count", "-1"],
+        ] {
+            assert!(parse_options(&args(&bad)).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn parse_usage_reads_each_format_version() {
+        let v1 = r#"{"a.desktop": {"count": 2, "last_used": 10}}"#;
+        let v2 = r#"{"version": 2, "entries": {"a.desktop": {"count": 3, "last_used": 20}}}"#;
+        assert!(matches!(parse_usage(v1), Ok(usage) if usage.entries["a.desktop"].count == 2));
+        assert!(matches!(
+            parse_usage(v2),
+            Ok(usage) if usage.entries["a.desktop"].count == 3 && usage.trash.is_empty()
+        ));
+        assert!(matches!(
+            parse_usage(r#"{"version": 4, "entries": {}}"#),
+            Err(UsageFileError::Unsupported(4))
+        ));
+        assert!(matches!(
+            parse_usage(r#"{"version": "2"}"#),
+            Err(UsageFileError::Corrupt(_))
+        ));
+        assert!(matches!(
+            parse_usage("[1, 2"),
+            Err(UsageFileError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn newer_usage_files_are_kept_aside_rather_than_loaded() {
+        let path = scratch_dir("future-usage").join("usage.json");
+        let future = r#"{"version": 4, "entries": {}}"#;
+        fs::write(&path, future).unwrap();
+        assert!(load_usage_file(&path).entries.is_empty());
+        assert_eq!(
+            fs::read_to_string(with_suffix(&path, ".unsupported")).unwrap(),
+            future
+        );
+        // Unlike a corrupt file, the backup isn't used in its place.
+        let backup = r#"{"a.desktop": {"count": 2, "last_used": 10}}"#;
+        fs::write(with_suffix(&path, ".bak"), backup).unwrap();
+        assert!(load_usage_file(&path).entries.is_empty());
+    }
+
+    fn desktop_entry(lines: &str) -> gio::AppInfo {
+        let keyfile = gtk::glib::KeyFile::new();
+        keyfile
+            .load_from_data(
+                &format!("[Desktop Entry]\nType=Application\nName=Test\n{}", lines),
+                gtk::glib::KeyFileFlags::NONE,
+            )
+            .unwrap();
+        gio::DesktopAppInfo::from_keyfile(&keyfile)
+            .unwrap()
+            .upcast()
+    }
+
+    #[test]
+    fn strict_exec_finds_programs_started_through_env() {
+        // GIO itself drops entries whose TryExec or Exec program is missing,
+        // but not one started through `env`.
+        let missing = "hyperfind-test-no-such-program";
+        let exec_only = desktop_entry(&format!("Exec=env FOO=1 {} %u", missing));
+        assert_eq!(missing_program(&exec_only, false), None);
+        assert_eq!(missing_program(&exec_only, true).as_deref(), Some(missing));
+
+        assert_eq!(missing_program(&desktop_entry("Exec=sh"), true), None);
+    }
+
+    #[test]
+    fn apps_for_other_desktops_are_foreign() {
+        let kde = vec!["KDE".to_string()];
+        let gnome_only =
+            build_app_entry(desktop_entry("Exec=sh\nOnlyShowIn=GNOME;Unity;")).unwrap();
+        assert_eq!(gnome_only.desktops, vec!["GNOME", "Unity"]);
+        assert!(is_foreign(&gnome_only, &kde));
+        assert!(!is_foreign(
+            &gnome_only,
+            &["ubuntu".to_string(), "unity".to_string()]
+        ));
+        // Nothing is foreign when the running desktop isn't known.
+        assert!(!is_foreign(&gnome_only, &[]));
+
+        let not_kde = build_app_entry(desktop_entry("Exec=sh\nNotShowIn=KDE;")).unwrap();
+        assert!(is_foreign(&not_kde, &kde));
+        assert!(!is_foreign(&not_kde, &["XFCE".to_string()]));
+
+        let anywhere = build_app_entry(desktop_entry("Exec=sh")).unwrap();
+        assert!(!is_foreign(&anywhere, &kde));
+    }
+
+    fn ranking() -> Ranking {
+        Ranking {
+            current_desktops: Vec::new(),
+            foreign_desktop_penalty: 0,
+            contextual: false,
+            recency_window_secs: 90 * DAY_SECS,
+            frequent_size: 5,
+            frequent_min_count: 1,
+            recent_section: false,
+        }
+    }
+
+    /// The names under Frequently Used on an empty search.
+    fn frequently_used(
+        apps: &[AppEntry],
+        usage: &UsageMap,
+        ranking: &Ranking,
+        now: u64,
+    ) -> Vec<String> {
+        let sources = Sources {
+            apps,
+            path_bins: &[],
+            recent: &[],
+            bookmarks: &[],
+            projects: &[],
+            power: &[],
+        };
+        let items = build_view_items(
+            &sources,
+            "",
+            usage,
+            ranking,
+            now,
+            false,
+            &Curation::default(),
+        );
+        items
+            .iter()
+            .skip_while(
+                |item| !matches!(item, ResultItem::Header(title) if title == "Frequently Used"),
+            )
+            .skip(1)
+            .map_while(|item| match item {
+                ResultItem::App(app) => Some(app.name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn recent_use_outranks_an_old_burst_of_launches() {
+        let apps = [app("old", "Old"), app("new", "New")];
+        let usage = UsageMap::from([
+            ("old".to_string(), used(50, NOW - 200 * DAY_SECS)),
+            ("new".to_string(), used(5, NOW - 60)),
+        ]);
+        assert_eq!(
+            frequently_used(&apps, &usage, &ranking(), NOW),
+            ["New", "Old"]
+        );
+    }
+
+    #[test]
+    fn equally_recent_apps_rank_by_launch_count() {
+        let apps = [app("few", "Few"), app("many", "Many")];
+        let usage = UsageMap::from([
+            ("few".to_string(), used(2, NOW - DAY_SECS)),
+            ("many".to_string(), used(20, NOW - DAY_SECS)),
+        ]);
+        assert_eq!(
+            frequently_used(&apps, &usage, &ranking(), NOW),
+            ["Many", "Few"]
+        );
+    }
+
+    #[test]
+    fn apps_that_only_failed_to_launch_are_not_frequent() {
+        let apps = [app("broken", "Broken"), app("fine", "Fine")];
+        let broken = UsageEntry {
+            fail_count: 3,
+            last_failed: NOW,
+            ..UsageEntry::default()
+        };
+        let usage = UsageMap::from([
+            ("broken".to_string(), broken),
+            ("fine".to_string(), used(1, NOW)),
+        ]);
+        assert_eq!(frequently_used(&apps, &usage, &ranking(), NOW), ["Fine"]);
+    }
+
+    #[test]
+    fn usage_files_move_out_of_the_legacy_directory() {
+        let legacy = scratch_dir("legacy-share");
+        let dir = scratch_dir("xdg-data").join("hyperfind");
+        fs::write(legacy.join("usage.json"), "{}").unwrap();
+        fs::write(legacy.join("usage-trash.json"), "{}").unwrap();
+        move_usage_files(&legacy, &dir);
+        assert!(!legacy.join("usage.json").exists());
+        assert!(!legacy.join("usage-trash.json").exists());
+        assert_eq!(fs::read_to_string(dir.join("usage.json")).unwrap(), "{}");
+        assert!(dir.join("usage-trash.json").exists());
+    }
+
+    #[test]
+    fn usage_already_at_the_new_location_is_not_overwritten() {
+        let legacy = scratch_dir("legacy-share-kept");
+        let dir = scratch_dir("xdg-data-kept");
+        fs::write(legacy.join("usage.json"), "old").unwrap();
+        fs::write(dir.join("usage.json"), "new").unwrap();
+        move_usage_files(&legacy, &dir);
+        assert_eq!(fs::read_to_string(dir.join("usage.json")).unwrap(), "new");
+        assert_eq!(
+            fs::read_to_string(legacy.join("usage.json")).unwrap(),
+            "old"
+        );
+    }
+
+    /// A unix time at `hour` o'clock local time, so the time-of-day tests
+    /// don't depend on the machine's time zone.
+    fn at_hour(hour: i32) -> u64 {
+        gtk::glib::DateTime::from_local(2026, 10, 14, hour, 30, 0.0)
+            .unwrap()
+            .to_unix() as u64
+    }
+
+    #[test]
+    fn time_bins_are_four_hours_of_the_local_day() {
+        assert_eq!(time_bin(at_hour(0)), 0);
+        assert_eq!(time_bin(at_hour(3)), 0);
+        assert_eq!(time_bin(at_hour(4)), 1);
+        assert_eq!(time_bin(at_hour(9)), 2);
+        assert_eq!(time_bin(at_hour(23)), TIME_BINS - 1);
+    }
+
+    #[test]
+    fn launches_are_counted_in_the_bin_they_happened_in() {
+        let mut usage = UsageMap::new();
+        record_usage("slack", &mut usage, at_hour(9));
+        record_usage("slack", &mut usage, at_hour(10));
+        record_usage("slack", &mut usage, at_hour(21));
+        assert_eq!(usage["slack"].time_bins, [0, 0, 2, 0, 0, 1]);
+    }
+
+    #[test]
+    fn time_of_day_boost_is_a_share_of_a_tenth_of_the_base() {
+        let mut entry = used(4, 0);
+        assert_eq!(time_of_day_boost(&entry, at_hour(9), 1000), 0);
+        entry.time_bins[2] = 4;
+        assert_eq!(time_of_day_boost(&entry, at_hour(9), 1000), 100);
+        assert_eq!(time_of_day_boost(&entry, at_hour(21), 1000), 0);
+        entry.time_bins[5] = 4;
+        assert_eq!(time_of_day_boost(&entry, at_hour(21), 1000), 50);
+    }
+
+    #[test]
+    fn contextual_ranking_breaks_ties_by_time_of_day() {
+        let apps = [app("slack", "Slack"), app("steam", "Steam")];
+        let last_used = at_hour(8);
+        let mut slack = used(6, last_used);
+        slack.time_bins[2] = 6;
+        let mut steam = used(6, last_used);
+        steam.time_bins[5] = 6;
+        let usage = UsageMap::from([("slack".to_string(), slack), ("steam".to_string(), steam)]);
+
+        let contextual = Ranking {
+            contextual: true,
+            ..ranking()
+        };
+        assert_eq!(
+            frequently_used(&apps, &usage, &contextual, at_hour(9)),
+            ["Slack", "Steam"]
+        );
+        assert_eq!(
+            frequently_used(&apps, &usage, &contextual, at_hour(21)),
+            ["Steam", "Slack"]
+        );
+        assert_eq!(
+            frequently_used(&apps, &usage, &ranking(), at_hour(21)),
+            ["Slack", "Steam"]
+        );
+    }
+
+    #[test]
+    fn relative_time_picks_the_largest_whole_unit() {
+        assert_eq!(relative_time(0, NOW), "never");
+        assert_eq!(relative_time(NOW - 59, NOW), "just now");
+        assert_eq!(relative_time(NOW - 60, NOW), "1m ago");
+        assert_eq!(relative_time(NOW - 3 * 60 * 60 - 59, NOW), "3h ago");
+        assert_eq!(relative_time(NOW - DAY_SECS, NOW), "1d ago");
+        assert_eq!(relative_time(NOW - 6 * DAY_SECS, NOW), "6d ago");
+        assert_eq!(relative_time(NOW - 15 * DAY_SECS, NOW), "2w ago");
+    }
+
+    #[test]
+    fn relative_time_in_the_future_is_just_now() {
+        assert_eq!(relative_time(NOW + 3600, NOW), "just now");
+    }
+
+    #[test]
+    fn usage_suffix_shows_count_and_last_use() {
+        let firefox = app("firefox", "Firefox");
+        let usage = UsageMap::from([("firefox".to_string(), used(42, NOW - 3 * 60 * 60))]);
+        assert_eq!(
+            usage_suffix(&firefox, &usage, true, NOW).as_deref(),
+            Some("(42 uses, 3h ago)")
+        );
+        assert_eq!(
+            usage_suffix(&firefox, &UsageMap::new(), true, NOW).as_deref(),
+            Some("(0 uses)")
+        );
+        assert_eq!(usage_suffix(&firefox, &usage, false, NOW), None);
+    }
+
+    #[test]
+    fn a_repeat_launch_within_the_debounce_is_not_counted() {
+        let mut usage = UsageMap::new();
+        record_usage("term", &mut usage, NOW);
+        record_usage("term", &mut usage, NOW + DUPLICATE_LAUNCH_SECS - 1);
+        let entry = &usage["term"];
+        assert_eq!(entry.count, 1);
+        assert_eq!(entry.last_used, NOW + DUPLICATE_LAUNCH_SECS - 1);
+        assert_eq!(entry.time_bins.iter().sum::<u64>(), 1);
+        assert_eq!(entry.daily.values().sum::<u64>(), 1);
+    }
+
+    #[test]
+    fn launches_further_apart_than_the_debounce_all_count() {
+        let mut usage = UsageMap::new();
+        record_usage("term", &mut usage, NOW);
+        record_usage("term", &mut usage, NOW + DUPLICATE_LAUNCH_SECS);
+        record_usage("other", &mut usage, NOW + DUPLICATE_LAUNCH_SECS);
+        assert_eq!(usage["term"].count, 2);
+        assert_eq!(usage["other"].count, 1);
+    }
+
+    #[test]
+    fn the_first_launch_is_never_a_duplicate() {
+        assert!(!is_duplicate_launch(&UsageEntry::default(), 1));
+        assert!(is_duplicate_launch(&used(1, NOW), NOW));
+    }
+
+    #[test]
+    fn frequently_used_is_capped_at_its_size() {
+        let apps: Vec<AppEntry> = (0..4)
+            .map(|index| app(&format!("app{}", index), &format!("App {}", index)))
+            .collect();
+        let usage: UsageMap = (0..4)
+            .map(|index| (format!("app{}", index), used(index + 1, NOW)))
+            .collect();
+        let ranking = Ranking {
+            frequent_size: 2,
+            ..ranking()
+        };
+        assert_eq!(
+            frequently_used(&apps, &usage, &ranking, NOW),
+            ["App 3", "App 2"]
+        );
+    }
+
+    #[test]
+    fn frequently_used_needs_the_minimum_launch_count() {
+        let apps = [app("once", "Once"), app("often", "Often")];
+        let usage = UsageMap::from([
+            ("once".to_string(), used(1, NOW)),
+            ("often".to_string(), used(3, NOW)),
+        ]);
+        let ranking = Ranking {
+            frequent_min_count: 3,
+            ..ranking()
+        };
+        assert_eq!(frequently_used(&apps, &usage, &ranking, NOW), ["Often"]);
+    }
+
+    #[test]
+    fn frequently_used_has_no_header_when_nothing_qualifies() {
+        let apps = [app("once", "Once")];
+        let usage = UsageMap::from([("once".to_string(), used(1, NOW))]);
+        let sources = Sources {
+            apps: &apps,
+            path_bins: &[],
+            recent: &[],
+            bookmarks: &[],
+            projects: &[],
+            power: &[],
+        };
+        for ranking in [
+            Ranking {
+                frequent_min_count: 3,
+                ..ranking()
+            },
+            Ranking {
+                frequent_size: 0,
+                ..ranking()
+            },
+        ] {
+            let items = build_view_items(
+                &sources,
+                "",
+                &usage,
+                &ranking,
+                NOW,
+                false,
+                &Curation::default(),
+            );
+            assert!(!items.iter().any(
+                |item| matches!(item, ResultItem::Header(title) if title == "Frequently Used")
+            ));
+        }
+    }
+
+    #[test]
+    fn frequent_min_count_can_be_set_on_the_command_line() {
+        let Ok(options) = parse_options(&args(&["hyperfind", "--frequent-min-count", "3"])) else {
+            panic!("options didn't parse");
+        };
+        assert_eq!(options.frequent.min_count, Some(3));
+        assert_eq!(options.frequent.size, None);
+    }
+
+    const WINDOW: u64 = 90 * DAY_SECS;
+
+    #[test]
+    fn usage_score_favours_recent_use_then_launch_count() {
+        let score = |count, age| usage_score(&used(count, NOW - age), NOW, WINDOW);
+        assert_eq!(score(0, 0), 0);
+        assert!(score(2, 0) > score(2, 30 * DAY_SECS));
+        assert!(score(20, DAY_SECS) > score(2, DAY_SECS));
+        // A few launches today beat a huge count from long ago.
+        assert!(score(5, 0) > score(1_000_000, 3 * WINDOW));
+    }
+
+    #[test]
+    fn usage_score_keeps_a_floor_past_the_window() {
+        let old = usage_score(&used(10, NOW - 2 * WINDOW), NOW, WINDOW);
+        let older = usage_score(&used(10, NOW - 20 * WINDOW), NOW, WINDOW);
+        assert_eq!(old, older);
+        assert!(old > usage_score(&used(2, NOW - 2 * WINDOW), NOW, WINDOW));
+        assert_eq!(old, (MIN_RECENCY * 11f64.ln() * USAGE_SCALE) as i64);
+    }
+
+    #[test]
+    fn usage_score_breaks_ties_between_equal_search_matches() {
+        let apps = [app("a", "Editor A"), app("b", "Editor B")];
+        let usage = UsageMap::from([
+            ("a".to_string(), used(2, NOW - 30 * DAY_SECS)),
+            ("b".to_string(), used(2, NOW - 60)),
+        ]);
+        let Ok(matcher) = parse_query("editor") else {
+            panic!("query didn't parse");
+        };
+        let mut scored = score_apps(&apps, &matcher, &usage, &ranking(), NOW);
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        let order: Vec<&str> = scored.iter().map(|(_, app)| app.name.as_str()).collect();
+        assert_eq!(order, ["Editor B", "Editor A"]);
+    }
+
+    #[test]
+    fn a_half_typed_calculation_still_shows_its_row() {
+        let sources = Sources {
+            apps: &[],
+            path_bins: &[],
+            recent: &[],
+            bookmarks: &[],
+            projects: &[],
+            power: &[],
+        };
+        let curation = Curation::default();
+        let first = |query| {
+            build_view_items(
+                &sources,
+                query,
+                &UsageMap::new(),
+                &ranking(),
+                NOW,
+                false,
+                &curation,
+            )
+            .into_iter()
+            .next()
+        };
+        assert!(matches!(
+            first("= 12*37+5"),
+            Some(ResultItem::Calc { value: Some(value), .. }) if value == 449.0
+        ));
+        assert!(matches!(
+            first("= 12*37+"),
+            Some(ResultItem::Calc { value: None, .. })
+        ));
+    }
+
+    fn settings_panel(id: &str, name: &str) -> settings::Panel {
+        settings::Panel {
+            id: id.to_string(),
+            name: name.to_string(),
+            keywords: Vec::new(),
+            command: format!("gnome-control-center {}", id),
+            icons: vec!["preferences-system".to_string()],
+        }
+    }
+
+    fn panel(id: &str, name: &str) -> AppEntry {
+        build_panel_entry(settings_panel(id, name), &[]).unwrap()
+    }
+
+    #[test]
+    fn settings_panels_an_installed_entry_opens_are_left_out() {
+        let mut bluetooth = app("gnome-bluetooth-panel.desktop", "Bluetooth");
+        bluetooth.sort_key = "bluetooth".to_string();
+        bluetooth.app_info = gio::AppInfo::create_from_commandline(
+            "gnome-control-center bluetooth",
+            Some("Bluetooth"),
+            gio::AppInfoCreateFlags::NONE,
+        )
+        .unwrap();
+        let mut zed = app("zed.desktop", "Zed");
+        zed.sort_key = "zed".to_string();
+        let mut apps = vec![bluetooth, zed];
+
+        add_settings_panels(
+            &mut apps,
+            &[panel("bluetooth", "Bluetooth"), panel("wifi", "Wi-Fi")],
+        );
+        let keys: Vec<&str> = apps.iter().map(|app| app.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "gnome-bluetooth-panel.desktop",
+                "settings:wifi",
+                "zed.desktop"
+            ]
+        );
+    }
+
+    #[test]
+    fn ignored_settings_panels_are_not_built() {
+        let wifi = settings_panel("wifi", "Wi-Fi");
+        assert!(build_panel_entry(wifi, &["settings:wifi".to_string()]).is_none());
+    }
+
+    #[test]
+    fn scrolling_to_the_selection_moves_as_little_as_it_can() {
+        // Rows 40 long on a page of 200, scrolled down to 100.
+        assert_eq!(scroll_to_show(120.0, 40.0, 100.0, 200.0), 100.0);
+        assert_eq!(scroll_to_show(260.0, 40.0, 100.0, 200.0), 100.0);
+        assert_eq!(scroll_to_show(80.0, 40.0, 100.0, 200.0), 80.0);
+        assert_eq!(scroll_to_show(280.0, 40.0, 100.0, 200.0), 120.0);
+        assert_eq!(scroll_to_show(0.0, 40.0, 100.0, 200.0), 0.0);
+    }
+
+    #[test]
+    fn usage_of_uninstalled_apps_is_pruned_after_the_grace_period() {
+        let grace = 30 * DAY_SECS;
+        let mut usage = UsageMap::from([
+            ("kept.desktop".to_string(), used(1, NOW - 2 * grace)),
+            (
+                "kept.desktop#new-window".to_string(),
+                used(1, NOW - 2 * grace),
+            ),
+            ("gone.desktop".to_string(), used(9, NOW - 2 * grace)),
+            (
+                "gone.desktop#new-window".to_string(),
+                used(1, NOW - 2 * grace),
+            ),
+            ("recent.desktop".to_string(), used(1, NOW - grace + 1)),
+        ]);
+        let installed = HashSet::from(["kept.desktop"]);
+        let mut trash = UsageTrash::new();
+        let removed = prune_usage(&mut usage, &mut trash, &installed, NOW, grace);
+        assert_eq!(removed, ["gone.desktop", "gone.desktop#new-window"]);
+        let mut left: Vec<&str> = usage.keys().map(String::as_str).collect();
+        left.sort();
+        assert_eq!(
+            left,
+            ["kept.desktop", "kept.desktop#new-window", "recent.desktop"]
+        );
+
+        // Pruned usage waits in the trash like a reset's.
+        assert_eq!(trash["gone.desktop"].deleted_at, NOW);
+        assert!(restore_usage("gone.desktop", &mut usage, &mut trash));
+        assert_eq!(usage["gone.desktop"].count, 9);
+        assert!(trash.contains_key("gone.desktop#new-window"));
+    }
+
+    #[test]
+    fn pruning_leaves_other_sources_usage_alone() {
+        let mut usage: UsageMap = [
+            "file:///tmp/a",
+            "settings:wifi",
+            "tmux:main",
+            url::USAGE_KEY,
+        ]
+        .into_iter()
+        .map(|key| (key.to_string(), used(1, 0)))
+        .collect();
+        let mut trash = UsageTrash::new();
+        assert!(prune_usage(&mut usage, &mut trash, &HashSet::new(), NOW, 0).is_empty());
+        assert!(trash.is_empty());
+        assert_eq!(usage.len(), 4);
+    }
+
+    #[test]
+    fn plugin_prefixes_are_checked_against_the_modes() {
+        let plugin = |name: &str| plugins::Plugin {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+        };
+        let conflicts = |plugins: &[plugins::Plugin]| {
+            let prefixes = mode_prefixes(plugins);
+            let prefixes: Vec<&str> = prefixes.iter().map(String::as_str).collect();
+            keymap::find_conflicts(&[], &prefixes).len()
+        };
+        let modes_alone = conflicts(&[]);
+        assert_eq!(conflicts(&[plugin("weather")]), modes_alone);
+        assert_eq!(conflicts(&[plugin("ssh")]), modes_alone + 1);
+    }
+
+    #[test]
+    fn score_items_adds_usage_and_subtracts_the_penalty() {
+        let names = ["firefox".to_string(), "fish".to_string(), "vim".to_string()];
+        let matcher = QueryMatcher::Fuzzy("fi");
+        let unscored = score_items(&names, String::as_str, |_| None, 0, &matcher, &|_| 0);
+        let base: HashMap<&str, i64> = unscored
+            .iter()
+            .map(|(score, name)| (name.as_str(), *score))
+            .collect();
+        assert_eq!(base.len(), 2);
+
+        let usage_bonus = |key: &str| if key == "fish" { 1000 } else { 0 };
+        let scored = score_items(
+            &names,
+            String::as_str,
+            |name| Some(name.clone()),
+            100,
+            &matcher,
+            &usage_bonus,
+        );
+        for (score, name) in scored {
+            let bonus = usage_bonus(name);
+            assert_eq!(score, base[name.as_str()] + bonus - 100);
+        }
+    }
 }
@@ -0,0 +1,29 @@
+use strsim::jaro_winkler;
+
+/// How well `name` matches a typed `query`, ignoring case. A name that
+/// contains the query scores 1000, less one for each letter it has
+/// beyond it; any other name its Jaro-Winkler similarity out of 1000, if
+/// that's close enough. None when it doesn't match; every name matches an
+/// empty query, with 0.
+pub fn score(name: &str, query: &str) -> Option<i64> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let name_l = name.to_lowercase();
+    let query_l = query.to_lowercase();
+
+    if name_l.contains(&query_l) {
+        let bonus = 1000i64;
+        let penalty = (name_l.len() as i64 - query_l.len() as i64).max(0);
+        return Some(bonus - penalty);
+    }
+
+    let score = jaro_winkler(&name_l, &query_l);
+    if score < 0.75 {
+        return None;
+    }
+
+    Some((score * 1000.0) as i64)
+}
@@ -0,0 +1,82 @@
+use gio::prelude::*;
+use gtk::glib;
+use std::ffi::OsString;
+use std::path::Path;
+
+/// The file passed to `--open`, which the listed apps are offered for.
+pub struct OpenWith {
+    pub file: gio::File,
+    /// Shown in the title so it's clear what is being opened.
+    pub name: String,
+    pub content_type: String,
+}
+
+/// Looks up the file after `--open`, relative to the working directory.
+/// None without the flag; an error for a file that can't be opened.
+pub fn from_args(args: &[OsString]) -> Option<Result<OpenWith, String>> {
+    let path = crate::flag_value(args, "--open")?;
+    let file = gio::File::for_commandline_arg(&path);
+    let content_type = match content_type(&file) {
+        Ok(content_type) => content_type,
+        Err(err) => return Some(Err(format!("{}: {}", path, err.message()))),
+    };
+    let name = file
+        .basename()
+        .map_or(path, |name| name.to_string_lossy().into_owned());
+    Some(Ok(OpenWith {
+        file,
+        name,
+        content_type,
+    }))
+}
+
+/// The file's content type, guessed from its name when GIO has none.
+/// Errors for a file that can't be read.
+pub fn content_type(file: &gio::File) -> Result<String, glib::Error> {
+    let info = file.query_info(
+        gio::FILE_ATTRIBUTE_STANDARD_CONTENT_TYPE,
+        gio::FileQueryInfoFlags::NONE,
+        gio::Cancellable::NONE,
+    )?;
+    Ok(info
+        .content_type()
+        .map(|content_type| content_type.to_string())
+        .unwrap_or_else(|| {
+            let name = file.basename().unwrap_or_default();
+            gio::content_type_guess(Some(Path::new(&name)), &[])
+                .0
+                .to_string()
+        }))
+}
+
+/// Apps that handle `content_type`, with the default one first.
+pub fn handlers(content_type: &str) -> Vec<gio::AppInfo> {
+    let mut apps = gio::AppInfo::all_for_type(content_type);
+    if let Some(default) = gio::AppInfo::default_for_type(content_type, false)
+        && let Some(index) = apps.iter().position(|app| app.equal(&default))
+    {
+        let default = apps.remove(index);
+        apps.insert(0, default);
+    }
+    apps
+}
+
+/// Apps that handle every one of `content_types`, in the order `handlers`
+/// gives for the first.
+pub fn common_handlers(content_types: &[String]) -> Vec<gio::AppInfo> {
+    let Some((first, rest)) = content_types.split_first() else {
+        return Vec::new();
+    };
+    let others: Vec<Vec<gio::AppInfo>> = rest
+        .iter()
+        .map(|content_type| gio::AppInfo::all_for_type(content_type))
+        .collect();
+    handlers(first)
+        .into_iter()
+        .filter(|app| {
+            others
+                .iter()
+                .all(|apps| apps.iter().any(|other| other.equal(app)))
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+use crate::matching;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Queries starting with this list password-store entries.
+pub const PREFIX: &str = "pass ";
+
+/// `$PASSWORD_STORE_DIR`, as pass itself reads it, or `~/.password-store`.
+fn store_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("PASSWORD_STORE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".password-store"))
+}
+
+fn collect(dir: &Path, prefix: &str, entries: &mut Vec<String>) {
+    let Ok(children) = fs::read_dir(dir) else {
+        return;
+    };
+    for child in children.flatten() {
+        let Ok(name) = child.file_name().into_string() else {
+            continue;
+        };
+        // Skips `.git` and `.gpg-id` along with any other dotfiles.
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = child.path();
+        if path.is_dir() {
+            collect(&path, &format!("{}{}/", prefix, name), entries);
+        } else if let Some(entry) = name.strip_suffix(".gpg") {
+            entries.push(format!("{}{}", prefix, entry));
+        }
+    }
+}
+
+/// Every entry in the store, as the names `pass show` takes, sorted.
+pub fn list() -> Vec<String> {
+    let mut entries = Vec::new();
+    if let Some(dir) = store_dir() {
+        collect(&dir, "", &mut entries);
+    }
+    entries.sort();
+    entries
+}
+
+/// Entries matching `filter` best first.
+pub fn search<'a>(entries: &'a [String], filter: &str) -> Vec<&'a String> {
+    let mut scored: Vec<(i64, &String)> = entries
+        .iter()
+        .filter_map(|entry| Some((matching::score(entry, filter)?, entry)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    scored.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Runs `pass show -c`, which decrypts the entry (asking for the key's
+/// passphrase if needed) and puts the password on the clipboard. Only
+/// stderr is kept, for the error; stdout is never read.
+pub fn copy(entry: &str) -> Result<(), String> {
+    let output = Command::new("pass")
+        .args(["show", "-c", "--", entry])
+        .stdout(std::process::Stdio::null())
+        .output()
+        .map_err(|err| format!("couldn't run pass: {}", err))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+/// `[`, `:` and dotfiles are never what anyone types into a launcher.
+fn is_noise(name: &str) -> bool {
+    name.starts_with('.') || !name.starts_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+/// Every executable file on `$PATH`, sorted and deduplicated by name.
+/// Reads whole directories, so run it off the main thread.
+pub fn scan() -> Vec<String> {
+    let Some(path) = env::var_os("PATH") else {
+        return Vec::new();
+    };
+
+    let mut names = BTreeSet::new();
+    for dir in env::split_paths(&path) {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if is_noise(&name) || names.contains(&name) {
+                continue;
+            }
+            // Follows symlinks, which is how most of /usr/bin is laid out.
+            let Ok(metadata) = fs::metadata(entry.path()) else {
+                continue;
+            };
+            if metadata.is_file() && metadata.permissions().mode() & 0o111 != 0 {
+                names.insert(name);
+            }
+        }
+    }
+    names.into_iter().collect()
+}
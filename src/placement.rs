@@ -0,0 +1,178 @@
+use gtk::gdk;
+use gtk::glib::translate::{FromGlibPtrFull, ToGlibPtr};
+use gtk::prelude::*;
+use serde::Deserialize;
+
+/// Where on its monitor the window opens.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Position {
+    Center,
+    /// Centered across, at the top edge.
+    Top,
+}
+
+/// Which monitor the window opens on.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(try_from = "MonitorSetting")]
+pub enum Monitor {
+    /// The one showing the focused window.
+    Active,
+    Primary,
+    /// The one the pointer is on.
+    Cursor,
+    /// GDK's numbering, from 0.
+    Index(i32),
+}
+
+/// `monitor` as written in the config: a name or a number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MonitorSetting {
+    Index(i32),
+    Name(String),
+}
+
+impl TryFrom<MonitorSetting> for Monitor {
+    type Error = String;
+
+    fn try_from(setting: MonitorSetting) -> Result<Self, Self::Error> {
+        match setting {
+            MonitorSetting::Index(index) => Ok(Monitor::Index(index)),
+            MonitorSetting::Name(name) => parse_monitor(&name).ok_or_else(|| {
+                format!(
+                    "unknown monitor {:?}, expected active, primary, cursor or a number",
+                    name
+                )
+            }),
+        }
+    }
+}
+
+pub fn parse_position(value: &str) -> Option<Position> {
+    match value {
+        "center" => Some(Position::Center),
+        "top" => Some(Position::Top),
+        _ => None,
+    }
+}
+
+pub fn parse_monitor(value: &str) -> Option<Monitor> {
+    match value {
+        "active" => Some(Monitor::Active),
+        "primary" => Some(Monitor::Primary),
+        "cursor" => Some(Monitor::Cursor),
+        _ => value.parse().ok().map(Monitor::Index),
+    }
+}
+
+fn cursor_monitor(display: &gdk::Display) -> Option<gdk::Monitor> {
+    let pointer = display.default_seat()?.pointer()?;
+    let (_, x, y) = pointer.position();
+    display.monitor_at_point(x, y)
+}
+
+/// The window `_NET_ACTIVE_WINDOW` names. The bindings leave this out as
+/// GTK deprecated it, but there's no other way to ask.
+fn active_window(screen: &gdk::Screen) -> Option<gdk::Window> {
+    unsafe {
+        FromGlibPtrFull::from_glib_full(gdk::ffi::gdk_screen_get_active_window(
+            screen.to_glib_none().0,
+        ))
+    }
+}
+
+/// Where the focused window is. A window manager that doesn't set
+/// `_NET_ACTIVE_WINDOW` gets the topmost window instead, which is the
+/// focused one with most. Only X11 lets clients see other windows.
+fn active_monitor(display: &gdk::Display) -> Option<gdk::Monitor> {
+    let screen = display.default_screen();
+    let window = active_window(&screen).or_else(|| screen.window_stack().pop())?;
+    display.monitor_at_window(&window)
+}
+
+/// The monitor `choice` names, looked up as the window opens. One that
+/// isn't there any more, or a window manager that doesn't say which
+/// window is active, falls back to the pointer's monitor, then the
+/// primary one, then the first.
+pub fn monitor(display: &gdk::Display, choice: Monitor) -> Option<gdk::Monitor> {
+    let chosen = match choice {
+        Monitor::Active => active_monitor(display),
+        Monitor::Primary => display.primary_monitor(),
+        Monitor::Cursor => cursor_monitor(display),
+        Monitor::Index(index) => display.monitor(index),
+    };
+    chosen
+        .or_else(|| cursor_monitor(display))
+        .or_else(|| display.primary_monitor())
+        .or_else(|| display.monitor(0))
+}
+
+/// Moves a `width` by `height` window to `position` on the chosen
+/// monitor, `y_offset` pixels further down. Monitor geometry and window
+/// sizes are both in GDK's scaled pixels, so monitors with a different
+/// scale come out the same.
+pub fn place(
+    window: &gtk::Window,
+    (width, height): (i32, i32),
+    position: Position,
+    y_offset: i32,
+    choice: Monitor,
+) {
+    let Some(display) = gdk::Display::default() else {
+        return;
+    };
+    let Some(monitor) = monitor(&display, choice) else {
+        return;
+    };
+    let area = monitor.geometry();
+    let x = area.x() + (area.width() - width).max(0) / 2;
+    let y = match position {
+        Position::Center => area.y() + (area.height() - height).max(0) / 2,
+        Position::Top => area.y(),
+    } + y_offset;
+    window.set_position(gtk::WindowPosition::None);
+    window.move_(x, y);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_position_reads_each_name() {
+        assert!(matches!(parse_position("center"), Some(Position::Center)));
+        assert!(matches!(parse_position("top"), Some(Position::Top)));
+        assert!(parse_position("Top").is_none());
+        assert!(parse_position("bottom").is_none());
+        assert!(parse_position("").is_none());
+    }
+
+    #[test]
+    fn parse_monitor_reads_names_and_numbers() {
+        assert!(matches!(parse_monitor("active"), Some(Monitor::Active)));
+        assert!(matches!(parse_monitor("primary"), Some(Monitor::Primary)));
+        assert!(matches!(parse_monitor("cursor"), Some(Monitor::Cursor)));
+        assert!(matches!(parse_monitor("0"), Some(Monitor::Index(0))));
+        assert!(matches!(parse_monitor("2"), Some(Monitor::Index(2))));
+        assert!(parse_monitor("left").is_none());
+        assert!(parse_monitor("1.5").is_none());
+        assert!(parse_monitor("").is_none());
+    }
+
+    #[test]
+    fn monitor_settings_take_a_name_or_a_number() {
+        #[derive(Deserialize)]
+        struct Setting {
+            monitor: Monitor,
+        }
+        let parse = |toml: &str| toml::from_str::<Setting>(toml).map(|setting| setting.monitor);
+        assert!(matches!(parse("monitor = 1"), Ok(Monitor::Index(1))));
+        assert!(matches!(parse("monitor = \"1\""), Ok(Monitor::Index(1))));
+        assert!(matches!(parse("monitor = \"cursor\""), Ok(Monitor::Cursor)));
+        let Err(err) = parse("monitor = \"left\"") else {
+            panic!("an unknown monitor name parsed");
+        };
+        assert!(err.to_string().contains("unknown monitor \"left\""));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::config;
+use std::fs;
+use std::io::Read;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A plugin still running after this is killed and its output dropped.
+pub const TIMEOUT: Duration = Duration::from_millis(300);
+
+/// An executable in `~/.config/hyperfind/plugins/`. Queries starting with
+/// its file name and a space are passed to it.
+pub struct Plugin {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Plugin {
+    pub fn prefix(&self) -> String {
+        format!("{} ", self.name)
+    }
+
+    /// What follows the prefix, if the query is meant for this plugin.
+    pub fn argument<'a>(&self, query: &'a str) -> Option<&'a str> {
+        query.trim_start().strip_prefix(&self.prefix())
+    }
+}
+
+/// One line of plugin output: `title<TAB>icon-name<TAB>command`, with the
+/// icon allowed to be empty.
+#[derive(Clone)]
+pub struct PluginResult {
+    pub title: String,
+    pub icon: Option<String>,
+    pub command: String,
+}
+
+/// Lines without all three fields, or with an empty title or command, are
+/// skipped.
+pub fn parse(output: &str) -> Vec<PluginResult> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let title = fields.next()?.trim();
+            let icon = fields.next()?.trim();
+            let command = fields.next()?.trim();
+            if title.is_empty() || command.is_empty() {
+                return None;
+            }
+            Some(PluginResult {
+                title: title.to_string(),
+                icon: (!icon.is_empty()).then(|| icon.to_string()),
+                command: command.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Executables in the plugin directory, by name.
+pub fn discover() -> Vec<Plugin> {
+    let Some(dir) = config::config_dir().map(|dir| dir.join("plugins")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<Plugin> = entries
+        .flatten()
+        .filter(|entry| {
+            entry.metadata().is_ok_and(|metadata| {
+                metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+            })
+        })
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            Some(Plugin {
+                name,
+                path: entry.path(),
+            })
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    plugins
+}
+
+/// Runs the plugin with `argument` as its only argument and parses what
+/// it prints, killing it if it takes longer than `TIMEOUT`. Blocks, so
+/// call it off the main thread.
+pub fn run(path: &Path, argument: &str) -> Result<Vec<PluginResult>, String> {
+    let mut child = Command::new(path)
+        .arg(argument)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| err.to_string())?;
+
+    // Read on another thread so a plugin filling the pipe can't block
+    // itself past the deadline.
+    let mut stdout = child.stdout.take().ok_or("no stdout")?;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stdout.read_to_string(&mut output);
+        let _ = sender.send(output);
+    });
+
+    let deadline = Instant::now() + TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(10)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {}ms", TIMEOUT.as_millis()));
+            }
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+    // Something the plugin started in the background can keep its
+    // stdout open after it exits, so the read has the same deadline. The
+    // reader is left to finish on its own.
+    let output = receiver
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .map_err(|_| format!("timed out after {}ms", TIMEOUT.as_millis()))?;
+    Ok(parse(&output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+
+    fn fields(result: &PluginResult) -> (&str, Option<&str>, &str) {
+        (&result.title, result.icon.as_deref(), &result.command)
+    }
+
+    #[test]
+    fn parse_reads_tab_separated_lines() {
+        let results = parse("Open\tfirefox\tfirefox --new-window\nNo icon\t\techo a\tb\n");
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            fields(&results[0]),
+            ("Open", Some("firefox"), "firefox --new-window")
+        );
+        // Only the first two tabs split; the command keeps the rest.
+        assert_eq!(fields(&results[1]), ("No icon", None, "echo a\tb"));
+    }
+
+    #[test]
+    fn parse_skips_malformed_lines() {
+        let output = [
+            "",
+            "just a title",
+            "title\ticon",
+            "\ticon\tcommand",
+            "title\ticon\t  ",
+            "kept\t\ttrue",
+        ]
+        .join("\n");
+        let results = parse(&output);
+        assert_eq!(results.len(), 1);
+        assert_eq!(fields(&results[0]), ("kept", None, "true"));
+    }
+
+    #[test]
+    fn run_gives_up_on_output_held_open_past_the_timeout() {
+        let path = scratch_dir("plugin-held-open").join("held");
+        fs::write(&path, "#!/bin/sh\nsleep 5 &\nprintf 'a\\t\\ttrue\\n'\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let started = Instant::now();
+        assert!(run(&path, "").is_err());
+        assert!(started.elapsed() < TIMEOUT * 3);
+    }
+}
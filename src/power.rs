@@ -0,0 +1,56 @@
+/// A session or power command offered alongside apps.
+pub struct Action {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub icon: &'static str,
+    /// Run unless the config overrides it under `[power_commands]`.
+    pub command: &'static str,
+    /// Destructive actions only run on a second Enter, after this question
+    /// replaces their name.
+    pub confirm: Option<&'static str>,
+}
+
+pub const ACTIONS: &[Action] = &[
+    Action {
+        id: "lock",
+        name: "Lock",
+        icon: "system-lock-screen",
+        command: "loginctl lock-session",
+        confirm: None,
+    },
+    Action {
+        id: "logout",
+        name: "Log out",
+        icon: "system-log-out",
+        command: "loginctl terminate-session self",
+        confirm: Some("Really log out?"),
+    },
+    Action {
+        id: "suspend",
+        name: "Suspend",
+        icon: "system-suspend",
+        command: "systemctl suspend",
+        confirm: None,
+    },
+    Action {
+        id: "reboot",
+        name: "Reboot",
+        icon: "system-reboot",
+        command: "systemctl reboot",
+        confirm: Some("Really reboot?"),
+    },
+    Action {
+        id: "shutdown",
+        name: "Shut down",
+        icon: "system-shutdown",
+        command: "systemctl poweroff",
+        confirm: Some("Really shut down?"),
+    },
+];
+
+impl Action {
+    /// Kept apart from app keys, which are desktop file names.
+    pub fn usage_key(&self) -> String {
+        format!("power:{}", self.id)
+    }
+}
@@ -0,0 +1,163 @@
+use crate::matching;
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Queries starting with this list running processes.
+pub const PREFIX: &str = "kill ";
+
+/// Every running process with one command name, killed together.
+#[derive(Clone)]
+pub struct Process {
+    pub name: String,
+    pub pids: Vec<i32>,
+    /// Resident memory of all of them, in KiB.
+    pub memory_kb: u64,
+    /// Some pid belongs to another user, so killing them asks first.
+    pub foreign: bool,
+}
+
+/// The real uid and `VmRSS` from `/proc/<pid>/status`. Kernel threads have
+/// no `VmRSS`, so they come back as None.
+pub fn parse_status(contents: &str) -> Option<(u32, u64)> {
+    let mut uid = None;
+    let mut rss = None;
+    for line in contents.lines() {
+        if let Some(rest) = line.strip_prefix("Uid:") {
+            uid = rest.split_whitespace().next()?.parse().ok();
+        } else if let Some(rest) = line.strip_prefix("VmRSS:") {
+            rss = rest.split_whitespace().next()?.parse().ok();
+        }
+    }
+    Some((uid?, rss?))
+}
+
+/// Running processes grouped by command name, leaving out kernel threads
+/// and hyperfind itself.
+pub fn list() -> Vec<Process> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let uid = unsafe { libc::getuid() };
+    let own_pid = std::process::id() as i32;
+
+    let mut processes: BTreeMap<String, Process> = BTreeMap::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<i32>().ok())
+        else {
+            continue;
+        };
+        if pid == own_pid {
+            continue;
+        }
+        let dir = entry.path();
+        let (Ok(comm), Ok(status)) = (
+            fs::read_to_string(dir.join("comm")),
+            fs::read_to_string(dir.join("status")),
+        ) else {
+            continue;
+        };
+        let Some((owner, rss)) = parse_status(&status) else {
+            continue;
+        };
+        let name = comm.trim_end().to_string();
+        let process = processes.entry(name.clone()).or_insert_with(|| Process {
+            name,
+            pids: Vec::new(),
+            memory_kb: 0,
+            foreign: false,
+        });
+        process.pids.push(pid);
+        process.memory_kb += rss;
+        process.foreign |= owner != uid;
+    }
+    processes.into_values().collect()
+}
+
+/// Processes whose command name matches `filter` best first, or all of
+/// them, biggest first, while it's empty.
+pub fn search(processes: Vec<Process>, filter: &str) -> Vec<Process> {
+    let mut scored: Vec<(i64, Process)> = processes
+        .into_iter()
+        .filter_map(|process| Some((matching::score(&process.name, filter)?, process)))
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.memory_kb.cmp(&a.1.memory_kb))
+            .then_with(|| a.1.name.cmp(&b.1.name))
+    });
+    scored.into_iter().map(|(_, process)| process).collect()
+}
+
+/// Sends `signal` to every pid, returning the error for the first one
+/// that couldn't be signalled. Pids that already exited are fine.
+pub fn signal(pids: &[i32], signal: i32) -> Result<(), String> {
+    let mut first_error = None;
+    for &pid in pids {
+        if unsafe { libc::kill(pid, signal) } != 0 {
+            let err = std::io::Error::last_os_error();
+            if err.raw_os_error() != Some(libc::ESRCH) && first_error.is_none() {
+                first_error = Some(format!("pid {}: {}", pid, err));
+            }
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Memory as the row shows it, e.g. `512 MB` or `1.4 GB`.
+pub fn format_memory(kb: u64) -> String {
+    let mb = kb as f64 / 1024.0;
+    if mb < 1024.0 {
+        format!("{:.0} MB", mb)
+    } else {
+        format!("{:.1} GB", mb / 1024.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_gives_the_real_uid_and_resident_memory() {
+        let status = "\
+Name:\tfirefox
+State:\tS (sleeping)
+Uid:\t1000\t1000\t1000\t1000
+Gid:\t1000\t1000\t1000\t1000
+VmRSS:\t  524288 kB
+Threads:\t93
+";
+        assert_eq!(parse_status(status), Some((1000, 524288)));
+    }
+
+    #[test]
+    fn kernel_threads_have_no_status() {
+        let status = "Name:\tkworker/0:1\nUid:\t0\t0\t0\t0\nThreads:\t1\n";
+        assert_eq!(parse_status(status), None);
+    }
+
+    fn process(name: &str, memory_kb: u64) -> Process {
+        Process {
+            name: name.to_string(),
+            pids: vec![1],
+            memory_kb,
+            foreign: false,
+        }
+    }
+
+    #[test]
+    fn an_empty_search_lists_the_biggest_first() {
+        let found = search(vec![process("bash", 10), process("firefox", 500)], "");
+        let names: Vec<&str> = found.iter().map(|process| process.name.as_str()).collect();
+        assert_eq!(names, ["firefox", "bash"]);
+    }
+
+    #[test]
+    fn memory_is_shown_in_megabytes_then_gigabytes() {
+        assert_eq!(format_memory(512 * 1024), "512 MB");
+        assert_eq!(format_memory(1434 * 1024), "1.4 GB");
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Chosen once in `main`, before anything reads a path, and fixed for the
+/// life of the process.
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Profile names become a directory name and part of the application id,
+/// so only letters, digits, `-` and `_` are allowed.
+pub fn validate(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Profile name must not be empty".to_string());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && *c != '-' && *c != '_')
+    {
+        return Err(format!("Profile name must not contain '{}'", c));
+    }
+    Ok(())
+}
+
+pub fn set(name: &str) {
+    let _ = PROFILE.set(name.to_string());
+}
+
+pub fn current() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+/// `dir` itself for the default profile, `dir/profiles/<name>` otherwise.
+pub fn dir(dir: PathBuf) -> PathBuf {
+    match current() {
+        Some(name) => dir.join("profiles").join(name),
+        None => dir,
+    }
+}
+
+/// Each profile gets its own application id, so a work and a personal
+/// instance don't forward their command lines to each other.
+pub fn application_id(base: &str) -> String {
+    match current() {
+        Some(name) => format!("{}.profile-{}", base, name),
+        None => base.to_string(),
+    }
+}
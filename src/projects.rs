@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone)]
+pub struct Project {
+    /// The directory's own name, which the search matches.
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Project {
+    /// Opened projects are recorded under `project:<path>`.
+    pub fn usage_key(&self) -> String {
+        format!("project:{}", self.path.display())
+    }
+}
+
+/// The directory `path` is, following it if it's a symlink. A link to a
+/// link isn't followed further.
+fn resolve_dir(path: &Path) -> Option<PathBuf> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if metadata.is_dir() {
+        return Some(path.to_path_buf());
+    }
+    if !metadata.is_symlink() {
+        return None;
+    }
+    let target = fs::read_link(path).ok()?;
+    let target = path
+        .parent()
+        .map_or(target.clone(), |dir| dir.join(&target));
+    fs::symlink_metadata(&target)
+        .is_ok_and(|metadata| metadata.is_dir())
+        .then_some(target)
+}
+
+fn push(path: &Path, name: String, seen: &mut HashSet<PathBuf>, projects: &mut Vec<Project>) {
+    if let Some(path) = resolve_dir(path)
+        && seen.insert(path.clone())
+    {
+        projects.push(Project { name, path });
+    }
+}
+
+fn name_of(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+/// The directories directly inside each root, skipping hidden ones, then
+/// the explicitly listed `paths`. Missing roots and paths are warned
+/// about and skipped.
+pub fn load(roots: &[PathBuf], paths: &[PathBuf]) -> Vec<Project> {
+    let mut seen = HashSet::new();
+    let mut projects = Vec::new();
+    for root in roots {
+        let Ok(entries) = fs::read_dir(root) else {
+            eprintln!("Skipping project root {}: not a directory", root.display());
+            continue;
+        };
+        let mut entries: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| !name_of(path).starts_with('.'))
+            .collect();
+        entries.sort();
+        for path in entries {
+            push(&path, name_of(&path), &mut seen, &mut projects);
+        }
+    }
+    for path in paths {
+        if resolve_dir(path).is_none() {
+            eprintln!("Skipping project {}: not a directory", path.display());
+            continue;
+        }
+        push(path, name_of(path), &mut seen, &mut projects);
+    }
+    projects
+}
+
+/// `template` with `{}` replaced by the shell-quoted path, or the path
+/// appended if it has no `{}`.
+pub fn command_line(template: &str, path: &Path) -> String {
+    let quoted = gtk::glib::shell_quote(path);
+    let quoted = quoted.to_string_lossy();
+    if template.contains("{}") {
+        template.replace("{}", &quoted)
+    } else {
+        format!("{} {}", template, quoted)
+    }
+}
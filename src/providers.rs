@@ -0,0 +1,1259 @@
+use crate::{
+    AppEntry, ErrorBar, LaunchError, LauncherState, QueryMatcher, ResultItem, Sources, bookmarks,
+    build_view_items, calc, clipboard, emoji, files, first_selectable_index, gpu, launch_app,
+    launch_desktop_action, launch_in_terminal, launch_on_gpu, locate, now_unix, open_uri,
+    parse_run_command, pass, power, procs, projects, quit_later, recent, record_in_background,
+    record_usage, rerender, run_command, score_apps, show_error, snippets, snooze, ssh,
+    switch_to_running, symbols, systemd, tmux, url, usage_score,
+};
+use gtk::prelude::*;
+use gtk::{ListBox, glib};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Asks for the current query's rows again, for a provider whose answer
+/// arrives after `query` has returned.
+pub type Refresh = Rc<dyn Fn()>;
+
+/// What activating a row did.
+pub enum ActivateOutcome {
+    /// Something was launched or copied; hyperfind quits.
+    Quit,
+    /// The row changed in place or there was nothing to do.
+    Stay,
+    /// Shown in the error bar; hyperfind stays open.
+    Failed(String),
+}
+
+impl From<bool> for ActivateOutcome {
+    fn from(quit: bool) -> Self {
+        if quit {
+            ActivateOutcome::Quit
+        } else {
+            ActivateOutcome::Stay
+        }
+    }
+}
+
+impl From<Result<(), LaunchError>> for ActivateOutcome {
+    fn from(result: Result<(), LaunchError>) -> Self {
+        match result {
+            Ok(()) => ActivateOutcome::Quit,
+            // The activation that got there first does the quitting.
+            Err(LaunchError::Cancelled) => ActivateOutcome::Stay,
+            Err(err) => ActivateOutcome::Failed(err.to_string()),
+        }
+    }
+}
+
+/// One kind of search. A query starting with a provider's prefix goes to
+/// it alone; any other query goes to the first provider without one.
+pub trait Provider {
+    fn prefix(&self) -> Option<&str>;
+
+    /// The rows for `query`, which still starts with the prefix. A
+    /// provider that answers later calls `refresh` once it has.
+    fn query(&self, state: &LauncherState, query: &str, refresh: &Refresh) -> Vec<ResultItem>;
+
+    /// Called when a query goes to another provider, to stop anything
+    /// still running for an earlier one.
+    fn cancel(&self, _state: &LauncherState) {}
+
+    /// Runs one of the rows this provider returned.
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        error_bar: &ErrorBar,
+    ) -> ActivateOutcome;
+
+    /// Runs a row the other way, on Shift+Enter: in a terminal, or more
+    /// forcefully. None when the row has no other way.
+    fn activate_secondary(
+        &self,
+        _state: &LauncherState,
+        _item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> Option<ActivateOutcome> {
+        None
+    }
+}
+
+/// Every provider in the order prefixes are tried, the app search last.
+/// A `--dmenu` picker has only its lines, and `--open` only the file's
+/// handlers.
+pub fn registry(state: &LauncherState) -> Vec<Box<dyn Provider>> {
+    if let Some(dmenu) = &state.dmenu {
+        return vec![Box::new(Dmenu {
+            lines: Rc::clone(dmenu),
+        })];
+    }
+    if let Some(open_with) = &state.open_with {
+        return vec![Box::new(OpenWith {
+            file: open_with.file.clone(),
+        })];
+    }
+    let mut providers: Vec<Box<dyn Provider>> = vec![
+        Box::new(Run),
+        Box::new(Calc),
+        Box::new(Files(files::ROOT_PREFIX)),
+        Box::new(Files(files::HOME_PREFIX)),
+        Box::new(Locate),
+        Box::new(Ssh),
+        Box::new(Units),
+        Box::new(Processes),
+        Box::new(Tmux),
+        Box::new(Symbols),
+        Box::new(Snippets),
+        Box::new(Emoji),
+    ];
+    if state.pass_entries.is_some() {
+        providers.push(Box::new(Pass));
+    }
+    if state.clipboard_history {
+        providers.push(Box::new(Clipboard));
+    }
+    providers.push(Box::new(Apps));
+    providers
+}
+
+/// The provider `query` goes to.
+pub fn route(providers: &[Box<dyn Provider>], query: &str) -> usize {
+    let query = query.trim_start();
+    providers
+        .iter()
+        .position(|provider| {
+            provider
+                .prefix()
+                .is_some_and(|prefix| query.starts_with(prefix))
+        })
+        .or_else(|| {
+            providers
+                .iter()
+                .position(|provider| provider.prefix().is_none())
+        })
+        .unwrap_or(0)
+}
+
+/// What follows `prefix` in the query.
+fn filter<'a>(query: &'a str, prefix: &str) -> &'a str {
+    query.trim_start().strip_prefix(prefix).unwrap_or_default()
+}
+
+/// A calculator result, put on the clipboard.
+fn copy_value(value: Option<f64>) -> ActivateOutcome {
+    let Some(value) = value else {
+        return ActivateOutcome::Stay;
+    };
+    clipboard::copy(&calc::format(value));
+    ActivateOutcome::Quit
+}
+
+/// Counts a use of something other than an app under `key`, unless this
+/// is a demo or a private session.
+fn count_use(key: &str, state: &LauncherState) {
+    if state.options.demo || state.options.private {
+        return;
+    }
+    let mut usage_mut = state.usage.borrow_mut();
+    record_usage(key, &mut usage_mut, now_unix());
+    record_in_background(key, &state.saves);
+}
+
+/// Apps, desktop actions, power actions, recent files, bookmarks,
+/// projects, `$PATH` executables and inline arithmetic, ranked together,
+/// and typed URLs.
+struct Apps;
+
+impl Provider for Apps {
+    fn prefix(&self) -> Option<&str> {
+        None
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        if let Some(dropped) = state.dropped.borrow().as_ref() {
+            let mut items = vec![ResultItem::Header(format!("Open {} with", dropped.name))];
+            items.extend(handler_items(&dropped.handlers, query, state));
+            return items;
+        }
+        let apps = state.apps.borrow();
+        let path_bins = state.path_bins.borrow();
+        let recent = state.recent.borrow();
+        let bookmarks = state.bookmarks.borrow();
+        let sources = Sources {
+            apps: &apps,
+            path_bins: &path_bins,
+            recent: &recent,
+            bookmarks: &bookmarks,
+            projects: &state.projects,
+            power: if state.power_menu {
+                power::ACTIONS
+            } else {
+                &[]
+            },
+        };
+        build_view_items(
+            &sources,
+            query,
+            &state.usage.borrow(),
+            &state.ranking,
+            state.now(),
+            state.snooze_deadline().is_some(),
+            &state.curation.borrow(),
+        )
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        match item {
+            ResultItem::App(app) => {
+                let files = state
+                    .dropped
+                    .borrow()
+                    .as_ref()
+                    .map(|dropped| dropped.files.clone())
+                    .unwrap_or_default();
+                if files.is_empty() && switch_to_running(app, state) {
+                    return ActivateOutcome::Quit;
+                }
+                launch_app(app, &files, state).into()
+            }
+            ResultItem::Action {
+                parent_key,
+                action,
+                label,
+            } => match gpu::action_choice(action) {
+                Some(dedicated) => launch_on_gpu(parent_key, dedicated, state).into(),
+                None => launch_desktop_action(parent_key, action, label, state).into(),
+            },
+            ResultItem::Power { action, confirming } => {
+                activate_power(action, *confirming, state, listbox, error_bar).into()
+            }
+            ResultItem::PathBin(name) => run_command(name, false, state).into(),
+            ResultItem::Plugin(result) => run_command(&result.command, false, state).into(),
+            ResultItem::Calc { value, .. } => copy_value(*value),
+            ResultItem::Recent(file) => open_recent(file, state).into(),
+            ResultItem::Bookmark(bookmark) => open_bookmark(bookmark, state).into(),
+            ResultItem::Project(project) => open_project(project, state).into(),
+            ResultItem::WebSearch { url, .. } => open_uri(url, "the search", state).into(),
+            ResultItem::OpenUrl(url) => open_url(url, state).into(),
+            _ => ActivateOutcome::Stay,
+        }
+    }
+
+    /// Apps, `$PATH` executables and plugin lines run in a terminal.
+    fn activate_secondary(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> Option<ActivateOutcome> {
+        let outcome = match item {
+            ResultItem::App(app) => launch_in_terminal(app.clone(), state),
+            ResultItem::PathBin(name) => run_command(name, true, state),
+            ResultItem::Plugin(result) => run_command(&result.command, true, state),
+            _ => return None,
+        };
+        Some(outcome.into())
+    }
+}
+
+/// `--dmenu`: the lines read from stdin.
+struct Dmenu {
+    lines: Rc<crate::dmenu::Dmenu>,
+}
+
+impl Provider for Dmenu {
+    fn prefix(&self) -> Option<&str> {
+        None
+    }
+
+    fn query(&self, _state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        self.lines
+            .search(query)
+            .into_iter()
+            .map(|(index, text)| ResultItem::Line {
+                index,
+                text: text.clone(),
+            })
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        _state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::Line { index, .. } = item else {
+            return ActivateOutcome::Stay;
+        };
+        self.lines.pick(*index);
+        ActivateOutcome::Quit
+    }
+}
+
+/// `--open`: the apps that handle the file, the default one first.
+struct OpenWith {
+    file: gio::File,
+}
+
+impl Provider for OpenWith {
+    fn prefix(&self) -> Option<&str> {
+        None
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        handler_items(&state.apps.borrow(), query, state)
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::App(app) = item else {
+            return ActivateOutcome::Stay;
+        };
+        launch_app(app, std::slice::from_ref(&self.file), state).into()
+    }
+}
+
+/// `>command`, or `>command;` to run it in a terminal.
+struct Run;
+
+impl Provider for Run {
+    fn prefix(&self) -> Option<&str> {
+        Some(crate::RUN_PREFIX)
+    }
+
+    fn query(&self, _state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        let Some((command, in_terminal)) = parse_run_command(query) else {
+            return Vec::new();
+        };
+        if command.is_empty() {
+            return vec![ResultItem::Header("Type a command to run".to_string())];
+        }
+        vec![ResultItem::RunCommand {
+            command: command.to_string(),
+            in_terminal,
+        }]
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::RunCommand {
+            command,
+            in_terminal,
+        } = item
+        else {
+            return ActivateOutcome::Stay;
+        };
+        run_command(command, *in_terminal, state).into()
+    }
+
+    /// The command in a terminal, with or without the `;`.
+    fn activate_secondary(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> Option<ActivateOutcome> {
+        let ResultItem::RunCommand { command, .. } = item else {
+            return None;
+        };
+        Some(run_command(command, true, state).into())
+    }
+}
+
+/// `=expression`, with nothing else alongside the result.
+struct Calc;
+
+impl Provider for Calc {
+    fn prefix(&self) -> Option<&str> {
+        Some(calc::PREFIX)
+    }
+
+    fn query(&self, _state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        calc::expression(query)
+            .map(|expression| ResultItem::Calc {
+                expression: expression.to_string(),
+                value: calc::evaluate(expression),
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        _state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::Calc { value, .. } = item else {
+            return ActivateOutcome::Stay;
+        };
+        copy_value(*value)
+    }
+}
+
+/// Completions of a path typed from `/` or `~/`.
+struct Files(&'static str);
+
+impl Provider for Files {
+    fn prefix(&self) -> Option<&str> {
+        Some(self.0)
+    }
+
+    fn query(&self, _state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        files::complete(query)
+            .into_iter()
+            .map(ResultItem::File)
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::File(file) = item else {
+            return ActivateOutcome::Stay;
+        };
+        open_file(file, state).into()
+    }
+}
+
+/// Files anywhere, by name, from the locate database.
+struct Locate;
+
+impl Provider for Locate {
+    fn prefix(&self) -> Option<&str> {
+        Some(locate::PREFIX)
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, refresh: &Refresh) -> Vec<ResultItem> {
+        let name = filter(query, locate::PREFIX).trim();
+        if name.is_empty() {
+            state.file_search.replace(None);
+            return vec![ResultItem::Header("Type part of a file name".to_string())];
+        }
+        if let Some(search) = &*state.file_search.borrow()
+            && search.name == name
+        {
+            if let Some(err) = &search.error {
+                return vec![ResultItem::Header(err.clone())];
+            }
+            if search.found.is_empty() {
+                let status = if search.done {
+                    "No files found"
+                } else {
+                    "Searching…"
+                };
+                return vec![ResultItem::Header(status.to_string())];
+            }
+            return search.found.iter().cloned().map(ResultItem::File).collect();
+        }
+        locate::search_later(
+            &state.file_search,
+            &state.locate_command,
+            name.to_string(),
+            Rc::clone(refresh),
+        );
+        vec![ResultItem::Header("Searching…".to_string())]
+    }
+
+    fn cancel(&self, state: &LauncherState) {
+        // Kills the locate process, if it is still running.
+        state.file_search.replace(None);
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::File(file) = item else {
+            return ActivateOutcome::Stay;
+        };
+        open_file(file, state).into()
+    }
+}
+
+/// Hosts from the SSH config, connected to in a terminal.
+struct Ssh;
+
+impl Provider for Ssh {
+    fn prefix(&self) -> Option<&str> {
+        Some(ssh::PREFIX)
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        ssh::search(&state.ssh_hosts, filter(query, ssh::PREFIX))
+            .into_iter()
+            .map(|host| ResultItem::SshHost(host.clone()))
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::SshHost(host) = item else {
+            return ActivateOutcome::Stay;
+        };
+        run_command(&ssh::command(host), true, state).into()
+    }
+
+    /// Already in a terminal, so the same as Enter.
+    fn activate_secondary(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        error_bar: &ErrorBar,
+    ) -> Option<ActivateOutcome> {
+        Some(self.activate(state, item, listbox, error_bar))
+    }
+}
+
+/// systemd user services, listed the first time the mode is used.
+struct Units;
+
+impl Provider for Units {
+    fn prefix(&self) -> Option<&str> {
+        Some(systemd::PREFIX)
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        let mut units = state.units.borrow_mut();
+        match units.get_or_insert_with(systemd::list) {
+            Ok(units) => systemd::search(units, filter(query, systemd::PREFIX))
+                .into_iter()
+                .map(|unit| ResultItem::Unit(unit.clone()))
+                .collect(),
+            Err(err) => vec![ResultItem::Header(format!(
+                "systemd user services unavailable: {}",
+                err
+            ))],
+        }
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::Unit(unit) = item else {
+            return ActivateOutcome::Stay;
+        };
+        let verb = if unit.is_active() { "stop" } else { "start" };
+        control_unit(verb, unit.name.clone(), state, listbox, error_bar);
+        ActivateOutcome::Stay
+    }
+
+    /// Restarts the service.
+    fn activate_secondary(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        error_bar: &ErrorBar,
+    ) -> Option<ActivateOutcome> {
+        let ResultItem::Unit(unit) = item else {
+            return None;
+        };
+        control_unit("restart", unit.name.clone(), state, listbox, error_bar);
+        Some(ActivateOutcome::Stay)
+    }
+}
+
+/// Running processes, sent SIGTERM when activated, or SIGKILL on
+/// Shift+Enter.
+struct Processes;
+
+impl Provider for Processes {
+    fn prefix(&self) -> Option<&str> {
+        Some(procs::PREFIX)
+    }
+
+    fn query(&self, _state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        procs::search(procs::list(), filter(query, procs::PREFIX))
+            .into_iter()
+            .map(|process| ResultItem::Process {
+                process,
+                confirming: false,
+            })
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::Process {
+            process,
+            confirming,
+        } = item
+        else {
+            return ActivateOutcome::Stay;
+        };
+        kill_process(
+            process.clone(),
+            libc::SIGTERM,
+            *confirming,
+            state,
+            listbox,
+            error_bar,
+        );
+        ActivateOutcome::Stay
+    }
+
+    fn activate_secondary(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        error_bar: &ErrorBar,
+    ) -> Option<ActivateOutcome> {
+        let ResultItem::Process {
+            process,
+            confirming,
+        } = item
+        else {
+            return None;
+        };
+        kill_process(
+            process.clone(),
+            libc::SIGKILL,
+            *confirming,
+            state,
+            listbox,
+            error_bar,
+        );
+        Some(ActivateOutcome::Stay)
+    }
+}
+
+/// tmux sessions and windows, the most used first.
+struct Tmux;
+
+impl Provider for Tmux {
+    fn prefix(&self) -> Option<&str> {
+        Some(tmux::PREFIX)
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        let Some(targets) = tmux::list() else {
+            return vec![ResultItem::Header("No tmux server running".to_string())];
+        };
+        let usage = state.usage.borrow();
+        let now = state.now();
+        let usage_boost = |target: &tmux::Target| {
+            usage.get(&target.usage_key()).map_or(0, |entry| {
+                usage_score(entry, now, state.ranking.recency_window_secs)
+            })
+        };
+        tmux::search(targets, filter(query, tmux::PREFIX), usage_boost)
+            .into_iter()
+            .map(ResultItem::Tmux)
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::Tmux(target) = item else {
+            return ActivateOutcome::Stay;
+        };
+        open_tmux(target, state).into()
+    }
+}
+
+/// The emoji picker, which also holds the `:snooze` command.
+struct Emoji;
+
+impl Provider for Emoji {
+    fn prefix(&self) -> Option<&str> {
+        Some(emoji::PREFIX)
+    }
+
+    fn query(&self, _state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        if let Some(minutes) = snooze::parse_command(query) {
+            return vec![ResultItem::Header(snooze::command_hint(minutes))];
+        }
+        if emoji::EMOJI.is_empty() {
+            return vec![ResultItem::Header("No emoji in this build".to_string())];
+        }
+        emoji::search(filter(query, emoji::PREFIX).trim())
+            .into_iter()
+            .map(ResultItem::Emoji)
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::Emoji(emoji) = item else {
+            return ActivateOutcome::Stay;
+        };
+        clipboard::copy(emoji.emoji);
+        if let Some(command) = &state.emoji_type_command {
+            emoji::type_later(command, emoji.emoji);
+        }
+        ActivateOutcome::Quit
+    }
+}
+
+/// Characters from the symbol table, by name.
+struct Symbols;
+
+impl Provider for Symbols {
+    fn prefix(&self) -> Option<&str> {
+        Some(symbols::PREFIX)
+    }
+
+    fn query(&self, _state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        symbols::search(filter(query, symbols::PREFIX).trim())
+            .into_iter()
+            .map(ResultItem::Symbol)
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        _state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::Symbol(symbol) = item else {
+            return ActivateOutcome::Stay;
+        };
+        clipboard::copy(&symbol.symbol.to_string());
+        ActivateOutcome::Quit
+    }
+}
+
+/// Text from the `[snippets]` config table, by name.
+struct Snippets;
+
+impl Provider for Snippets {
+    fn prefix(&self) -> Option<&str> {
+        Some(snippets::PREFIX)
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        if state.snippets.is_empty() {
+            return vec![ResultItem::Header(
+                "No snippets yet; add them under [snippets] in config.toml".to_string(),
+            )];
+        }
+        snippets::search(&state.snippets, filter(query, snippets::PREFIX).trim())
+            .into_iter()
+            .map(|snippet| ResultItem::Snippet(snippet.clone()))
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        _state: &LauncherState,
+        item: &ResultItem,
+        _listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::Snippet(snippet) = item else {
+            return ActivateOutcome::Stay;
+        };
+        clipboard::copy(&snippets::expand(&snippet.text, now_unix()));
+        ActivateOutcome::Quit
+    }
+}
+
+/// Password-store entries, only registered when the store is turned on.
+struct Pass;
+
+impl Provider for Pass {
+    fn prefix(&self) -> Option<&str> {
+        Some(pass::PREFIX)
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        let entries = state.pass_entries.as_deref().map_or(&[][..], Vec::as_slice);
+        pass::search(entries, filter(query, pass::PREFIX))
+            .into_iter()
+            .map(|entry| ResultItem::PassEntry(entry.clone()))
+            .collect()
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        let ResultItem::PassEntry(entry) = item else {
+            return ActivateOutcome::Stay;
+        };
+        copy_password(entry.clone(), state, listbox);
+        ActivateOutcome::Stay
+    }
+}
+
+/// Text copied while the daemon runs, only registered when the history
+/// is turned on. `clip clear` offers to empty it.
+struct Clipboard;
+
+impl Provider for Clipboard {
+    fn prefix(&self) -> Option<&str> {
+        Some(clipboard::PREFIX)
+    }
+
+    fn query(&self, state: &LauncherState, query: &str, _refresh: &Refresh) -> Vec<ResultItem> {
+        let history = state.clipboard.borrow();
+        if !history.watching() {
+            return vec![ResultItem::Header(
+                "The clipboard history is only kept by hyperfind --daemon".to_string(),
+            )];
+        }
+        if history.entries().is_empty() {
+            return vec![ResultItem::Header("Nothing copied yet".to_string())];
+        }
+        let filter = filter(query, clipboard::PREFIX).trim();
+        let mut items: Vec<ResultItem> = clipboard::search(history.entries(), filter)
+            .into_iter()
+            .map(|entry| ResultItem::ClipboardEntry(entry.clone()))
+            .collect();
+        if filter == clipboard::CLEAR {
+            items.insert(0, ResultItem::ClearClipboard);
+        }
+        items
+    }
+
+    fn activate(
+        &self,
+        state: &LauncherState,
+        item: &ResultItem,
+        listbox: &ListBox,
+        _error_bar: &ErrorBar,
+    ) -> ActivateOutcome {
+        match item {
+            ResultItem::ClipboardEntry(text) => {
+                clipboard::copy(text);
+                ActivateOutcome::Quit
+            }
+            ResultItem::ClearClipboard => {
+                clear_clipboard_history(state, listbox);
+                ActivateOutcome::Stay
+            }
+            _ => ActivateOutcome::Stay,
+        }
+    }
+}
+
+/// Apps that can open a file, in their own order while the query is empty
+/// and ranked like a search otherwise.
+fn handler_items(apps: &[AppEntry], query: &str, state: &LauncherState) -> Vec<ResultItem> {
+    if query.trim().is_empty() {
+        return apps.iter().cloned().map(ResultItem::App).collect();
+    }
+    let mut scored = score_apps(
+        apps,
+        &QueryMatcher::Fuzzy(query),
+        &state.usage.borrow(),
+        &state.ranking,
+        state.now(),
+    );
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    scored
+        .into_iter()
+        .map(|(_, app)| ResultItem::App(app.clone()))
+        .collect()
+}
+
+/// Opens a file or directory with the default handler for its type.
+fn open_file(file: &files::FileMatch, state: &LauncherState) -> Result<(), LaunchError> {
+    open_uri(&gio::File::for_path(&file.path).uri(), &file.name, state)
+}
+
+/// Opens a recent file and counts it like a launch.
+fn open_recent(file: &recent::RecentFile, state: &LauncherState) -> Result<(), LaunchError> {
+    open_uri(&file.uri, &file.name, state)?;
+    count_use(&file.usage_key(), state);
+    Ok(())
+}
+
+fn open_bookmark(bookmark: &bookmarks::Bookmark, state: &LauncherState) -> Result<(), LaunchError> {
+    open_uri(&bookmark.url, &bookmark.title, state)?;
+    count_use(&bookmark.usage_key(), state);
+    Ok(())
+}
+
+/// Opens a typed URL, counting every one under `url::USAGE_KEY`.
+fn open_url(url: &str, state: &LauncherState) -> Result<(), LaunchError> {
+    open_uri(url, url, state)?;
+    count_use(url::USAGE_KEY, state);
+    Ok(())
+}
+
+/// Runs the project command on a project's directory.
+fn open_project(project: &projects::Project, state: &LauncherState) -> Result<(), LaunchError> {
+    let command = projects::command_line(&state.project_command, &project.path);
+    run_command(&command, false, state)?;
+    count_use(&project.usage_key(), state);
+    Ok(())
+}
+
+/// Switches the current tmux client to `target` when hyperfind runs inside
+/// tmux, and otherwise attaches to it in a new terminal.
+fn open_tmux(target: &tmux::Target, state: &LauncherState) -> Result<(), LaunchError> {
+    let quoted = glib::shell_quote(target.target());
+    let quoted = quoted.to_string_lossy();
+    if tmux::inside() {
+        run_command(&format!("tmux switch-client -t {}", quoted), false, state)?;
+    } else {
+        run_command(&format!("tmux attach -t {}", quoted), true, state)?;
+    }
+    count_use(&target.usage_key(), state);
+    Ok(())
+}
+
+/// How long "Copied the password" shows before hyperfind quits.
+const PASS_CONFIRM_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// Copies a password-store entry's password with `pass show -c` off the
+/// main thread, since gpg may ask for a passphrase. Says so and quits on
+/// success; a failure, such as a cancelled pinentry, becomes a row at the
+/// top of the results. The clipboard history isn't recorded meanwhile,
+/// nor for a moment after, while the change is still on its way in.
+fn copy_password(entry: String, state: &LauncherState, listbox: &ListBox) {
+    if state.launching.replace(true) {
+        return;
+    }
+    if state.options.demo {
+        println!("Would copy the password for {}", entry);
+        quit_later(Duration::ZERO);
+        return;
+    }
+
+    state.clipboard.borrow_mut().pause();
+    let state = state.clone();
+    let listbox = listbox.clone();
+    glib::MainContext::default().spawn_local(async move {
+        let entry_for_copy = entry.clone();
+        let copied = gio::spawn_blocking(move || pass::copy(&entry_for_copy))
+            .await
+            .unwrap_or_else(|_| Err("pass panicked".to_string()));
+        let clipboard = Rc::clone(&state.clipboard);
+        glib::timeout_add_local_once(PASS_CONFIRM_TIMEOUT, move || {
+            clipboard.borrow_mut().resume();
+        });
+        let message = match copied {
+            Ok(()) => {
+                quit_later(PASS_CONFIRM_TIMEOUT);
+                format!("Copied the password for {}", entry)
+            }
+            Err(err) => {
+                state.launching.set(false);
+                format!("Couldn't copy the password for {}: {}", entry, err)
+            }
+        };
+        let mut view_state = state.view.borrow_mut();
+        view_state.items.insert(0, ResultItem::Header(message));
+        view_state.selected_index = view_state.selected_index.map(|index| index + 1);
+        rerender(&listbox, &state, &view_state);
+    });
+}
+
+/// The process list is read again this long after a kill, giving the
+/// processes a moment to exit.
+const KILL_REFRESH_DELAY: Duration = Duration::from_millis(200);
+
+/// Sends `signal` to every pid of the process, asking first if any of
+/// them is another user's, then lists the processes again to show what
+/// is left.
+fn kill_process(
+    process: procs::Process,
+    signal: i32,
+    confirming: bool,
+    state: &LauncherState,
+    listbox: &ListBox,
+    error_bar: &ErrorBar,
+) {
+    if process.foreign && !confirming {
+        let mut view_state = state.view.borrow_mut();
+        let index = view_state.items.iter().position(
+            |item| matches!(item, ResultItem::Process { process: other, .. } if other.name == process.name),
+        );
+        if let Some(index) = index {
+            view_state.items[index] = ResultItem::Process {
+                process,
+                confirming: true,
+            };
+            view_state.selected_index = Some(index);
+            rerender(listbox, state, &view_state);
+        }
+        return;
+    }
+
+    if state.options.demo {
+        println!("Would send signal {} to {:?}", signal, process.pids);
+        return;
+    }
+    if let Err(message) = procs::signal(&process.pids, signal) {
+        show_error(
+            error_bar,
+            &format!("Couldn't kill {}: {}", process.name, message),
+        );
+    }
+
+    let state = state.clone();
+    let listbox = listbox.clone();
+    glib::timeout_add_local_once(KILL_REFRESH_DELAY, move || {
+        let running = procs::list();
+        let mut view_state = state.view.borrow_mut();
+        let selected_name =
+            view_state
+                .selected_index
+                .and_then(|index| match &view_state.items[index] {
+                    ResultItem::Process { process, .. } => Some(process.name.clone()),
+                    _ => None,
+                });
+        view_state.items = std::mem::take(&mut view_state.items)
+            .into_iter()
+            .filter_map(|item| match item {
+                ResultItem::Process { process, .. } => running
+                    .iter()
+                    .find(|other| other.name == process.name)
+                    .map(|updated| ResultItem::Process {
+                        process: updated.clone(),
+                        confirming: false,
+                    }),
+                item => Some(item),
+            })
+            .collect();
+        view_state.selected_index = selected_name
+            .and_then(|name| {
+                view_state.items.iter().position(
+                    |item| matches!(item, ResultItem::Process { process, .. } if process.name == name),
+                )
+            })
+            .or_else(|| first_selectable_index(&view_state.items));
+        rerender(&listbox, &state, &view_state);
+    });
+}
+
+/// Runs `systemctl --user <verb>` on a service off the main thread, then
+/// reloads the services and redraws the listed ones in place.
+fn control_unit(
+    verb: &'static str,
+    name: String,
+    state: &LauncherState,
+    listbox: &ListBox,
+    error_bar: &ErrorBar,
+) {
+    if state.options.demo {
+        println!("Would {} {}", verb, name);
+        return;
+    }
+
+    let state = state.clone();
+    let listbox = listbox.clone();
+    let error_bar = error_bar.clone();
+    glib::MainContext::default().spawn_local(async move {
+        let name_for_control = name.clone();
+        let controlled = gio::spawn_blocking(move || systemd::control(verb, &name_for_control))
+            .await
+            .unwrap_or_else(|_| Err("systemctl panicked".to_string()));
+        if let Err(message) = controlled {
+            show_error(
+                &error_bar,
+                &format!("Couldn't {} {}: {}", verb, name, message),
+            );
+        }
+        let units = gio::spawn_blocking(systemd::list)
+            .await
+            .unwrap_or_else(|_| Err("systemctl panicked".to_string()));
+
+        let mut view_state = state.view.borrow_mut();
+        if let Ok(units) = &units {
+            for item in &mut view_state.items {
+                if let ResultItem::Unit(unit) = item
+                    && let Some(updated) = units.iter().find(|other| other.name == unit.name)
+                {
+                    *unit = updated.clone();
+                }
+            }
+        }
+        state.units.replace(Some(units));
+        rerender(&listbox, &state, &view_state);
+    });
+}
+
+/// Runs a power action, or for a destructive one not yet confirmed, swaps
+/// its row for the confirmation question and waits for another Enter.
+fn activate_power(
+    action: &'static power::Action,
+    confirming: bool,
+    state: &LauncherState,
+    listbox: &ListBox,
+    error_bar: &ErrorBar,
+) -> bool {
+    if action.confirm.is_some() && !confirming {
+        let mut view_state = state.view.borrow_mut();
+        let index = view_state.items.iter().position(
+            |item| matches!(item, ResultItem::Power { action: other, .. } if other.id == action.id),
+        );
+        if let Some(index) = index {
+            view_state.items[index] = ResultItem::Power {
+                action,
+                confirming: true,
+            };
+            view_state.selected_index = Some(index);
+            rerender(listbox, state, &view_state);
+        }
+        return false;
+    }
+
+    if state.launching.replace(true) {
+        return true;
+    }
+    let command = state
+        .power_commands
+        .get(action.id)
+        .map_or(action.command, String::as_str);
+    if state.options.demo {
+        println!("Would run {}", command);
+        return true;
+    }
+    if let Err(err) = glib::spawn_command_line_async(command) {
+        eprintln!("Failed to run {}: {}", command, err);
+        state.launching.set(false);
+        show_error(error_bar, &format!("Couldn't {}: {}", action.name, err));
+        return false;
+    }
+    count_use(&action.usage_key(), state);
+    true
+}
+
+fn clear_clipboard_history(state: &LauncherState, listbox: &ListBox) {
+    state.clipboard.borrow_mut().clear();
+    let mut view_state = state.view.borrow_mut();
+    view_state.items = vec![ResultItem::Header("Clipboard history cleared".to_string())];
+    view_state.selected_index = None;
+    rerender(listbox, state, &view_state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `registry` with every optional provider turned on.
+    fn providers() -> Vec<Box<dyn Provider>> {
+        vec![
+            Box::new(Run),
+            Box::new(Calc),
+            Box::new(Files(files::ROOT_PREFIX)),
+            Box::new(Files(files::HOME_PREFIX)),
+            Box::new(Locate),
+            Box::new(Ssh),
+            Box::new(Units),
+            Box::new(Processes),
+            Box::new(Tmux),
+            Box::new(Symbols),
+            Box::new(Snippets),
+            Box::new(Emoji),
+            Box::new(Pass),
+            Box::new(Clipboard),
+            Box::new(Apps),
+        ]
+    }
+
+    #[test]
+    fn each_prefix_routes_to_its_provider() {
+        let providers = providers();
+        for (index, provider) in providers.iter().enumerate() {
+            let Some(prefix) = provider.prefix() else {
+                continue;
+            };
+            let query = format!("  {}query", prefix);
+            assert_eq!(route(&providers, &query), index, "{:?}", query);
+        }
+    }
+
+    #[test]
+    fn other_queries_route_to_the_app_search() {
+        let providers = providers();
+        let apps = providers.len() - 1;
+        assert_eq!(route(&providers, "firefox"), apps);
+        assert_eq!(route(&providers, ""), apps);
+        // A prefix needs its trailing space to count.
+        assert_eq!(route(&providers, "sshfs"), apps);
+    }
+
+    #[test]
+    fn filter_is_what_follows_the_prefix() {
+        assert_eq!(filter("  ssh host", ssh::PREFIX), "host");
+        assert_eq!(filter("ssh", ssh::PREFIX), "");
+    }
+
+    #[test]
+    fn a_launch_already_under_way_leaves_the_launcher_alone() {
+        assert!(matches!(
+            ActivateOutcome::from(Err(LaunchError::Cancelled)),
+            ActivateOutcome::Stay
+        ));
+        assert!(matches!(
+            ActivateOutcome::from(Ok(())),
+            ActivateOutcome::Quit
+        ));
+        let failed = ActivateOutcome::from(Err(LaunchError::Spawn {
+            verb: "launch",
+            name: "Files".to_string(),
+            reason: "no such file".to_string(),
+        }));
+        assert!(matches!(
+            failed,
+            ActivateOutcome::Failed(message) if message == "Couldn't launch Files: no such file"
+        ));
+    }
+}
@@ -0,0 +1,49 @@
+use gtk::prelude::*;
+use std::collections::HashSet;
+
+/// Only the most recently used files are searched; GTK keeps years of
+/// history in `recently-used.xbel`.
+const MAX_INDEXED: usize = 200;
+
+#[derive(Clone)]
+pub struct RecentFile {
+    pub uri: String,
+    pub name: String,
+    pub icon: Option<gio::Icon>,
+}
+
+impl RecentFile {
+    /// Launches of a recent file are recorded under `file:<uri>`.
+    pub fn usage_key(&self) -> String {
+        format!("file:{}", self.uri)
+    }
+}
+
+/// Recently used files that still exist, newest first, each URI once.
+pub fn load() -> Vec<RecentFile> {
+    let Some(manager) = gtk::RecentManager::default() else {
+        return Vec::new();
+    };
+
+    let mut items = manager.items();
+    items.sort_by_key(|item| std::cmp::Reverse(item.modified()));
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| item.exists())
+        .filter_map(|item| {
+            let uri = item.uri()?.to_string();
+            if !seen.insert(uri.clone()) {
+                return None;
+            }
+            Some(RecentFile {
+                name: item
+                    .display_name()
+                    .map_or_else(|| uri.clone(), |name| name.to_string()),
+                icon: item.gicon(),
+                uri,
+            })
+        })
+        .take(MAX_INDEXED)
+        .collect()
+}
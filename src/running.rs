@@ -0,0 +1,72 @@
+use gio::prelude::*;
+use gtk::gdk;
+
+/// Programs whose name says nothing about the app they start.
+const WRAPPERS: &[&str] = &["env", "flatpak", "snap", "sh", "bash"];
+
+/// Another client's top-level window, with the names from its `WM_CLASS`
+/// lowercased.
+pub struct Window {
+    window: gdk::Window,
+    classes: Vec<String>,
+}
+
+/// The windows the window manager lists in `_NET_CLIENT_LIST_STACKING`.
+/// Empty off X11, where clients can't see each other's windows.
+pub fn list() -> Vec<Window> {
+    let Some(screen) = gdk::Screen::default() else {
+        return Vec::new();
+    };
+    if !screen.display().type_().name().starts_with("GdkX11") {
+        return Vec::new();
+    }
+    let wm_class = gdk::Atom::intern("WM_CLASS");
+    let string = gdk::Atom::intern("STRING");
+    screen
+        .window_stack()
+        .into_iter()
+        .filter_map(|window| {
+            let (_, _, data) = gdk::property_get(&window, &wm_class, &string, 0, 1024, 0)?;
+            // The instance name and the class name, each ending in a NUL.
+            let classes = data
+                .split(|byte| *byte == 0)
+                .filter(|name| !name.is_empty())
+                .map(|name| String::from_utf8_lossy(name).to_lowercase())
+                .collect();
+            Some(Window { window, classes })
+        })
+        .collect()
+}
+
+/// What the app's windows may be called: its entry's `StartupWMClass`,
+/// its desktop id and the id's last dotted part, and its program's name.
+fn names(app: &gio::AppInfo, key: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(desktop) = app.downcast_ref::<gio::DesktopAppInfo>()
+        && let Some(class) = desktop.startup_wm_class()
+    {
+        names.push(class.to_lowercase());
+    }
+    let id = key.strip_suffix(".desktop").unwrap_or(key).to_lowercase();
+    if let Some((_, last)) = id.rsplit_once('.') {
+        names.push(last.to_string());
+    }
+    names.push(id);
+    if let Some(program) = app.executable().file_name() {
+        let program = program.to_string_lossy().to_lowercase();
+        if !WRAPPERS.contains(&program.as_str()) {
+            names.push(program);
+        }
+    }
+    names
+}
+
+/// The topmost of the app's open windows.
+pub fn find<'a>(windows: &'a [Window], app: &gio::AppInfo, key: &str) -> Option<&'a gdk::Window> {
+    let names = names(app, key);
+    windows
+        .iter()
+        .rev()
+        .find(|window| window.classes.iter().any(|class| names.contains(class)))
+        .map(|window| &window.window)
+}
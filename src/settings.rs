@@ -0,0 +1,213 @@
+use gtk::glib;
+use std::process::Command;
+
+/// One page of the desktop's settings app.
+pub struct Panel {
+    pub id: String,
+    pub name: String,
+    /// Other words the panel can be found by.
+    pub keywords: Vec<String>,
+    pub command: String,
+    /// Themed icon names, best first.
+    pub icons: Vec<String>,
+}
+
+impl Panel {
+    /// Launches are recorded under `settings:<id>`.
+    pub fn usage_key(&self) -> String {
+        format!("settings:{}", self.id)
+    }
+}
+
+/// Names GNOME's own panel ids don't spell out.
+const GNOME_NAMES: &[(&str, &str)] = &[
+    ("wifi", "Wi-Fi"),
+    ("wwan", "Mobile Network"),
+    ("datetime", "Date & Time"),
+    ("display", "Displays"),
+    ("info-overview", "About"),
+    ("universal-access", "Accessibility"),
+    ("region", "Region & Language"),
+    ("default-apps", "Default Apps"),
+    ("removable-media", "Removable Media"),
+];
+
+/// `online-accounts` as `Online Accounts`.
+fn title_case(id: &str) -> String {
+    id.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `gnome-control-center --list`: a header line, then one indented panel
+/// id per line.
+pub fn parse_gnome(output: &str) -> Vec<Panel> {
+    output
+        .lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            let name = GNOME_NAMES
+                .iter()
+                .find(|(known, _)| *known == id)
+                .map_or_else(|| title_case(id), |(_, name)| name.to_string());
+            Panel {
+                id: id.to_string(),
+                name,
+                keywords: vec![id.to_string(), "settings".to_string()],
+                command: format!("gnome-control-center {}", id),
+                icons: vec![
+                    format!("org.gnome.Settings-{}-symbolic", id),
+                    "preferences-system".to_string(),
+                ],
+            }
+        })
+        .collect()
+}
+
+/// `kcmshell6 --list` (or `kcmshell5`): a header line, then
+/// `module - description` per line. The description names the row.
+pub fn parse_kde(output: &str, kcmshell: &str) -> Vec<Panel> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (id, description) = line.split_once(" - ")?;
+            let id = id.trim();
+            let description = description.trim();
+            if id.is_empty() || id.contains(char::is_whitespace) {
+                return None;
+            }
+            let short = id.strip_prefix("kcm_").unwrap_or(id);
+            Some(Panel {
+                id: id.to_string(),
+                name: if description.is_empty() {
+                    title_case(short)
+                } else {
+                    description.to_string()
+                },
+                keywords: vec![short.to_string(), "settings".to_string()],
+                command: format!("{} {}", kcmshell, id),
+                icons: vec!["preferences-system".to_string()],
+            })
+        })
+        .collect()
+}
+
+fn run(program: &str) -> Option<String> {
+    glib::find_program_in_path(program)?;
+    let output = Command::new(program).arg("--list").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The panels of whichever settings app belongs to the running desktop.
+/// Blocks on a subprocess, so call it off the main thread.
+pub fn list(desktops: &[String]) -> Vec<Panel> {
+    let running = |name: &str| desktops.iter().any(|desktop| desktop == name);
+    let mut panels = Vec::new();
+    if running("GNOME")
+        && let Some(output) = run("gnome-control-center")
+    {
+        panels.extend(parse_gnome(&output));
+    }
+    if running("KDE") {
+        let kcmshell = ["kcmshell6", "kcmshell5"]
+            .into_iter()
+            .find_map(|program| Some((program, run(program)?)));
+        if let Some((program, output)) = kcmshell {
+            panels.extend(parse_kde(&output, program));
+        }
+    }
+    panels
+}
+
+/// Programs whose desktop entries already open one panel, e.g.
+/// `gnome-control-center bluetooth` from gnome-bluetooth-panel.desktop.
+const SETTINGS_PROGRAMS: &[&str] = &[
+    "gnome-control-center",
+    "systemsettings",
+    "kcmshell6",
+    "kcmshell5",
+];
+
+/// The panel a command line opens, if it runs a settings app with one:
+/// `bluetooth` for `gnome-control-center bluetooth`.
+pub fn panel_of(commandline: &str) -> Option<&str> {
+    let mut words = commandline.split_whitespace();
+    let program = words.next()?;
+    let program = program.rsplit('/').next().unwrap_or(program);
+    if !SETTINGS_PROGRAMS.contains(&program) {
+        return None;
+    }
+    words.find(|word| !word.starts_with(['-', '%']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gnome_panels_are_the_indented_lines() {
+        let output = "\
+Available panels:
+\tbackground
+\twifi
+\tonline-accounts
+
+";
+        let panels = parse_gnome(output);
+        let names: Vec<&str> = panels.iter().map(|panel| panel.name.as_str()).collect();
+        assert_eq!(names, ["Background", "Wi-Fi", "Online Accounts"]);
+        assert_eq!(panels[1].command, "gnome-control-center wifi");
+        assert_eq!(panels[1].usage_key(), "settings:wifi");
+        assert!(panels[2].keywords.contains(&"online-accounts".to_string()));
+    }
+
+    #[test]
+    fn kde_modules_are_named_by_their_description() {
+        let output = concat!(
+            "The following modules are available:\n",
+            "kcm_bluetooth - Configure Bluetooth devices\n",
+            "kcm_networkmanagement - \n",
+            "not a module line\n",
+            "kcm_kscreen - Manage and configure monitors and displays\n",
+        );
+        let panels = parse_kde(output, "kcmshell6");
+        let names: Vec<&str> = panels.iter().map(|panel| panel.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "Configure Bluetooth devices",
+                "Networkmanagement",
+                "Manage and configure monitors and displays"
+            ]
+        );
+        assert_eq!(panels[0].command, "kcmshell6 kcm_bluetooth");
+        assert_eq!(panels[0].keywords[0], "bluetooth");
+    }
+
+    #[test]
+    fn panel_of_finds_the_panel_a_command_opens() {
+        assert_eq!(
+            panel_of("gnome-control-center bluetooth"),
+            Some("bluetooth")
+        );
+        assert_eq!(
+            panel_of("/usr/bin/gnome-control-center --verbose wifi %U"),
+            Some("wifi")
+        );
+        assert_eq!(panel_of("kcmshell6 kcm_kscreen"), Some("kcm_kscreen"));
+        assert_eq!(panel_of("gnome-control-center"), None);
+        assert_eq!(panel_of("firefox bluetooth"), None);
+    }
+}
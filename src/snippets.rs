@@ -0,0 +1,107 @@
+use crate::matching;
+use gtk::glib;
+use std::collections::BTreeMap;
+
+/// Queries starting with this list the configured snippets.
+pub const PREFIX: &str = "snip ";
+
+/// A named piece of text from the `[snippets]` config table.
+#[derive(Clone)]
+pub struct Snippet {
+    pub name: String,
+    /// Copied with its placeholders filled in.
+    pub text: String,
+}
+
+/// The configured snippets by name, leaving out empty ones.
+pub fn load(configured: BTreeMap<String, String>) -> Vec<Snippet> {
+    configured
+        .into_iter()
+        .filter_map(|(name, text)| {
+            if text.is_empty() {
+                eprintln!("Ignoring snippet '{}': its text is empty", name);
+                return None;
+            }
+            Some(Snippet { name, text })
+        })
+        .collect()
+}
+
+/// Snippets whose name matches `filter` best first, or all of them by
+/// name while it's empty.
+pub fn search<'a>(snippets: &'a [Snippet], filter: &str) -> Vec<&'a Snippet> {
+    let mut scored: Vec<(i64, &Snippet)> = snippets
+        .iter()
+        .filter_map(|snippet| Some((matching::score(&snippet.name, filter)?, snippet)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    scored.into_iter().map(|(_, snippet)| snippet).collect()
+}
+
+/// `text` with `{date}` replaced by the local date at `now` (`YYYY-MM-DD`)
+/// and `{time}` by the local time (`HH:MM`). Other braces are left alone.
+pub fn expand(text: &str, now: u64) -> String {
+    let format = |format: &str| {
+        glib::DateTime::from_unix_local(now as i64)
+            .and_then(|time| time.format(format))
+            .map(|formatted| formatted.to_string())
+            .unwrap_or_default()
+    };
+    text.replace("{date}", &format("%F"))
+        .replace("{time}", &format("%H:%M"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn multi_line_snippets_load_from_the_config() {
+        let config: Config = toml::from_str(
+            r#"
+[snippets]
+signature = """
+Best,
+Jake"""
+"empty one" = ""
+email = "me@example.com"
+"#,
+        )
+        .unwrap();
+        let snippets = load(config.snippets);
+        let names: Vec<&str> = snippets
+            .iter()
+            .map(|snippet| snippet.name.as_str())
+            .collect();
+        assert_eq!(names, ["email", "signature"]);
+        assert_eq!(snippets[1].text, "Best,\nJake");
+    }
+
+    #[test]
+    fn placeholders_expand_to_the_local_date_and_time() {
+        let now = glib::DateTime::from_local(2026, 3, 7, 9, 5, 0.0)
+            .unwrap()
+            .to_unix() as u64;
+        assert_eq!(
+            expand("Sent {date} at {time}, {other} {", now),
+            "Sent 2026-03-07 at 09:05, {other} {"
+        );
+    }
+
+    #[test]
+    fn an_empty_search_lists_snippets_by_name() {
+        let snippets = load(BTreeMap::from([
+            ("zip".to_string(), "90210".to_string()),
+            ("address".to_string(), "1 Main St".to_string()),
+        ]));
+        let names = |filter| -> Vec<String> {
+            search(&snippets, filter)
+                .iter()
+                .map(|snippet| snippet.name.clone())
+                .collect()
+        };
+        assert_eq!(names(""), ["address", "zip"]);
+        assert_eq!(names("zip"), ["zip"]);
+    }
+}
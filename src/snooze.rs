@@ -0,0 +1,66 @@
+use crate::profile;
+use gtk::glib;
+use std::fs;
+use std::path::PathBuf;
+
+/// Typing `:snooze` hides usage for this long; `:snooze N` for N minutes
+/// and `:snooze 0` ends it early.
+pub const DEFAULT_MINUTES: u64 = 60;
+
+pub const PREFIX: &str = ":snooze";
+
+/// Lives in the runtime dir so a snooze survives restarting hyperfind but
+/// not logging out.
+fn snooze_path() -> PathBuf {
+    profile::dir(glib::user_runtime_dir().join("hyperfind")).join("snooze")
+}
+
+/// The snooze deadline, if one is stored and hasn't passed yet.
+pub fn load(now: u64) -> Option<u64> {
+    let contents = fs::read_to_string(snooze_path()).ok()?;
+    let deadline = contents.trim().parse::<u64>().ok()?;
+    (deadline > now).then_some(deadline)
+}
+
+pub fn save(deadline: Option<u64>) {
+    let path = snooze_path();
+    let Some(deadline) = deadline else {
+        let _ = fs::remove_file(&path);
+        return;
+    };
+
+    if let Some(parent) = path.parent()
+        && let Err(err) = fs::create_dir_all(parent)
+    {
+        eprintln!("Failed to create {}: {}", parent.display(), err);
+        return;
+    }
+    if let Err(err) = fs::write(&path, deadline.to_string()) {
+        eprintln!("Failed to write {}: {}", path.display(), err);
+    }
+}
+
+/// The snooze length in minutes a `:snooze` query asks for.
+pub fn parse_command(query: &str) -> Option<u64> {
+    let rest = query.trim().strip_prefix(PREFIX)?;
+    if rest.trim().is_empty() {
+        return Some(DEFAULT_MINUTES);
+    }
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    rest.trim().parse().ok()
+}
+
+pub fn command_hint(minutes: u64) -> String {
+    if minutes == 0 {
+        "Press Enter to show usage again".to_string()
+    } else {
+        format!("Press Enter to hide usage for {} min", minutes)
+    }
+}
+
+pub fn indicator(deadline: u64, now: u64) -> String {
+    let minutes = deadline.saturating_sub(now).div_ceil(60);
+    format!("Usage hidden for {} more min", minutes)
+}
@@ -0,0 +1,156 @@
+use crate::matching;
+use gtk::glib;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Queries starting with this list SSH hosts.
+pub const PREFIX: &str = "ssh ";
+
+fn ssh_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".ssh"))
+}
+
+/// Patterns match many hosts, so they can't be connected to by name.
+fn is_pattern(host: &str) -> bool {
+    host.contains(['*', '?', '!'])
+}
+
+/// Aliases from `Host` lines. Keywords are case-insensitive and may be
+/// followed by `=`; `Include` and everything else is skipped.
+pub fn parse_config(contents: &str) -> Vec<String> {
+    let mut hosts = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((keyword, rest)) = line.split_once(|c: char| c.is_whitespace() || c == '=') else {
+            continue;
+        };
+        if !keyword.eq_ignore_ascii_case("host") {
+            continue;
+        }
+        let rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '=');
+        for host in rest.split_whitespace() {
+            if host.starts_with('#') {
+                break;
+            }
+            if !is_pattern(host) {
+                hosts.push(host.to_string());
+            }
+        }
+    }
+    hosts
+}
+
+/// Host names from `known_hosts`. Hashed entries can't be read back, and
+/// `[host]:port` entries need a port `ssh <host>` wouldn't use, so both
+/// are skipped, as are `@cert-authority` and `@revoked` lines.
+pub fn parse_known_hosts(contents: &str) -> Vec<String> {
+    let mut hosts = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('@') {
+            continue;
+        }
+        let Some(names) = line.split_whitespace().next() else {
+            continue;
+        };
+        for name in names.split(',') {
+            if name.starts_with('|') || name.starts_with('[') || is_pattern(name) {
+                continue;
+            }
+            hosts.push(name.to_string());
+        }
+    }
+    hosts
+}
+
+/// Hosts matching `filter` best first, or all of them while it's empty.
+pub fn search<'a>(hosts: &'a [String], filter: &str) -> Vec<&'a String> {
+    let mut scored: Vec<(i64, &String)> = hosts
+        .iter()
+        .filter_map(|host| Some((matching::score(host, filter)?, host)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    scored.into_iter().map(|(_, host)| host).collect()
+}
+
+/// The command that connects to `host`, run in a terminal.
+pub fn command(host: &str) -> String {
+    format!("ssh {}", glib::shell_quote(host).to_string_lossy())
+}
+
+/// Every host from `~/.ssh/config`, plus `known_hosts` when asked, sorted
+/// and deduplicated. Missing files just contribute nothing.
+pub fn load(known_hosts: bool) -> Vec<String> {
+    let Some(dir) = ssh_dir() else {
+        return Vec::new();
+    };
+
+    let mut hosts = BTreeSet::new();
+    if let Ok(contents) = fs::read_to_string(dir.join("config")) {
+        hosts.extend(parse_config(&contents));
+    }
+    if known_hosts && let Ok(contents) = fs::read_to_string(dir.join("known_hosts")) {
+        hosts.extend(parse_known_hosts(&contents));
+    }
+    hosts.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_lines_give_every_alias_but_patterns() {
+        let config = "\
+# work
+Host prod-db prod-db-replica   # both replicas
+    HostName 10.0.0.5
+host=staging
+Host *.internal !bastion dev?
+HOST  laptop *
+";
+        assert_eq!(
+            parse_config(config),
+            ["prod-db", "prod-db-replica", "staging", "laptop"]
+        );
+    }
+
+    #[test]
+    fn include_and_other_keywords_are_skipped() {
+        let config = "\
+Include ~/.ssh/config.d/*
+Match host prod-*
+    User admin
+Hostname example.com
+";
+        assert!(parse_config(config).is_empty());
+    }
+
+    #[test]
+    fn known_hosts_skips_what_ssh_host_cannot_reach() {
+        let known_hosts = "\
+github.com,140.82.121.3 ssh-ed25519 AAAA
+|1|hashed= ssh-rsa AAAA
+[git.example.com]:2222 ssh-rsa AAAA
+@cert-authority *.example.com ssh-rsa AAAA
+# comment
+
+gitlab.com ssh-ed25519 AAAA
+";
+        assert_eq!(
+            parse_known_hosts(known_hosts),
+            ["github.com", "140.82.121.3", "gitlab.com"]
+        );
+    }
+
+    #[test]
+    fn the_host_is_quoted_for_the_shell() {
+        assert_eq!(command("prod-db"), "ssh 'prod-db'");
+        assert_eq!(command("a b"), "ssh 'a b'");
+    }
+}
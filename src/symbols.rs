@@ -0,0 +1,2436 @@
+use crate::matching;
+
+/// Queries starting with this search the symbol table by character name.
+pub const PREFIX: &str = "sym ";
+
+pub struct Symbol {
+    pub symbol: char,
+    /// The Unicode character name, lowercased.
+    pub name: &'static str,
+}
+
+impl Symbol {
+    /// `U+2192`.
+    pub fn codepoint(&self) -> String {
+        format!("U+{:04X}", self.symbol as u32)
+    }
+}
+
+/// Symbols whose name matches `filter` best first, or the whole table in
+/// its own order while it's empty.
+pub fn search(filter: &str) -> Vec<&'static Symbol> {
+    let mut scored: Vec<(i64, usize, &'static Symbol)> = SYMBOLS
+        .iter()
+        .enumerate()
+        .filter_map(|(index, symbol)| Some((matching::score(symbol.name, filter)?, index, symbol)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, symbol)| symbol).collect()
+}
+
+/// Greek letters, arrows, mathematical operators and box drawing in full,
+/// then a few common typographic and keyboard symbols. Names are taken
+/// from UnicodeData.txt.
+pub const SYMBOLS: &[Symbol] = &[
+    // Greek letters.
+    Symbol {
+        symbol: 'Α',
+        name: "greek capital letter alpha",
+    },
+    Symbol {
+        symbol: 'Β',
+        name: "greek capital letter beta",
+    },
+    Symbol {
+        symbol: 'Γ',
+        name: "greek capital letter gamma",
+    },
+    Symbol {
+        symbol: 'Δ',
+        name: "greek capital letter delta",
+    },
+    Symbol {
+        symbol: 'Ε',
+        name: "greek capital letter epsilon",
+    },
+    Symbol {
+        symbol: 'Ζ',
+        name: "greek capital letter zeta",
+    },
+    Symbol {
+        symbol: 'Η',
+        name: "greek capital letter eta",
+    },
+    Symbol {
+        symbol: 'Θ',
+        name: "greek capital letter theta",
+    },
+    Symbol {
+        symbol: 'Ι',
+        name: "greek capital letter iota",
+    },
+    Symbol {
+        symbol: 'Κ',
+        name: "greek capital letter kappa",
+    },
+    Symbol {
+        symbol: 'Λ',
+        name: "greek capital letter lamda",
+    },
+    Symbol {
+        symbol: 'Μ',
+        name: "greek capital letter mu",
+    },
+    Symbol {
+        symbol: 'Ν',
+        name: "greek capital letter nu",
+    },
+    Symbol {
+        symbol: 'Ξ',
+        name: "greek capital letter xi",
+    },
+    Symbol {
+        symbol: 'Ο',
+        name: "greek capital letter omicron",
+    },
+    Symbol {
+        symbol: 'Π',
+        name: "greek capital letter pi",
+    },
+    Symbol {
+        symbol: 'Ρ',
+        name: "greek capital letter rho",
+    },
+    Symbol {
+        symbol: 'Σ',
+        name: "greek capital letter sigma",
+    },
+    Symbol {
+        symbol: 'Τ',
+        name: "greek capital letter tau",
+    },
+    Symbol {
+        symbol: 'Υ',
+        name: "greek capital letter upsilon",
+    },
+    Symbol {
+        symbol: 'Φ',
+        name: "greek capital letter phi",
+    },
+    Symbol {
+        symbol: 'Χ',
+        name: "greek capital letter chi",
+    },
+    Symbol {
+        symbol: 'Ψ',
+        name: "greek capital letter psi",
+    },
+    Symbol {
+        symbol: 'Ω',
+        name: "greek capital letter omega",
+    },
+    Symbol {
+        symbol: 'α',
+        name: "greek small letter alpha",
+    },
+    Symbol {
+        symbol: 'β',
+        name: "greek small letter beta",
+    },
+    Symbol {
+        symbol: 'γ',
+        name: "greek small letter gamma",
+    },
+    Symbol {
+        symbol: 'δ',
+        name: "greek small letter delta",
+    },
+    Symbol {
+        symbol: 'ε',
+        name: "greek small letter epsilon",
+    },
+    Symbol {
+        symbol: 'ζ',
+        name: "greek small letter zeta",
+    },
+    Symbol {
+        symbol: 'η',
+        name: "greek small letter eta",
+    },
+    Symbol {
+        symbol: 'θ',
+        name: "greek small letter theta",
+    },
+    Symbol {
+        symbol: 'ι',
+        name: "greek small letter iota",
+    },
+    Symbol {
+        symbol: 'κ',
+        name: "greek small letter kappa",
+    },
+    Symbol {
+        symbol: 'λ',
+        name: "greek small letter lamda",
+    },
+    Symbol {
+        symbol: 'μ',
+        name: "greek small letter mu",
+    },
+    Symbol {
+        symbol: 'ν',
+        name: "greek small letter nu",
+    },
+    Symbol {
+        symbol: 'ξ',
+        name: "greek small letter xi",
+    },
+    Symbol {
+        symbol: 'ο',
+        name: "greek small letter omicron",
+    },
+    Symbol {
+        symbol: 'π',
+        name: "greek small letter pi",
+    },
+    Symbol {
+        symbol: 'ρ',
+        name: "greek small letter rho",
+    },
+    Symbol {
+        symbol: 'ς',
+        name: "greek small letter final sigma",
+    },
+    Symbol {
+        symbol: 'σ',
+        name: "greek small letter sigma",
+    },
+    Symbol {
+        symbol: 'τ',
+        name: "greek small letter tau",
+    },
+    Symbol {
+        symbol: 'υ',
+        name: "greek small letter upsilon",
+    },
+    Symbol {
+        symbol: 'φ',
+        name: "greek small letter phi",
+    },
+    Symbol {
+        symbol: 'χ',
+        name: "greek small letter chi",
+    },
+    Symbol {
+        symbol: 'ψ',
+        name: "greek small letter psi",
+    },
+    Symbol {
+        symbol: 'ω',
+        name: "greek small letter omega",
+    },
+    // Arrows.
+    Symbol {
+        symbol: '←',
+        name: "leftwards arrow",
+    },
+    Symbol {
+        symbol: '↑',
+        name: "upwards arrow",
+    },
+    Symbol {
+        symbol: '→',
+        name: "rightwards arrow",
+    },
+    Symbol {
+        symbol: '↓',
+        name: "downwards arrow",
+    },
+    Symbol {
+        symbol: '↔',
+        name: "left right arrow",
+    },
+    Symbol {
+        symbol: '↕',
+        name: "up down arrow",
+    },
+    Symbol {
+        symbol: '↖',
+        name: "north west arrow",
+    },
+    Symbol {
+        symbol: '↗',
+        name: "north east arrow",
+    },
+    Symbol {
+        symbol: '↘',
+        name: "south east arrow",
+    },
+    Symbol {
+        symbol: '↙',
+        name: "south west arrow",
+    },
+    Symbol {
+        symbol: '↚',
+        name: "leftwards arrow with stroke",
+    },
+    Symbol {
+        symbol: '↛',
+        name: "rightwards arrow with stroke",
+    },
+    Symbol {
+        symbol: '↜',
+        name: "leftwards wave arrow",
+    },
+    Symbol {
+        symbol: '↝',
+        name: "rightwards wave arrow",
+    },
+    Symbol {
+        symbol: '↞',
+        name: "leftwards two headed arrow",
+    },
+    Symbol {
+        symbol: '↟',
+        name: "upwards two headed arrow",
+    },
+    Symbol {
+        symbol: '↠',
+        name: "rightwards two headed arrow",
+    },
+    Symbol {
+        symbol: '↡',
+        name: "downwards two headed arrow",
+    },
+    Symbol {
+        symbol: '↢',
+        name: "leftwards arrow with tail",
+    },
+    Symbol {
+        symbol: '↣',
+        name: "rightwards arrow with tail",
+    },
+    Symbol {
+        symbol: '↤',
+        name: "leftwards arrow from bar",
+    },
+    Symbol {
+        symbol: '↥',
+        name: "upwards arrow from bar",
+    },
+    Symbol {
+        symbol: '↦',
+        name: "rightwards arrow from bar",
+    },
+    Symbol {
+        symbol: '↧',
+        name: "downwards arrow from bar",
+    },
+    Symbol {
+        symbol: '↨',
+        name: "up down arrow with base",
+    },
+    Symbol {
+        symbol: '↩',
+        name: "leftwards arrow with hook",
+    },
+    Symbol {
+        symbol: '↪',
+        name: "rightwards arrow with hook",
+    },
+    Symbol {
+        symbol: '↫',
+        name: "leftwards arrow with loop",
+    },
+    Symbol {
+        symbol: '↬',
+        name: "rightwards arrow with loop",
+    },
+    Symbol {
+        symbol: '↭',
+        name: "left right wave arrow",
+    },
+    Symbol {
+        symbol: '↮',
+        name: "left right arrow with stroke",
+    },
+    Symbol {
+        symbol: '↯',
+        name: "downwards zigzag arrow",
+    },
+    Symbol {
+        symbol: '↰',
+        name: "upwards arrow with tip leftwards",
+    },
+    Symbol {
+        symbol: '↱',
+        name: "upwards arrow with tip rightwards",
+    },
+    Symbol {
+        symbol: '↲',
+        name: "downwards arrow with tip leftwards",
+    },
+    Symbol {
+        symbol: '↳',
+        name: "downwards arrow with tip rightwards",
+    },
+    Symbol {
+        symbol: '↴',
+        name: "rightwards arrow with corner downwards",
+    },
+    Symbol {
+        symbol: '↵',
+        name: "downwards arrow with corner leftwards",
+    },
+    Symbol {
+        symbol: '↶',
+        name: "anticlockwise top semicircle arrow",
+    },
+    Symbol {
+        symbol: '↷',
+        name: "clockwise top semicircle arrow",
+    },
+    Symbol {
+        symbol: '↸',
+        name: "north west arrow to long bar",
+    },
+    Symbol {
+        symbol: '↹',
+        name: "leftwards arrow to bar over rightwards arrow to bar",
+    },
+    Symbol {
+        symbol: '↺',
+        name: "anticlockwise open circle arrow",
+    },
+    Symbol {
+        symbol: '↻',
+        name: "clockwise open circle arrow",
+    },
+    Symbol {
+        symbol: '↼',
+        name: "leftwards harpoon with barb upwards",
+    },
+    Symbol {
+        symbol: '↽',
+        name: "leftwards harpoon with barb downwards",
+    },
+    Symbol {
+        symbol: '↾',
+        name: "upwards harpoon with barb rightwards",
+    },
+    Symbol {
+        symbol: '↿',
+        name: "upwards harpoon with barb leftwards",
+    },
+    Symbol {
+        symbol: '⇀',
+        name: "rightwards harpoon with barb upwards",
+    },
+    Symbol {
+        symbol: '⇁',
+        name: "rightwards harpoon with barb downwards",
+    },
+    Symbol {
+        symbol: '⇂',
+        name: "downwards harpoon with barb rightwards",
+    },
+    Symbol {
+        symbol: '⇃',
+        name: "downwards harpoon with barb leftwards",
+    },
+    Symbol {
+        symbol: '⇄',
+        name: "rightwards arrow over leftwards arrow",
+    },
+    Symbol {
+        symbol: '⇅',
+        name: "upwards arrow leftwards of downwards arrow",
+    },
+    Symbol {
+        symbol: '⇆',
+        name: "leftwards arrow over rightwards arrow",
+    },
+    Symbol {
+        symbol: '⇇',
+        name: "leftwards paired arrows",
+    },
+    Symbol {
+        symbol: '⇈',
+        name: "upwards paired arrows",
+    },
+    Symbol {
+        symbol: '⇉',
+        name: "rightwards paired arrows",
+    },
+    Symbol {
+        symbol: '⇊',
+        name: "downwards paired arrows",
+    },
+    Symbol {
+        symbol: '⇋',
+        name: "leftwards harpoon over rightwards harpoon",
+    },
+    Symbol {
+        symbol: '⇌',
+        name: "rightwards harpoon over leftwards harpoon",
+    },
+    Symbol {
+        symbol: '⇍',
+        name: "leftwards double arrow with stroke",
+    },
+    Symbol {
+        symbol: '⇎',
+        name: "left right double arrow with stroke",
+    },
+    Symbol {
+        symbol: '⇏',
+        name: "rightwards double arrow with stroke",
+    },
+    Symbol {
+        symbol: '⇐',
+        name: "leftwards double arrow",
+    },
+    Symbol {
+        symbol: '⇑',
+        name: "upwards double arrow",
+    },
+    Symbol {
+        symbol: '⇒',
+        name: "rightwards double arrow",
+    },
+    Symbol {
+        symbol: '⇓',
+        name: "downwards double arrow",
+    },
+    Symbol {
+        symbol: '⇔',
+        name: "left right double arrow",
+    },
+    Symbol {
+        symbol: '⇕',
+        name: "up down double arrow",
+    },
+    Symbol {
+        symbol: '⇖',
+        name: "north west double arrow",
+    },
+    Symbol {
+        symbol: '⇗',
+        name: "north east double arrow",
+    },
+    Symbol {
+        symbol: '⇘',
+        name: "south east double arrow",
+    },
+    Symbol {
+        symbol: '⇙',
+        name: "south west double arrow",
+    },
+    Symbol {
+        symbol: '⇚',
+        name: "leftwards triple arrow",
+    },
+    Symbol {
+        symbol: '⇛',
+        name: "rightwards triple arrow",
+    },
+    Symbol {
+        symbol: '⇜',
+        name: "leftwards squiggle arrow",
+    },
+    Symbol {
+        symbol: '⇝',
+        name: "rightwards squiggle arrow",
+    },
+    Symbol {
+        symbol: '⇞',
+        name: "upwards arrow with double stroke",
+    },
+    Symbol {
+        symbol: '⇟',
+        name: "downwards arrow with double stroke",
+    },
+    Symbol {
+        symbol: '⇠',
+        name: "leftwards dashed arrow",
+    },
+    Symbol {
+        symbol: '⇡',
+        name: "upwards dashed arrow",
+    },
+    Symbol {
+        symbol: '⇢',
+        name: "rightwards dashed arrow",
+    },
+    Symbol {
+        symbol: '⇣',
+        name: "downwards dashed arrow",
+    },
+    Symbol {
+        symbol: '⇤',
+        name: "leftwards arrow to bar",
+    },
+    Symbol {
+        symbol: '⇥',
+        name: "rightwards arrow to bar",
+    },
+    Symbol {
+        symbol: '⇦',
+        name: "leftwards white arrow",
+    },
+    Symbol {
+        symbol: '⇧',
+        name: "upwards white arrow",
+    },
+    Symbol {
+        symbol: '⇨',
+        name: "rightwards white arrow",
+    },
+    Symbol {
+        symbol: '⇩',
+        name: "downwards white arrow",
+    },
+    Symbol {
+        symbol: '⇪',
+        name: "upwards white arrow from bar",
+    },
+    Symbol {
+        symbol: '⇫',
+        name: "upwards white arrow on pedestal",
+    },
+    Symbol {
+        symbol: '⇬',
+        name: "upwards white arrow on pedestal with horizontal bar",
+    },
+    Symbol {
+        symbol: '⇭',
+        name: "upwards white arrow on pedestal with vertical bar",
+    },
+    Symbol {
+        symbol: '⇮',
+        name: "upwards white double arrow",
+    },
+    Symbol {
+        symbol: '⇯',
+        name: "upwards white double arrow on pedestal",
+    },
+    Symbol {
+        symbol: '⇰',
+        name: "rightwards white arrow from wall",
+    },
+    Symbol {
+        symbol: '⇱',
+        name: "north west arrow to corner",
+    },
+    Symbol {
+        symbol: '⇲',
+        name: "south east arrow to corner",
+    },
+    Symbol {
+        symbol: '⇳',
+        name: "up down white arrow",
+    },
+    Symbol {
+        symbol: '⇴',
+        name: "right arrow with small circle",
+    },
+    Symbol {
+        symbol: '⇵',
+        name: "downwards arrow leftwards of upwards arrow",
+    },
+    Symbol {
+        symbol: '⇶',
+        name: "three rightwards arrows",
+    },
+    Symbol {
+        symbol: '⇷',
+        name: "leftwards arrow with vertical stroke",
+    },
+    Symbol {
+        symbol: '⇸',
+        name: "rightwards arrow with vertical stroke",
+    },
+    Symbol {
+        symbol: '⇹',
+        name: "left right arrow with vertical stroke",
+    },
+    Symbol {
+        symbol: '⇺',
+        name: "leftwards arrow with double vertical stroke",
+    },
+    Symbol {
+        symbol: '⇻',
+        name: "rightwards arrow with double vertical stroke",
+    },
+    Symbol {
+        symbol: '⇼',
+        name: "left right arrow with double vertical stroke",
+    },
+    Symbol {
+        symbol: '⇽',
+        name: "leftwards open-headed arrow",
+    },
+    Symbol {
+        symbol: '⇾',
+        name: "rightwards open-headed arrow",
+    },
+    Symbol {
+        symbol: '⇿',
+        name: "left right open-headed arrow",
+    },
+    // Mathematical operators.
+    Symbol {
+        symbol: '∀',
+        name: "for all",
+    },
+    Symbol {
+        symbol: '∁',
+        name: "complement",
+    },
+    Symbol {
+        symbol: '∂',
+        name: "partial differential",
+    },
+    Symbol {
+        symbol: '∃',
+        name: "there exists",
+    },
+    Symbol {
+        symbol: '∄',
+        name: "there does not exist",
+    },
+    Symbol {
+        symbol: '∅',
+        name: "empty set",
+    },
+    Symbol {
+        symbol: '∆',
+        name: "increment",
+    },
+    Symbol {
+        symbol: '∇',
+        name: "nabla",
+    },
+    Symbol {
+        symbol: '∈',
+        name: "element of",
+    },
+    Symbol {
+        symbol: '∉',
+        name: "not an element of",
+    },
+    Symbol {
+        symbol: '∊',
+        name: "small element of",
+    },
+    Symbol {
+        symbol: '∋',
+        name: "contains as member",
+    },
+    Symbol {
+        symbol: '∌',
+        name: "does not contain as member",
+    },
+    Symbol {
+        symbol: '∍',
+        name: "small contains as member",
+    },
+    Symbol {
+        symbol: '∎',
+        name: "end of proof",
+    },
+    Symbol {
+        symbol: '∏',
+        name: "n-ary product",
+    },
+    Symbol {
+        symbol: '∐',
+        name: "n-ary coproduct",
+    },
+    Symbol {
+        symbol: '∑',
+        name: "n-ary summation",
+    },
+    Symbol {
+        symbol: '−',
+        name: "minus sign",
+    },
+    Symbol {
+        symbol: '∓',
+        name: "minus-or-plus sign",
+    },
+    Symbol {
+        symbol: '∔',
+        name: "dot plus",
+    },
+    Symbol {
+        symbol: '∕',
+        name: "division slash",
+    },
+    Symbol {
+        symbol: '∖',
+        name: "set minus",
+    },
+    Symbol {
+        symbol: '∗',
+        name: "asterisk operator",
+    },
+    Symbol {
+        symbol: '∘',
+        name: "ring operator",
+    },
+    Symbol {
+        symbol: '∙',
+        name: "bullet operator",
+    },
+    Symbol {
+        symbol: '√',
+        name: "square root",
+    },
+    Symbol {
+        symbol: '∛',
+        name: "cube root",
+    },
+    Symbol {
+        symbol: '∜',
+        name: "fourth root",
+    },
+    Symbol {
+        symbol: '∝',
+        name: "proportional to",
+    },
+    Symbol {
+        symbol: '∞',
+        name: "infinity",
+    },
+    Symbol {
+        symbol: '∟',
+        name: "right angle",
+    },
+    Symbol {
+        symbol: '∠',
+        name: "angle",
+    },
+    Symbol {
+        symbol: '∡',
+        name: "measured angle",
+    },
+    Symbol {
+        symbol: '∢',
+        name: "spherical angle",
+    },
+    Symbol {
+        symbol: '∣',
+        name: "divides",
+    },
+    Symbol {
+        symbol: '∤',
+        name: "does not divide",
+    },
+    Symbol {
+        symbol: '∥',
+        name: "parallel to",
+    },
+    Symbol {
+        symbol: '∦',
+        name: "not parallel to",
+    },
+    Symbol {
+        symbol: '∧',
+        name: "logical and",
+    },
+    Symbol {
+        symbol: '∨',
+        name: "logical or",
+    },
+    Symbol {
+        symbol: '∩',
+        name: "intersection",
+    },
+    Symbol {
+        symbol: '∪',
+        name: "union",
+    },
+    Symbol {
+        symbol: '∫',
+        name: "integral",
+    },
+    Symbol {
+        symbol: '∬',
+        name: "double integral",
+    },
+    Symbol {
+        symbol: '∭',
+        name: "triple integral",
+    },
+    Symbol {
+        symbol: '∮',
+        name: "contour integral",
+    },
+    Symbol {
+        symbol: '∯',
+        name: "surface integral",
+    },
+    Symbol {
+        symbol: '∰',
+        name: "volume integral",
+    },
+    Symbol {
+        symbol: '∱',
+        name: "clockwise integral",
+    },
+    Symbol {
+        symbol: '∲',
+        name: "clockwise contour integral",
+    },
+    Symbol {
+        symbol: '∳',
+        name: "anticlockwise contour integral",
+    },
+    Symbol {
+        symbol: '∴',
+        name: "therefore",
+    },
+    Symbol {
+        symbol: '∵',
+        name: "because",
+    },
+    Symbol {
+        symbol: '∶',
+        name: "ratio",
+    },
+    Symbol {
+        symbol: '∷',
+        name: "proportion",
+    },
+    Symbol {
+        symbol: '∸',
+        name: "dot minus",
+    },
+    Symbol {
+        symbol: '∹',
+        name: "excess",
+    },
+    Symbol {
+        symbol: '∺',
+        name: "geometric proportion",
+    },
+    Symbol {
+        symbol: '∻',
+        name: "homothetic",
+    },
+    Symbol {
+        symbol: '∼',
+        name: "tilde operator",
+    },
+    Symbol {
+        symbol: '∽',
+        name: "reversed tilde",
+    },
+    Symbol {
+        symbol: '∾',
+        name: "inverted lazy s",
+    },
+    Symbol {
+        symbol: '∿',
+        name: "sine wave",
+    },
+    Symbol {
+        symbol: '≀',
+        name: "wreath product",
+    },
+    Symbol {
+        symbol: '≁',
+        name: "not tilde",
+    },
+    Symbol {
+        symbol: '≂',
+        name: "minus tilde",
+    },
+    Symbol {
+        symbol: '≃',
+        name: "asymptotically equal to",
+    },
+    Symbol {
+        symbol: '≄',
+        name: "not asymptotically equal to",
+    },
+    Symbol {
+        symbol: '≅',
+        name: "approximately equal to",
+    },
+    Symbol {
+        symbol: '≆',
+        name: "approximately but not actually equal to",
+    },
+    Symbol {
+        symbol: '≇',
+        name: "neither approximately nor actually equal to",
+    },
+    Symbol {
+        symbol: '≈',
+        name: "almost equal to",
+    },
+    Symbol {
+        symbol: '≉',
+        name: "not almost equal to",
+    },
+    Symbol {
+        symbol: '≊',
+        name: "almost equal or equal to",
+    },
+    Symbol {
+        symbol: '≋',
+        name: "triple tilde",
+    },
+    Symbol {
+        symbol: '≌',
+        name: "all equal to",
+    },
+    Symbol {
+        symbol: '≍',
+        name: "equivalent to",
+    },
+    Symbol {
+        symbol: '≎',
+        name: "geometrically equivalent to",
+    },
+    Symbol {
+        symbol: '≏',
+        name: "difference between",
+    },
+    Symbol {
+        symbol: '≐',
+        name: "approaches the limit",
+    },
+    Symbol {
+        symbol: '≑',
+        name: "geometrically equal to",
+    },
+    Symbol {
+        symbol: '≒',
+        name: "approximately equal to or the image of",
+    },
+    Symbol {
+        symbol: '≓',
+        name: "image of or approximately equal to",
+    },
+    Symbol {
+        symbol: '≔',
+        name: "colon equals",
+    },
+    Symbol {
+        symbol: '≕',
+        name: "equals colon",
+    },
+    Symbol {
+        symbol: '≖',
+        name: "ring in equal to",
+    },
+    Symbol {
+        symbol: '≗',
+        name: "ring equal to",
+    },
+    Symbol {
+        symbol: '≘',
+        name: "corresponds to",
+    },
+    Symbol {
+        symbol: '≙',
+        name: "estimates",
+    },
+    Symbol {
+        symbol: '≚',
+        name: "equiangular to",
+    },
+    Symbol {
+        symbol: '≛',
+        name: "star equals",
+    },
+    Symbol {
+        symbol: '≜',
+        name: "delta equal to",
+    },
+    Symbol {
+        symbol: '≝',
+        name: "equal to by definition",
+    },
+    Symbol {
+        symbol: '≞',
+        name: "measured by",
+    },
+    Symbol {
+        symbol: '≟',
+        name: "questioned equal to",
+    },
+    Symbol {
+        symbol: '≠',
+        name: "not equal to",
+    },
+    Symbol {
+        symbol: '≡',
+        name: "identical to",
+    },
+    Symbol {
+        symbol: '≢',
+        name: "not identical to",
+    },
+    Symbol {
+        symbol: '≣',
+        name: "strictly equivalent to",
+    },
+    Symbol {
+        symbol: '≤',
+        name: "less-than or equal to",
+    },
+    Symbol {
+        symbol: '≥',
+        name: "greater-than or equal to",
+    },
+    Symbol {
+        symbol: '≦',
+        name: "less-than over equal to",
+    },
+    Symbol {
+        symbol: '≧',
+        name: "greater-than over equal to",
+    },
+    Symbol {
+        symbol: '≨',
+        name: "less-than but not equal to",
+    },
+    Symbol {
+        symbol: '≩',
+        name: "greater-than but not equal to",
+    },
+    Symbol {
+        symbol: '≪',
+        name: "much less-than",
+    },
+    Symbol {
+        symbol: '≫',
+        name: "much greater-than",
+    },
+    Symbol {
+        symbol: '≬',
+        name: "between",
+    },
+    Symbol {
+        symbol: '≭',
+        name: "not equivalent to",
+    },
+    Symbol {
+        symbol: '≮',
+        name: "not less-than",
+    },
+    Symbol {
+        symbol: '≯',
+        name: "not greater-than",
+    },
+    Symbol {
+        symbol: '≰',
+        name: "neither less-than nor equal to",
+    },
+    Symbol {
+        symbol: '≱',
+        name: "neither greater-than nor equal to",
+    },
+    Symbol {
+        symbol: '≲',
+        name: "less-than or equivalent to",
+    },
+    Symbol {
+        symbol: '≳',
+        name: "greater-than or equivalent to",
+    },
+    Symbol {
+        symbol: '≴',
+        name: "neither less-than nor equivalent to",
+    },
+    Symbol {
+        symbol: '≵',
+        name: "neither greater-than nor equivalent to",
+    },
+    Symbol {
+        symbol: '≶',
+        name: "less-than or greater-than",
+    },
+    Symbol {
+        symbol: '≷',
+        name: "greater-than or less-than",
+    },
+    Symbol {
+        symbol: '≸',
+        name: "neither less-than nor greater-than",
+    },
+    Symbol {
+        symbol: '≹',
+        name: "neither greater-than nor less-than",
+    },
+    Symbol {
+        symbol: '≺',
+        name: "precedes",
+    },
+    Symbol {
+        symbol: '≻',
+        name: "succeeds",
+    },
+    Symbol {
+        symbol: '≼',
+        name: "precedes or equal to",
+    },
+    Symbol {
+        symbol: '≽',
+        name: "succeeds or equal to",
+    },
+    Symbol {
+        symbol: '≾',
+        name: "precedes or equivalent to",
+    },
+    Symbol {
+        symbol: '≿',
+        name: "succeeds or equivalent to",
+    },
+    Symbol {
+        symbol: '⊀',
+        name: "does not precede",
+    },
+    Symbol {
+        symbol: '⊁',
+        name: "does not succeed",
+    },
+    Symbol {
+        symbol: '⊂',
+        name: "subset of",
+    },
+    Symbol {
+        symbol: '⊃',
+        name: "superset of",
+    },
+    Symbol {
+        symbol: '⊄',
+        name: "not a subset of",
+    },
+    Symbol {
+        symbol: '⊅',
+        name: "not a superset of",
+    },
+    Symbol {
+        symbol: '⊆',
+        name: "subset of or equal to",
+    },
+    Symbol {
+        symbol: '⊇',
+        name: "superset of or equal to",
+    },
+    Symbol {
+        symbol: '⊈',
+        name: "neither a subset of nor equal to",
+    },
+    Symbol {
+        symbol: '⊉',
+        name: "neither a superset of nor equal to",
+    },
+    Symbol {
+        symbol: '⊊',
+        name: "subset of with not equal to",
+    },
+    Symbol {
+        symbol: '⊋',
+        name: "superset of with not equal to",
+    },
+    Symbol {
+        symbol: '⊌',
+        name: "multiset",
+    },
+    Symbol {
+        symbol: '⊍',
+        name: "multiset multiplication",
+    },
+    Symbol {
+        symbol: '⊎',
+        name: "multiset union",
+    },
+    Symbol {
+        symbol: '⊏',
+        name: "square image of",
+    },
+    Symbol {
+        symbol: '⊐',
+        name: "square original of",
+    },
+    Symbol {
+        symbol: '⊑',
+        name: "square image of or equal to",
+    },
+    Symbol {
+        symbol: '⊒',
+        name: "square original of or equal to",
+    },
+    Symbol {
+        symbol: '⊓',
+        name: "square cap",
+    },
+    Symbol {
+        symbol: '⊔',
+        name: "square cup",
+    },
+    Symbol {
+        symbol: '⊕',
+        name: "circled plus",
+    },
+    Symbol {
+        symbol: '⊖',
+        name: "circled minus",
+    },
+    Symbol {
+        symbol: '⊗',
+        name: "circled times",
+    },
+    Symbol {
+        symbol: '⊘',
+        name: "circled division slash",
+    },
+    Symbol {
+        symbol: '⊙',
+        name: "circled dot operator",
+    },
+    Symbol {
+        symbol: '⊚',
+        name: "circled ring operator",
+    },
+    Symbol {
+        symbol: '⊛',
+        name: "circled asterisk operator",
+    },
+    Symbol {
+        symbol: '⊜',
+        name: "circled equals",
+    },
+    Symbol {
+        symbol: '⊝',
+        name: "circled dash",
+    },
+    Symbol {
+        symbol: '⊞',
+        name: "squared plus",
+    },
+    Symbol {
+        symbol: '⊟',
+        name: "squared minus",
+    },
+    Symbol {
+        symbol: '⊠',
+        name: "squared times",
+    },
+    Symbol {
+        symbol: '⊡',
+        name: "squared dot operator",
+    },
+    Symbol {
+        symbol: '⊢',
+        name: "right tack",
+    },
+    Symbol {
+        symbol: '⊣',
+        name: "left tack",
+    },
+    Symbol {
+        symbol: '⊤',
+        name: "down tack",
+    },
+    Symbol {
+        symbol: '⊥',
+        name: "up tack",
+    },
+    Symbol {
+        symbol: '⊦',
+        name: "assertion",
+    },
+    Symbol {
+        symbol: '⊧',
+        name: "models",
+    },
+    Symbol {
+        symbol: '⊨',
+        name: "true",
+    },
+    Symbol {
+        symbol: '⊩',
+        name: "forces",
+    },
+    Symbol {
+        symbol: '⊪',
+        name: "triple vertical bar right turnstile",
+    },
+    Symbol {
+        symbol: '⊫',
+        name: "double vertical bar double right turnstile",
+    },
+    Symbol {
+        symbol: '⊬',
+        name: "does not prove",
+    },
+    Symbol {
+        symbol: '⊭',
+        name: "not true",
+    },
+    Symbol {
+        symbol: '⊮',
+        name: "does not force",
+    },
+    Symbol {
+        symbol: '⊯',
+        name: "negated double vertical bar double right turnstile",
+    },
+    Symbol {
+        symbol: '⊰',
+        name: "precedes under relation",
+    },
+    Symbol {
+        symbol: '⊱',
+        name: "succeeds under relation",
+    },
+    Symbol {
+        symbol: '⊲',
+        name: "normal subgroup of",
+    },
+    Symbol {
+        symbol: '⊳',
+        name: "contains as normal subgroup",
+    },
+    Symbol {
+        symbol: '⊴',
+        name: "normal subgroup of or equal to",
+    },
+    Symbol {
+        symbol: '⊵',
+        name: "contains as normal subgroup or equal to",
+    },
+    Symbol {
+        symbol: '⊶',
+        name: "original of",
+    },
+    Symbol {
+        symbol: '⊷',
+        name: "image of",
+    },
+    Symbol {
+        symbol: '⊸',
+        name: "multimap",
+    },
+    Symbol {
+        symbol: '⊹',
+        name: "hermitian conjugate matrix",
+    },
+    Symbol {
+        symbol: '⊺',
+        name: "intercalate",
+    },
+    Symbol {
+        symbol: '⊻',
+        name: "xor",
+    },
+    Symbol {
+        symbol: '⊼',
+        name: "nand",
+    },
+    Symbol {
+        symbol: '⊽',
+        name: "nor",
+    },
+    Symbol {
+        symbol: '⊾',
+        name: "right angle with arc",
+    },
+    Symbol {
+        symbol: '⊿',
+        name: "right triangle",
+    },
+    Symbol {
+        symbol: '⋀',
+        name: "n-ary logical and",
+    },
+    Symbol {
+        symbol: '⋁',
+        name: "n-ary logical or",
+    },
+    Symbol {
+        symbol: '⋂',
+        name: "n-ary intersection",
+    },
+    Symbol {
+        symbol: '⋃',
+        name: "n-ary union",
+    },
+    Symbol {
+        symbol: '⋄',
+        name: "diamond operator",
+    },
+    Symbol {
+        symbol: '⋅',
+        name: "dot operator",
+    },
+    Symbol {
+        symbol: '⋆',
+        name: "star operator",
+    },
+    Symbol {
+        symbol: '⋇',
+        name: "division times",
+    },
+    Symbol {
+        symbol: '⋈',
+        name: "bowtie",
+    },
+    Symbol {
+        symbol: '⋉',
+        name: "left normal factor semidirect product",
+    },
+    Symbol {
+        symbol: '⋊',
+        name: "right normal factor semidirect product",
+    },
+    Symbol {
+        symbol: '⋋',
+        name: "left semidirect product",
+    },
+    Symbol {
+        symbol: '⋌',
+        name: "right semidirect product",
+    },
+    Symbol {
+        symbol: '⋍',
+        name: "reversed tilde equals",
+    },
+    Symbol {
+        symbol: '⋎',
+        name: "curly logical or",
+    },
+    Symbol {
+        symbol: '⋏',
+        name: "curly logical and",
+    },
+    Symbol {
+        symbol: '⋐',
+        name: "double subset",
+    },
+    Symbol {
+        symbol: '⋑',
+        name: "double superset",
+    },
+    Symbol {
+        symbol: '⋒',
+        name: "double intersection",
+    },
+    Symbol {
+        symbol: '⋓',
+        name: "double union",
+    },
+    Symbol {
+        symbol: '⋔',
+        name: "pitchfork",
+    },
+    Symbol {
+        symbol: '⋕',
+        name: "equal and parallel to",
+    },
+    Symbol {
+        symbol: '⋖',
+        name: "less-than with dot",
+    },
+    Symbol {
+        symbol: '⋗',
+        name: "greater-than with dot",
+    },
+    Symbol {
+        symbol: '⋘',
+        name: "very much less-than",
+    },
+    Symbol {
+        symbol: '⋙',
+        name: "very much greater-than",
+    },
+    Symbol {
+        symbol: '⋚',
+        name: "less-than equal to or greater-than",
+    },
+    Symbol {
+        symbol: '⋛',
+        name: "greater-than equal to or less-than",
+    },
+    Symbol {
+        symbol: '⋜',
+        name: "equal to or less-than",
+    },
+    Symbol {
+        symbol: '⋝',
+        name: "equal to or greater-than",
+    },
+    Symbol {
+        symbol: '⋞',
+        name: "equal to or precedes",
+    },
+    Symbol {
+        symbol: '⋟',
+        name: "equal to or succeeds",
+    },
+    Symbol {
+        symbol: '⋠',
+        name: "does not precede or equal",
+    },
+    Symbol {
+        symbol: '⋡',
+        name: "does not succeed or equal",
+    },
+    Symbol {
+        symbol: '⋢',
+        name: "not square image of or equal to",
+    },
+    Symbol {
+        symbol: '⋣',
+        name: "not square original of or equal to",
+    },
+    Symbol {
+        symbol: '⋤',
+        name: "square image of or not equal to",
+    },
+    Symbol {
+        symbol: '⋥',
+        name: "square original of or not equal to",
+    },
+    Symbol {
+        symbol: '⋦',
+        name: "less-than but not equivalent to",
+    },
+    Symbol {
+        symbol: '⋧',
+        name: "greater-than but not equivalent to",
+    },
+    Symbol {
+        symbol: '⋨',
+        name: "precedes but not equivalent to",
+    },
+    Symbol {
+        symbol: '⋩',
+        name: "succeeds but not equivalent to",
+    },
+    Symbol {
+        symbol: '⋪',
+        name: "not normal subgroup of",
+    },
+    Symbol {
+        symbol: '⋫',
+        name: "does not contain as normal subgroup",
+    },
+    Symbol {
+        symbol: '⋬',
+        name: "not normal subgroup of or equal to",
+    },
+    Symbol {
+        symbol: '⋭',
+        name: "does not contain as normal subgroup or equal",
+    },
+    Symbol {
+        symbol: '⋮',
+        name: "vertical ellipsis",
+    },
+    Symbol {
+        symbol: '⋯',
+        name: "midline horizontal ellipsis",
+    },
+    Symbol {
+        symbol: '⋰',
+        name: "up right diagonal ellipsis",
+    },
+    Symbol {
+        symbol: '⋱',
+        name: "down right diagonal ellipsis",
+    },
+    Symbol {
+        symbol: '⋲',
+        name: "element of with long horizontal stroke",
+    },
+    Symbol {
+        symbol: '⋳',
+        name: "element of with vertical bar at end of horizontal stroke",
+    },
+    Symbol {
+        symbol: '⋴',
+        name: "small element of with vertical bar at end of horizontal stroke",
+    },
+    Symbol {
+        symbol: '⋵',
+        name: "element of with dot above",
+    },
+    Symbol {
+        symbol: '⋶',
+        name: "element of with overbar",
+    },
+    Symbol {
+        symbol: '⋷',
+        name: "small element of with overbar",
+    },
+    Symbol {
+        symbol: '⋸',
+        name: "element of with underbar",
+    },
+    Symbol {
+        symbol: '⋹',
+        name: "element of with two horizontal strokes",
+    },
+    Symbol {
+        symbol: '⋺',
+        name: "contains with long horizontal stroke",
+    },
+    Symbol {
+        symbol: '⋻',
+        name: "contains with vertical bar at end of horizontal stroke",
+    },
+    Symbol {
+        symbol: '⋼',
+        name: "small contains with vertical bar at end of horizontal stroke",
+    },
+    Symbol {
+        symbol: '⋽',
+        name: "contains with overbar",
+    },
+    Symbol {
+        symbol: '⋾',
+        name: "small contains with overbar",
+    },
+    Symbol {
+        symbol: '⋿',
+        name: "z notation bag membership",
+    },
+    // Box drawing.
+    Symbol {
+        symbol: '─',
+        name: "box drawings light horizontal",
+    },
+    Symbol {
+        symbol: '━',
+        name: "box drawings heavy horizontal",
+    },
+    Symbol {
+        symbol: '│',
+        name: "box drawings light vertical",
+    },
+    Symbol {
+        symbol: '┃',
+        name: "box drawings heavy vertical",
+    },
+    Symbol {
+        symbol: '┄',
+        name: "box drawings light triple dash horizontal",
+    },
+    Symbol {
+        symbol: '┅',
+        name: "box drawings heavy triple dash horizontal",
+    },
+    Symbol {
+        symbol: '┆',
+        name: "box drawings light triple dash vertical",
+    },
+    Symbol {
+        symbol: '┇',
+        name: "box drawings heavy triple dash vertical",
+    },
+    Symbol {
+        symbol: '┈',
+        name: "box drawings light quadruple dash horizontal",
+    },
+    Symbol {
+        symbol: '┉',
+        name: "box drawings heavy quadruple dash horizontal",
+    },
+    Symbol {
+        symbol: '┊',
+        name: "box drawings light quadruple dash vertical",
+    },
+    Symbol {
+        symbol: '┋',
+        name: "box drawings heavy quadruple dash vertical",
+    },
+    Symbol {
+        symbol: '┌',
+        name: "box drawings light down and right",
+    },
+    Symbol {
+        symbol: '┍',
+        name: "box drawings down light and right heavy",
+    },
+    Symbol {
+        symbol: '┎',
+        name: "box drawings down heavy and right light",
+    },
+    Symbol {
+        symbol: '┏',
+        name: "box drawings heavy down and right",
+    },
+    Symbol {
+        symbol: '┐',
+        name: "box drawings light down and left",
+    },
+    Symbol {
+        symbol: '┑',
+        name: "box drawings down light and left heavy",
+    },
+    Symbol {
+        symbol: '┒',
+        name: "box drawings down heavy and left light",
+    },
+    Symbol {
+        symbol: '┓',
+        name: "box drawings heavy down and left",
+    },
+    Symbol {
+        symbol: '└',
+        name: "box drawings light up and right",
+    },
+    Symbol {
+        symbol: '┕',
+        name: "box drawings up light and right heavy",
+    },
+    Symbol {
+        symbol: '┖',
+        name: "box drawings up heavy and right light",
+    },
+    Symbol {
+        symbol: '┗',
+        name: "box drawings heavy up and right",
+    },
+    Symbol {
+        symbol: '┘',
+        name: "box drawings light up and left",
+    },
+    Symbol {
+        symbol: '┙',
+        name: "box drawings up light and left heavy",
+    },
+    Symbol {
+        symbol: '┚',
+        name: "box drawings up heavy and left light",
+    },
+    Symbol {
+        symbol: '┛',
+        name: "box drawings heavy up and left",
+    },
+    Symbol {
+        symbol: '├',
+        name: "box drawings light vertical and right",
+    },
+    Symbol {
+        symbol: '┝',
+        name: "box drawings vertical light and right heavy",
+    },
+    Symbol {
+        symbol: '┞',
+        name: "box drawings up heavy and right down light",
+    },
+    Symbol {
+        symbol: '┟',
+        name: "box drawings down heavy and right up light",
+    },
+    Symbol {
+        symbol: '┠',
+        name: "box drawings vertical heavy and right light",
+    },
+    Symbol {
+        symbol: '┡',
+        name: "box drawings down light and right up heavy",
+    },
+    Symbol {
+        symbol: '┢',
+        name: "box drawings up light and right down heavy",
+    },
+    Symbol {
+        symbol: '┣',
+        name: "box drawings heavy vertical and right",
+    },
+    Symbol {
+        symbol: '┤',
+        name: "box drawings light vertical and left",
+    },
+    Symbol {
+        symbol: '┥',
+        name: "box drawings vertical light and left heavy",
+    },
+    Symbol {
+        symbol: '┦',
+        name: "box drawings up heavy and left down light",
+    },
+    Symbol {
+        symbol: '┧',
+        name: "box drawings down heavy and left up light",
+    },
+    Symbol {
+        symbol: '┨',
+        name: "box drawings vertical heavy and left light",
+    },
+    Symbol {
+        symbol: '┩',
+        name: "box drawings down light and left up heavy",
+    },
+    Symbol {
+        symbol: '┪',
+        name: "box drawings up light and left down heavy",
+    },
+    Symbol {
+        symbol: '┫',
+        name: "box drawings heavy vertical and left",
+    },
+    Symbol {
+        symbol: '┬',
+        name: "box drawings light down and horizontal",
+    },
+    Symbol {
+        symbol: '┭',
+        name: "box drawings left heavy and right down light",
+    },
+    Symbol {
+        symbol: '┮',
+        name: "box drawings right heavy and left down light",
+    },
+    Symbol {
+        symbol: '┯',
+        name: "box drawings down light and horizontal heavy",
+    },
+    Symbol {
+        symbol: '┰',
+        name: "box drawings down heavy and horizontal light",
+    },
+    Symbol {
+        symbol: '┱',
+        name: "box drawings right light and left down heavy",
+    },
+    Symbol {
+        symbol: '┲',
+        name: "box drawings left light and right down heavy",
+    },
+    Symbol {
+        symbol: '┳',
+        name: "box drawings heavy down and horizontal",
+    },
+    Symbol {
+        symbol: '┴',
+        name: "box drawings light up and horizontal",
+    },
+    Symbol {
+        symbol: '┵',
+        name: "box drawings left heavy and right up light",
+    },
+    Symbol {
+        symbol: '┶',
+        name: "box drawings right heavy and left up light",
+    },
+    Symbol {
+        symbol: '┷',
+        name: "box drawings up light and horizontal heavy",
+    },
+    Symbol {
+        symbol: '┸',
+        name: "box drawings up heavy and horizontal light",
+    },
+    Symbol {
+        symbol: '┹',
+        name: "box drawings right light and left up heavy",
+    },
+    Symbol {
+        symbol: '┺',
+        name: "box drawings left light and right up heavy",
+    },
+    Symbol {
+        symbol: '┻',
+        name: "box drawings heavy up and horizontal",
+    },
+    Symbol {
+        symbol: '┼',
+        name: "box drawings light vertical and horizontal",
+    },
+    Symbol {
+        symbol: '┽',
+        name: "box drawings left heavy and right vertical light",
+    },
+    Symbol {
+        symbol: '┾',
+        name: "box drawings right heavy and left vertical light",
+    },
+    Symbol {
+        symbol: '┿',
+        name: "box drawings vertical light and horizontal heavy",
+    },
+    Symbol {
+        symbol: '╀',
+        name: "box drawings up heavy and down horizontal light",
+    },
+    Symbol {
+        symbol: '╁',
+        name: "box drawings down heavy and up horizontal light",
+    },
+    Symbol {
+        symbol: '╂',
+        name: "box drawings vertical heavy and horizontal light",
+    },
+    Symbol {
+        symbol: '╃',
+        name: "box drawings left up heavy and right down light",
+    },
+    Symbol {
+        symbol: '╄',
+        name: "box drawings right up heavy and left down light",
+    },
+    Symbol {
+        symbol: '╅',
+        name: "box drawings left down heavy and right up light",
+    },
+    Symbol {
+        symbol: '╆',
+        name: "box drawings right down heavy and left up light",
+    },
+    Symbol {
+        symbol: '╇',
+        name: "box drawings down light and up horizontal heavy",
+    },
+    Symbol {
+        symbol: '╈',
+        name: "box drawings up light and down horizontal heavy",
+    },
+    Symbol {
+        symbol: '╉',
+        name: "box drawings right light and left vertical heavy",
+    },
+    Symbol {
+        symbol: '╊',
+        name: "box drawings left light and right vertical heavy",
+    },
+    Symbol {
+        symbol: '╋',
+        name: "box drawings heavy vertical and horizontal",
+    },
+    Symbol {
+        symbol: '╌',
+        name: "box drawings light double dash horizontal",
+    },
+    Symbol {
+        symbol: '╍',
+        name: "box drawings heavy double dash horizontal",
+    },
+    Symbol {
+        symbol: '╎',
+        name: "box drawings light double dash vertical",
+    },
+    Symbol {
+        symbol: '╏',
+        name: "box drawings heavy double dash vertical",
+    },
+    Symbol {
+        symbol: '═',
+        name: "box drawings double horizontal",
+    },
+    Symbol {
+        symbol: '║',
+        name: "box drawings double vertical",
+    },
+    Symbol {
+        symbol: '╒',
+        name: "box drawings down single and right double",
+    },
+    Symbol {
+        symbol: '╓',
+        name: "box drawings down double and right single",
+    },
+    Symbol {
+        symbol: '╔',
+        name: "box drawings double down and right",
+    },
+    Symbol {
+        symbol: '╕',
+        name: "box drawings down single and left double",
+    },
+    Symbol {
+        symbol: '╖',
+        name: "box drawings down double and left single",
+    },
+    Symbol {
+        symbol: '╗',
+        name: "box drawings double down and left",
+    },
+    Symbol {
+        symbol: '╘',
+        name: "box drawings up single and right double",
+    },
+    Symbol {
+        symbol: '╙',
+        name: "box drawings up double and right single",
+    },
+    Symbol {
+        symbol: '╚',
+        name: "box drawings double up and right",
+    },
+    Symbol {
+        symbol: '╛',
+        name: "box drawings up single and left double",
+    },
+    Symbol {
+        symbol: '╜',
+        name: "box drawings up double and left single",
+    },
+    Symbol {
+        symbol: '╝',
+        name: "box drawings double up and left",
+    },
+    Symbol {
+        symbol: '╞',
+        name: "box drawings vertical single and right double",
+    },
+    Symbol {
+        symbol: '╟',
+        name: "box drawings vertical double and right single",
+    },
+    Symbol {
+        symbol: '╠',
+        name: "box drawings double vertical and right",
+    },
+    Symbol {
+        symbol: '╡',
+        name: "box drawings vertical single and left double",
+    },
+    Symbol {
+        symbol: '╢',
+        name: "box drawings vertical double and left single",
+    },
+    Symbol {
+        symbol: '╣',
+        name: "box drawings double vertical and left",
+    },
+    Symbol {
+        symbol: '╤',
+        name: "box drawings down single and horizontal double",
+    },
+    Symbol {
+        symbol: '╥',
+        name: "box drawings down double and horizontal single",
+    },
+    Symbol {
+        symbol: '╦',
+        name: "box drawings double down and horizontal",
+    },
+    Symbol {
+        symbol: '╧',
+        name: "box drawings up single and horizontal double",
+    },
+    Symbol {
+        symbol: '╨',
+        name: "box drawings up double and horizontal single",
+    },
+    Symbol {
+        symbol: '╩',
+        name: "box drawings double up and horizontal",
+    },
+    Symbol {
+        symbol: '╪',
+        name: "box drawings vertical single and horizontal double",
+    },
+    Symbol {
+        symbol: '╫',
+        name: "box drawings vertical double and horizontal single",
+    },
+    Symbol {
+        symbol: '╬',
+        name: "box drawings double vertical and horizontal",
+    },
+    Symbol {
+        symbol: '╭',
+        name: "box drawings light arc down and right",
+    },
+    Symbol {
+        symbol: '╮',
+        name: "box drawings light arc down and left",
+    },
+    Symbol {
+        symbol: '╯',
+        name: "box drawings light arc up and left",
+    },
+    Symbol {
+        symbol: '╰',
+        name: "box drawings light arc up and right",
+    },
+    Symbol {
+        symbol: '╱',
+        name: "box drawings light diagonal upper right to lower left",
+    },
+    Symbol {
+        symbol: '╲',
+        name: "box drawings light diagonal upper left to lower right",
+    },
+    Symbol {
+        symbol: '╳',
+        name: "box drawings light diagonal cross",
+    },
+    Symbol {
+        symbol: '╴',
+        name: "box drawings light left",
+    },
+    Symbol {
+        symbol: '╵',
+        name: "box drawings light up",
+    },
+    Symbol {
+        symbol: '╶',
+        name: "box drawings light right",
+    },
+    Symbol {
+        symbol: '╷',
+        name: "box drawings light down",
+    },
+    Symbol {
+        symbol: '╸',
+        name: "box drawings heavy left",
+    },
+    Symbol {
+        symbol: '╹',
+        name: "box drawings heavy up",
+    },
+    Symbol {
+        symbol: '╺',
+        name: "box drawings heavy right",
+    },
+    Symbol {
+        symbol: '╻',
+        name: "box drawings heavy down",
+    },
+    Symbol {
+        symbol: '╼',
+        name: "box drawings light left and heavy right",
+    },
+    Symbol {
+        symbol: '╽',
+        name: "box drawings light up and heavy down",
+    },
+    Symbol {
+        symbol: '╾',
+        name: "box drawings heavy left and light right",
+    },
+    Symbol {
+        symbol: '╿',
+        name: "box drawings heavy up and light down",
+    },
+    // Typography, fractions and keyboard keys.
+    Symbol {
+        symbol: '°',
+        name: "degree sign",
+    },
+    Symbol {
+        symbol: '±',
+        name: "plus-minus sign",
+    },
+    Symbol {
+        symbol: '×',
+        name: "multiplication sign",
+    },
+    Symbol {
+        symbol: '÷',
+        name: "division sign",
+    },
+    Symbol {
+        symbol: '·',
+        name: "middle dot",
+    },
+    Symbol {
+        symbol: '•',
+        name: "bullet",
+    },
+    Symbol {
+        symbol: '…',
+        name: "horizontal ellipsis",
+    },
+    Symbol {
+        symbol: '–',
+        name: "en dash",
+    },
+    Symbol {
+        symbol: '—',
+        name: "em dash",
+    },
+    Symbol {
+        symbol: '‘',
+        name: "left single quotation mark",
+    },
+    Symbol {
+        symbol: '’',
+        name: "right single quotation mark",
+    },
+    Symbol {
+        symbol: '“',
+        name: "left double quotation mark",
+    },
+    Symbol {
+        symbol: '”',
+        name: "right double quotation mark",
+    },
+    Symbol {
+        symbol: '«',
+        name: "left-pointing double angle quotation mark",
+    },
+    Symbol {
+        symbol: '»',
+        name: "right-pointing double angle quotation mark",
+    },
+    Symbol {
+        symbol: '‹',
+        name: "single left-pointing angle quotation mark",
+    },
+    Symbol {
+        symbol: '›',
+        name: "single right-pointing angle quotation mark",
+    },
+    Symbol {
+        symbol: '€',
+        name: "euro sign",
+    },
+    Symbol {
+        symbol: '£',
+        name: "pound sign",
+    },
+    Symbol {
+        symbol: '¥',
+        name: "yen sign",
+    },
+    Symbol {
+        symbol: '¢',
+        name: "cent sign",
+    },
+    Symbol {
+        symbol: '©',
+        name: "copyright sign",
+    },
+    Symbol {
+        symbol: '®',
+        name: "registered sign",
+    },
+    Symbol {
+        symbol: '™',
+        name: "trade mark sign",
+    },
+    Symbol {
+        symbol: '§',
+        name: "section sign",
+    },
+    Symbol {
+        symbol: '¶',
+        name: "pilcrow sign",
+    },
+    Symbol {
+        symbol: '†',
+        name: "dagger",
+    },
+    Symbol {
+        symbol: '‡',
+        name: "double dagger",
+    },
+    Symbol {
+        symbol: '‰',
+        name: "per mille sign",
+    },
+    Symbol {
+        symbol: '′',
+        name: "prime",
+    },
+    Symbol {
+        symbol: '″',
+        name: "double prime",
+    },
+    Symbol {
+        symbol: 'µ',
+        name: "micro sign",
+    },
+    Symbol {
+        symbol: '¹',
+        name: "superscript one",
+    },
+    Symbol {
+        symbol: '²',
+        name: "superscript two",
+    },
+    Symbol {
+        symbol: '³',
+        name: "superscript three",
+    },
+    Symbol {
+        symbol: '½',
+        name: "vulgar fraction one half",
+    },
+    Symbol {
+        symbol: '¼',
+        name: "vulgar fraction one quarter",
+    },
+    Symbol {
+        symbol: '¾',
+        name: "vulgar fraction three quarters",
+    },
+    Symbol {
+        symbol: '⅓',
+        name: "vulgar fraction one third",
+    },
+    Symbol {
+        symbol: '⅔',
+        name: "vulgar fraction two thirds",
+    },
+    Symbol {
+        symbol: '✓',
+        name: "check mark",
+    },
+    Symbol {
+        symbol: '✗',
+        name: "ballot x",
+    },
+    Symbol {
+        symbol: '★',
+        name: "black star",
+    },
+    Symbol {
+        symbol: '☆',
+        name: "white star",
+    },
+    Symbol {
+        symbol: '♠',
+        name: "black spade suit",
+    },
+    Symbol {
+        symbol: '♣',
+        name: "black club suit",
+    },
+    Symbol {
+        symbol: '♥',
+        name: "black heart suit",
+    },
+    Symbol {
+        symbol: '♦',
+        name: "black diamond suit",
+    },
+    Symbol {
+        symbol: '⌘',
+        name: "place of interest sign",
+    },
+    Symbol {
+        symbol: '⌥',
+        name: "option key",
+    },
+    Symbol {
+        symbol: '⌃',
+        name: "up arrowhead",
+    },
+    Symbol {
+        symbol: '⏎',
+        name: "return symbol",
+    },
+    Symbol {
+        symbol: '⌫',
+        name: "erase to the left",
+    },
+    Symbol {
+        symbol: '⎋',
+        name: "broken circle with northwest arrow",
+    },
+];
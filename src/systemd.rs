@@ -0,0 +1,120 @@
+use crate::matching;
+use serde::Deserialize;
+use std::process::Command;
+
+/// Queries starting with this list systemd user services.
+pub const PREFIX: &str = "svc ";
+
+/// A service as `systemctl --output=json list-units` describes it.
+#[derive(Clone, Deserialize)]
+pub struct Unit {
+    #[serde(rename = "unit")]
+    pub name: String,
+    /// `active`, `inactive`, `failed`, `activating`, and so on.
+    pub active: String,
+    /// The finer state, like `running` or `exited`.
+    pub sub: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl Unit {
+    pub fn is_active(&self) -> bool {
+        self.active == "active" || self.active == "activating" || self.active == "reloading"
+    }
+}
+
+/// The services, or why they couldn't be listed.
+pub type Listing = Result<Vec<Unit>, String>;
+
+pub fn parse(json: &str) -> Listing {
+    serde_json::from_str(json).map_err(|err| err.to_string())
+}
+
+/// Every user service systemd knows of, loaded or not. The error explains
+/// why there is no list, e.g. systemctl missing or no user manager.
+pub fn list() -> Listing {
+    let output = Command::new("systemctl")
+        .args([
+            "--user",
+            "list-units",
+            "--type=service",
+            "--all",
+            "--output=json",
+            "--no-pager",
+        ])
+        .output()
+        .map_err(|err| format!("couldn't run systemctl: {}", err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.trim().to_string());
+    }
+    parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Services matching `filter` best first, by name where they tie.
+pub fn search<'a>(units: &'a [Unit], filter: &str) -> Vec<&'a Unit> {
+    let mut scored: Vec<(i64, &Unit)> = units
+        .iter()
+        .filter_map(|unit| Some((matching::score(&unit.name, filter)?, unit)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    scored.into_iter().map(|(_, unit)| unit).collect()
+}
+
+/// Runs `systemctl --user <verb> <unit>` and waits for it, so the list
+/// can be refreshed afterwards.
+pub fn control(verb: &str, unit: &str) -> Result<(), String> {
+    let output = Command::new("systemctl")
+        .args(["--user", verb, "--", unit])
+        .output()
+        .map_err(|err| format!("couldn't run systemctl: {}", err))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LISTING: &str = r#"[
+        {"unit": "pipewire.service", "load": "loaded", "active": "active", "sub": "running", "description": "PipeWire Multimedia Service"},
+        {"unit": "syncthing.service", "load": "loaded", "active": "failed", "sub": "failed"},
+        {"unit": "pipewire-pulse.service", "load": "loaded", "active": "inactive", "sub": "dead", "description": "PipeWire PulseAudio"}
+    ]"#;
+
+    #[test]
+    fn parses_systemctl_json() {
+        let Ok(units) = parse(LISTING) else {
+            panic!("listing didn't parse");
+        };
+        assert_eq!(units.len(), 3);
+        assert_eq!(units[0].name, "pipewire.service");
+        assert_eq!(units[0].sub, "running");
+        assert_eq!(units[1].description, "");
+        assert!(units[0].is_active());
+        assert!(!units[1].is_active());
+        assert!(!units[2].is_active());
+    }
+
+    #[test]
+    fn output_that_isnt_a_listing_is_an_error() {
+        assert!(parse("Failed to connect to bus").is_err());
+        assert!(parse(r#"[{"active": "active"}]"#).is_err());
+    }
+
+    #[test]
+    fn search_ranks_closer_names_first() {
+        let Ok(units) = parse(LISTING) else {
+            panic!("listing didn't parse");
+        };
+        let names: Vec<&str> = search(&units, "pipewire")
+            .iter()
+            .map(|unit| unit.name.as_str())
+            .collect();
+        assert_eq!(names, ["pipewire.service", "pipewire-pulse.service"]);
+    }
+}
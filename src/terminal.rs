@@ -0,0 +1,117 @@
+use gio::prelude::*;
+use gtk::glib;
+
+/// Terminals tried in order when none is configured, with the argument
+/// each one wants before the command it should run.
+const KNOWN: &[(&str, &str)] = &[
+    ("x-terminal-emulator", "-e"),
+    ("gnome-terminal", "--"),
+    ("konsole", "-e"),
+    ("xfce4-terminal", "-x"),
+    ("kitty", ""),
+    ("alacritty", "-e"),
+    ("xterm", "-e"),
+];
+
+fn with_flag(program: &str, flag: &str) -> String {
+    if flag.is_empty() {
+        program.to_string()
+    } else {
+        format!("{} {}", program, flag)
+    }
+}
+
+/// The command line prefix that runs whatever follows it in a new
+/// terminal. A configured value is used as written, except that a bare
+/// known terminal name gets its usual flag.
+pub fn resolve(configured: Option<&str>) -> String {
+    if let Some(configured) = configured {
+        let configured = configured.trim();
+        return match KNOWN.iter().find(|(program, _)| *program == configured) {
+            Some((program, flag)) => with_flag(program, flag),
+            None => configured.to_string(),
+        };
+    }
+
+    KNOWN
+        .iter()
+        .find(|(program, _)| glib::find_program_in_path(program).is_some())
+        .map_or_else(
+            || with_flag("xterm", "-e"),
+            |(program, flag)| with_flag(program, flag),
+        )
+}
+
+/// A desktop entry's `Exec` line as a shell command, with `%f`/`%F` and
+/// `%u`/`%U` replaced by the quoted paths and URIs of `files` and the other
+/// field codes dropped.
+pub fn exec_command(exec: &str, files: &[gio::File]) -> String {
+    let quote = |text: &str| glib::shell_quote(text).to_string_lossy().into_owned();
+    let paths: Vec<String> = files
+        .iter()
+        .filter_map(|file| file.path())
+        .map(|path| quote(&path.to_string_lossy()))
+        .collect();
+    let uris: Vec<String> = files.iter().map(|file| quote(&file.uri())).collect();
+
+    let mut command = String::new();
+    let mut chars = exec.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            command.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => command.push('%'),
+            Some('f') => command.push_str(paths.first().map_or("", String::as_str)),
+            Some('F') => command.push_str(&paths.join(" ")),
+            Some('u') => command.push_str(uris.first().map_or("", String::as_str)),
+            Some('U') => command.push_str(&uris.join(" ")),
+            // %i, %c, %k and the deprecated codes.
+            _ => {}
+        }
+    }
+    command.trim().to_string()
+}
+
+/// A command line that runs `command` through `sh -c` in the terminal.
+pub fn command_line(terminal: &str, command: &str) -> String {
+    let script = glib::shell_quote(command);
+    format!("{} sh -c {}", terminal, script.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_terminals_get_their_flag() {
+        assert_eq!(resolve(Some("gnome-terminal")), "gnome-terminal --");
+        assert_eq!(resolve(Some(" konsole ")), "konsole -e");
+        assert_eq!(resolve(Some("kitty")), "kitty");
+    }
+
+    #[test]
+    fn other_configured_terminals_are_used_as_written() {
+        assert_eq!(resolve(Some("wezterm start --")), "wezterm start --");
+        assert_eq!(resolve(Some("xterm -e")), "xterm -e");
+    }
+
+    #[test]
+    fn the_default_is_a_known_terminal() {
+        let resolved = resolve(None);
+        assert!(
+            KNOWN
+                .iter()
+                .any(|(program, flag)| resolved == with_flag(program, flag))
+        );
+    }
+
+    #[test]
+    fn commands_run_through_sh() {
+        assert_eq!(
+            command_line("xterm -e", "ssh 'a b'"),
+            "xterm -e sh -c 'ssh '\\''a b'\\'''"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::matching;
+use std::process::Command;
+
+/// Queries starting with this list tmux sessions and windows.
+pub const PREFIX: &str = "tmux ";
+
+/// A session, or one window of it.
+#[derive(Clone)]
+pub struct Target {
+    pub session: String,
+    /// The window's index and name.
+    pub window: Option<(u32, String)>,
+}
+
+impl Target {
+    /// What `tmux -t` takes: `session` or `session:index`.
+    pub fn target(&self) -> String {
+        match &self.window {
+            Some((index, _)) => format!("{}:{}", self.session, index),
+            None => self.session.clone(),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match &self.window {
+            Some((index, name)) => format!("{}:{} {}", self.session, index, name),
+            None => self.session.clone(),
+        }
+    }
+
+    pub fn usage_key(&self) -> String {
+        format!("tmux:{}", self.target())
+    }
+}
+
+const SESSION_FORMAT: &str = "#{session_name}";
+const WINDOW_FORMAT: &str = "#{session_name}\t#{window_index}\t#{window_name}";
+
+/// `list-sessions -F SESSION_FORMAT` output, one session per line.
+pub fn parse_sessions(output: &str) -> Vec<Target> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|session| Target {
+            session: session.to_string(),
+            window: None,
+        })
+        .collect()
+}
+
+/// `list-windows -a -F WINDOW_FORMAT` output. Window names may contain
+/// tabs, so only the first two separate fields.
+pub fn parse_windows(output: &str) -> Vec<Target> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let session = fields.next()?;
+            let index = fields.next()?.parse().ok()?;
+            let name = fields.next()?;
+            Some(Target {
+                session: session.to_string(),
+                window: Some((index, name.to_string())),
+            })
+        })
+        .collect()
+}
+
+fn run(args: &[&str]) -> Option<String> {
+    let output = Command::new("tmux").args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Every session followed by its windows, or None when tmux isn't
+/// installed or no server is running.
+pub fn list() -> Option<Vec<Target>> {
+    let sessions = parse_sessions(&run(&["list-sessions", "-F", SESSION_FORMAT])?);
+    let windows =
+        parse_windows(&run(&["list-windows", "-a", "-F", WINDOW_FORMAT]).unwrap_or_default());
+    let mut targets = Vec::new();
+    for session in sessions {
+        let session_windows: Vec<Target> = windows
+            .iter()
+            .filter(|window| window.session == session.session)
+            .cloned()
+            .collect();
+        targets.push(session);
+        targets.extend(session_windows);
+    }
+    Some(targets)
+}
+
+/// Sessions and windows matching `filter`, best first once
+/// `usage_boost` has added what it gives each. While the filter is empty
+/// they stay in tmux's order, each session above its windows.
+pub fn search(
+    targets: Vec<Target>,
+    filter: &str,
+    usage_boost: impl Fn(&Target) -> i64,
+) -> Vec<Target> {
+    if filter.trim().is_empty() {
+        return targets;
+    }
+    let mut scored: Vec<(i64, Target)> = targets
+        .into_iter()
+        .filter_map(|target| {
+            let score = matching::score(&target.label(), filter)? + usage_boost(&target);
+            Some((score, target))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.label().cmp(&b.1.label())));
+    scored.into_iter().map(|(_, target)| target).collect()
+}
+
+/// Whether hyperfind was started from inside tmux, where switching the
+/// current client beats opening another terminal.
+pub fn inside() -> bool {
+    std::env::var_os("TMUX").is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(targets: &[Target]) -> Vec<String> {
+        targets.iter().map(Target::label).collect()
+    }
+
+    #[test]
+    fn sessions_are_one_per_line() {
+        let sessions = parse_sessions("main\nwork\n\n");
+        assert_eq!(labels(&sessions), ["main", "work"]);
+        assert_eq!(sessions[1].target(), "work");
+        assert_eq!(sessions[1].usage_key(), "tmux:work");
+    }
+
+    #[test]
+    fn window_names_keep_their_tabs() {
+        let windows =
+            parse_windows("main\t0\tvim\nmain\t1\tlogs\ttail\nbroken line\nwork\tx\tbad\n");
+        assert_eq!(labels(&windows), ["main:0 vim", "main:1 logs\ttail"]);
+        assert_eq!(windows[1].target(), "main:1");
+        assert_eq!(windows[1].usage_key(), "tmux:main:1");
+    }
+
+    #[test]
+    fn usage_lifts_an_equal_match() {
+        let targets = parse_windows("main\t0\tshell\nmain\t1\tshell\n");
+        let boosted = search(targets.clone(), "shell", |target| {
+            if target.target() == "main:1" { 50 } else { 0 }
+        });
+        assert_eq!(labels(&boosted), ["main:1 shell", "main:0 shell"]);
+        assert_eq!(
+            labels(&search(targets, " ", |_| 0)),
+            ["main:0 shell", "main:1 shell"]
+        );
+    }
+}
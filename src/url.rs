@@ -0,0 +1,52 @@
+/// Every opened URL counts towards this one key, so typed URLs don't fill
+/// the usage file with an entry each.
+pub const USAGE_KEY: &str = "url-open";
+
+/// Endings that make a bare name a domain. Kept short so file names like
+/// `main.rs` or `notes.md` aren't taken for one.
+const TLDS: &[&str] = &[
+    "com", "org", "net", "io", "dev", "app", "edu", "gov", "info", "xyz", "me", "co", "uk", "de",
+    "fr", "nl", "eu", "jp", "ca", "au", "us", "ch", "se",
+];
+
+/// Schemes opened as typed; anything else needs its own handler and is
+/// more likely a typo.
+const SCHEMES: &[&str] = &["http://", "https://", "file://", "ftp://"];
+
+/// A dotted host name under a known TLD, or any one starting `www.`.
+fn is_domain(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    let labels: Vec<&str> = host.split('.').collect();
+    let valid = labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    let tld = labels.last().copied().unwrap_or_default();
+    valid && (TLDS.contains(&tld) || (labels[0] == "www" && labels.len() >= 3))
+}
+
+/// The URL to open for a query that looks like one: one with a known
+/// scheme, or a bare domain (with an optional port and path) that gets
+/// `https://` in front.
+pub fn parse(query: &str) -> Option<String> {
+    let query = query.trim();
+    if query.is_empty() || query.contains(char::is_whitespace) {
+        return None;
+    }
+    let lowercase = query.to_ascii_lowercase();
+    if let Some(scheme) = SCHEMES.iter().find(|scheme| lowercase.starts_with(*scheme)) {
+        return (query.len() > scheme.len()).then(|| query.to_string());
+    }
+    let host_end = query.find(['/', '?', '#']).unwrap_or(query.len());
+    let host = &query[..host_end];
+    let (host, port) = match host.split_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (host, None),
+    };
+    if port.is_some_and(|port| port.is_empty() || !port.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    is_domain(host).then(|| format!("https://{}", query))
+}
@@ -0,0 +1,678 @@
+use crate::config::UsageBackend;
+use crate::{
+    USAGE_FILES, UsageMap, UsageTrash, load_usage_file, migrate_usage_files, now_unix, purge_trash,
+    record_failure, record_usage, usage_dir, usage_file_contents, with_suffix, write_atomic,
+};
+use std::fs;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+
+/// Where launch history is persisted. The in-memory `UsageMap` is always
+/// updated by the caller; a store only has to make the change durable.
+pub trait UsageStore {
+    fn load(&self) -> UsageMap;
+    /// Usage removed by a reset or prune that can still be restored.
+    fn load_trash(&self) -> UsageTrash;
+    /// Records one launch of `key`.
+    fn record(&mut self, key: &str) -> io::Result<()>;
+    /// Records one failed attempt to launch `key`.
+    fn record_failure(&mut self, key: &str) -> io::Result<()>;
+    /// Saves `usage` exactly as given, over whatever is stored.
+    fn replace(&mut self, usage: &UsageMap) -> io::Result<()>;
+    /// Re-reads the stored usage and trash and applies `update` to them in
+    /// one step, so removing an entry doesn't also drop what another
+    /// instance saved since this one loaded. Expired trash is purged.
+    fn update(&mut self, update: &mut dyn FnMut(&mut UsageMap, &mut UsageTrash)) -> io::Result<()>;
+    fn location(&self) -> PathBuf;
+}
+
+pub fn open(backend: UsageBackend) -> Box<dyn UsageStore> {
+    migrate_usage_files();
+    let dir = usage_dir();
+    let mut store = open_backend(backend, dir.clone());
+    adopt_legacy_trash(&mut *store, &dir);
+    store
+}
+
+fn open_backend(backend: UsageBackend, dir: PathBuf) -> Box<dyn UsageStore> {
+    match backend {
+        UsageBackend::Json => Box::new(JsonStore::new(dir)),
+        #[cfg(feature = "sqlite")]
+        UsageBackend::Sqlite => match SqliteStore::open(dir.clone()) {
+            Ok(store) => Box::new(store),
+            Err(err) => {
+                eprintln!("Failed to open usage database, using JSON: {}", err);
+                Box::new(JsonStore::new(dir))
+            }
+        },
+        #[cfg(not(feature = "sqlite"))]
+        UsageBackend::Sqlite => {
+            eprintln!("hyperfind was built without the sqlite feature, using JSON");
+            Box::new(JsonStore::new(dir))
+        }
+    }
+}
+
+/// Moves the trash older versions kept in `usage-trash.json` into the
+/// store, leaving the old file renamed beside it.
+fn adopt_legacy_trash(store: &mut dyn UsageStore, dir: &Path) {
+    let path = dir.join(USAGE_FILES[1]);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return;
+    };
+
+    let legacy: UsageTrash = serde_json::from_str(&contents).unwrap_or_default();
+    let adopted = store
+        .update(&mut |_, trash| {
+            for (key, trashed) in &legacy {
+                trash.entry(key.clone()).or_insert_with(|| trashed.clone());
+            }
+        })
+        .and_then(|()| fs::rename(&path, with_suffix(&path, ".migrated")));
+    if let Err(err) = adopted {
+        eprintln!(
+            "Failed to move {} into the usage data: {}",
+            path.display(),
+            err
+        );
+    }
+}
+
+/// Takes an exclusive advisory lock shared by every instance; it is released
+/// when the returned file is dropped. The lock lives in its own file since
+/// `usage.json` is replaced by rename on every save.
+fn lock_usage(dir: &Path) -> io::Result<fs::File> {
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join("usage.lock"))?;
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(file)
+}
+
+/// The whole map in `usage.json`, rewritten on every change.
+pub struct JsonStore {
+    dir: PathBuf,
+}
+
+impl JsonStore {
+    /// A store in `dir`, which is created on the first save.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.join(USAGE_FILES[0])
+    }
+
+    /// Re-reads the file and applies `update` to it under the lock, so
+    /// concurrent instances never save over each other's changes.
+    fn update_file(&self, update: impl FnOnce(&mut UsageMap, &mut UsageTrash)) -> io::Result<()> {
+        let path = self.path();
+        fs::create_dir_all(&self.dir)?;
+
+        let _lock = match lock_usage(&self.dir) {
+            Ok(lock) => Some(lock),
+            Err(err) => {
+                eprintln!("Failed to lock usage data, saving anyway: {}", err);
+                None
+            }
+        };
+
+        let mut stored = load_usage_file(&path);
+        update(&mut stored.entries, &mut stored.trash);
+        purge_trash(&mut stored.trash, now_unix());
+        let payload =
+            usage_file_contents(&stored.entries, &stored.trash, false).map_err(io::Error::other)?;
+        write_atomic(&path, payload.as_bytes())
+    }
+}
+
+impl UsageStore for JsonStore {
+    fn load(&self) -> UsageMap {
+        load_usage_file(&self.path()).entries
+    }
+
+    fn load_trash(&self) -> UsageTrash {
+        load_usage_file(&self.path()).trash
+    }
+
+    fn record(&mut self, key: &str) -> io::Result<()> {
+        self.update_file(|usage, _| record_usage(key, usage, now_unix()))
+    }
+
+    fn record_failure(&mut self, key: &str) -> io::Result<()> {
+        self.update_file(|usage, _| record_failure(key, usage))
+    }
+
+    fn replace(&mut self, usage: &UsageMap) -> io::Result<()> {
+        self.update_file(|on_disk, _| *on_disk = usage.clone())
+    }
+
+    fn update(&mut self, update: &mut dyn FnMut(&mut UsageMap, &mut UsageTrash)) -> io::Result<()> {
+        self.update_file(update)
+    }
+
+    fn location(&self) -> PathBuf {
+        self.path()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::UsageStore;
+    use crate::time_bin;
+    use crate::with_suffix;
+    use crate::{
+        DAILY_DAYS, DAY_SECS, DUPLICATE_LAUNCH_SECS, TrashedUsage, USAGE_FILES, UsageEntry,
+        UsageMap, UsageTrash, load_usage_file, local_date, now_unix, purge_trash,
+    };
+    use rusqlite::{Connection, params};
+    use std::fs;
+    use std::io;
+    use std::path::PathBuf;
+
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS usage (
+            key TEXT PRIMARY KEY,
+            count INTEGER NOT NULL,
+            last_used INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS daily (
+            key TEXT NOT NULL,
+            date TEXT NOT NULL,
+            count INTEGER NOT NULL,
+            PRIMARY KEY (key, date)
+        );
+        CREATE TABLE IF NOT EXISTS time_bins (
+            key TEXT NOT NULL,
+            bin INTEGER NOT NULL,
+            count INTEGER NOT NULL,
+            PRIMARY KEY (key, bin)
+        );
+        CREATE TABLE IF NOT EXISTS failures (
+            key TEXT PRIMARY KEY,
+            fail_count INTEGER NOT NULL,
+            last_failed INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS trash (
+            key TEXT PRIMARY KEY,
+            entry TEXT NOT NULL,
+            deleted_at INTEGER NOT NULL
+        );
+    ";
+
+    fn to_io(err: rusqlite::Error) -> io::Error {
+        io::Error::other(err)
+    }
+
+    /// Usage in an SQLite database, updated a row at a time.
+    pub struct SqliteStore {
+        connection: Connection,
+        path: PathBuf,
+    }
+
+    impl SqliteStore {
+        /// Opens the database in `dir`, creating it from the `usage.json`
+        /// there the first time.
+        pub fn open(dir: PathBuf) -> io::Result<Self> {
+            fs::create_dir_all(&dir)?;
+            let path = dir.join("usage.sqlite3");
+            let is_new = !path.exists();
+            let connection = Connection::open(&path).map_err(to_io)?;
+            connection.execute_batch(SCHEMA).map_err(to_io)?;
+            let mut store = Self { connection, path };
+
+            let json_path = dir.join(USAGE_FILES[0]);
+            if is_new && json_path.exists() {
+                let stored = load_usage_file(&json_path);
+                store.update(&mut |usage, trash| {
+                    usage.clone_from(&stored.entries);
+                    trash.clone_from(&stored.trash);
+                })?;
+                let migrated = with_suffix(&json_path, ".migrated");
+                fs::rename(&json_path, &migrated)?;
+                eprintln!(
+                    "Moved usage data into {}, the old file is {}",
+                    store.path.display(),
+                    migrated.display()
+                );
+            }
+            Ok(store)
+        }
+
+        fn write(&mut self, usage: &UsageMap) -> rusqlite::Result<()> {
+            let transaction = self.connection.transaction()?;
+            write(&transaction, usage)?;
+            transaction.commit()
+        }
+    }
+
+    /// Replaces everything stored with `usage`.
+    fn write(transaction: &Connection, usage: &UsageMap) -> rusqlite::Result<()> {
+        transaction.execute("DELETE FROM usage", [])?;
+        transaction.execute("DELETE FROM daily", [])?;
+        transaction.execute("DELETE FROM time_bins", [])?;
+        transaction.execute("DELETE FROM failures", [])?;
+        for (key, entry) in usage {
+            transaction.execute(
+                "INSERT INTO usage (key, count, last_used) VALUES (?1, ?2, ?3)",
+                params![key, entry.count as i64, entry.last_used as i64],
+            )?;
+            for (bin, count) in entry.time_bins.iter().enumerate() {
+                if *count == 0 {
+                    continue;
+                }
+                transaction.execute(
+                    "INSERT INTO time_bins (key, bin, count) VALUES (?1, ?2, ?3)",
+                    params![key, bin as i64, *count as i64],
+                )?;
+            }
+            if entry.fail_count > 0 {
+                transaction.execute(
+                    "INSERT INTO failures (key, fail_count, last_failed) VALUES (?1, ?2, ?3)",
+                    params![key, entry.fail_count as i64, entry.last_failed as i64],
+                )?;
+            }
+            for (date, count) in &entry.daily {
+                transaction.execute(
+                    "INSERT INTO daily (key, date, count) VALUES (?1, ?2, ?3)",
+                    params![key, date, *count as i64],
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read(connection: &Connection) -> rusqlite::Result<UsageMap> {
+        let mut usage = UsageMap::new();
+        let mut statement = connection.prepare("SELECT key, count, last_used FROM usage")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (key, count, last_used) = row?;
+            usage.insert(
+                key,
+                UsageEntry {
+                    count: count as u64,
+                    last_used: last_used as u64,
+                    ..UsageEntry::default()
+                },
+            );
+        }
+
+        let mut statement = connection.prepare("SELECT key, date, count FROM daily")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (key, date, count) = row?;
+            if let Some(entry) = usage.get_mut(&key) {
+                entry.daily.insert(date, count as u64);
+            }
+        }
+
+        let mut statement = connection.prepare("SELECT key, bin, count FROM time_bins")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (key, bin, count) = row?;
+            if let Some(slot) = usage
+                .get_mut(&key)
+                .and_then(|entry| entry.time_bins.get_mut(bin as usize))
+            {
+                *slot = count as u64;
+            }
+        }
+
+        let mut statement =
+            connection.prepare("SELECT key, fail_count, last_failed FROM failures")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (key, fail_count, last_failed) = row?;
+            let entry = usage.entry(key).or_default();
+            entry.fail_count = fail_count as u64;
+            entry.last_failed = last_failed as u64;
+        }
+        Ok(usage)
+    }
+
+    /// The trash, each entry kept as the JSON `usage.json` would hold.
+    fn read_trash(connection: &Connection) -> io::Result<UsageTrash> {
+        let mut trash = UsageTrash::new();
+        let mut statement = connection
+            .prepare("SELECT key, entry, deleted_at FROM trash")
+            .map_err(to_io)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })
+            .map_err(to_io)?;
+        for row in rows {
+            let (key, entry, deleted_at) = row.map_err(to_io)?;
+            trash.insert(
+                key,
+                TrashedUsage {
+                    entry: serde_json::from_str(&entry)?,
+                    deleted_at: deleted_at as u64,
+                },
+            );
+        }
+        Ok(trash)
+    }
+
+    fn write_trash(connection: &Connection, trash: &UsageTrash) -> io::Result<()> {
+        connection.execute("DELETE FROM trash", []).map_err(to_io)?;
+        for (key, trashed) in trash {
+            connection
+                .execute(
+                    "INSERT INTO trash (key, entry, deleted_at) VALUES (?1, ?2, ?3)",
+                    params![
+                        key,
+                        serde_json::to_string(&trashed.entry)?,
+                        trashed.deleted_at as i64
+                    ],
+                )
+                .map_err(to_io)?;
+        }
+        Ok(())
+    }
+
+    impl UsageStore for SqliteStore {
+        fn load(&self) -> UsageMap {
+            read(&self.connection).unwrap_or_else(|err| {
+                eprintln!("Failed to read {}: {}", self.path.display(), err);
+                UsageMap::new()
+            })
+        }
+
+        fn load_trash(&self) -> UsageTrash {
+            read_trash(&self.connection).unwrap_or_else(|err| {
+                eprintln!("Failed to read {}: {}", self.path.display(), err);
+                UsageTrash::new()
+            })
+        }
+
+        fn record(&mut self, key: &str) -> io::Result<()> {
+            let now = now_unix();
+            let oldest = local_date(now.saturating_sub(DAILY_DAYS * DAY_SECS));
+            let transaction = self.connection.transaction().map_err(to_io)?;
+            let duplicate = transaction
+                .query_row(
+                    "SELECT count(*) FROM usage WHERE key = ?1 AND last_used > ?2",
+                    params![key, now.saturating_sub(DUPLICATE_LAUNCH_SECS) as i64],
+                    |row| row.get::<_, i64>(0),
+                )
+                .map_err(to_io)?
+                > 0;
+            transaction
+                .execute(
+                    "INSERT INTO usage (key, count, last_used) VALUES (?1, 1, ?2)
+                     ON CONFLICT (key) DO UPDATE SET
+                         count = count + ?3,
+                         last_used = excluded.last_used",
+                    params![key, now as i64, i64::from(!duplicate)],
+                )
+                .map_err(to_io)?;
+            if duplicate {
+                return transaction.commit().map_err(to_io);
+            }
+            transaction
+                .execute(
+                    "INSERT INTO daily (key, date, count) VALUES (?1, ?2, 1)
+                     ON CONFLICT (key, date) DO UPDATE SET count = count + 1",
+                    params![key, local_date(now)],
+                )
+                .map_err(to_io)?;
+            transaction
+                .execute(
+                    "INSERT INTO time_bins (key, bin, count) VALUES (?1, ?2, 1)
+                     ON CONFLICT (key, bin) DO UPDATE SET count = count + 1",
+                    params![key, time_bin(now) as i64],
+                )
+                .map_err(to_io)?;
+            transaction
+                .execute("DELETE FROM daily WHERE date <= ?1", params![oldest])
+                .map_err(to_io)?;
+            transaction.commit().map_err(to_io)
+        }
+
+        fn record_failure(&mut self, key: &str) -> io::Result<()> {
+            self.connection
+                .execute(
+                    "INSERT INTO failures (key, fail_count, last_failed) VALUES (?1, 1, ?2)
+                     ON CONFLICT (key) DO UPDATE SET
+                         fail_count = fail_count + 1,
+                         last_failed = excluded.last_failed",
+                    params![key, now_unix() as i64],
+                )
+                .map(|_| ())
+                .map_err(to_io)
+        }
+
+        fn replace(&mut self, usage: &UsageMap) -> io::Result<()> {
+            self.write(usage).map_err(to_io)
+        }
+
+        fn update(
+            &mut self,
+            update: &mut dyn FnMut(&mut UsageMap, &mut UsageTrash),
+        ) -> io::Result<()> {
+            let transaction = self.connection.transaction().map_err(to_io)?;
+            let mut usage = read(&transaction).map_err(to_io)?;
+            let mut trash = read_trash(&transaction)?;
+            update(&mut usage, &mut trash);
+            purge_trash(&mut trash, now_unix());
+            write(&transaction, &usage).map_err(to_io)?;
+            write_trash(&transaction, &trash)?;
+            transaction.commit().map_err(to_io)
+        }
+
+        fn location(&self) -> PathBuf {
+            self.path.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+    use crate::{TrashedUsage, UsageEntry, trash_usage};
+
+    fn entry(count: u64, last_used: u64) -> UsageEntry {
+        UsageEntry {
+            count,
+            last_used,
+            ..UsageEntry::default()
+        }
+    }
+
+    /// A map with every kind of field set, to see it all survive a store.
+    /// The day is today's so a launch recorded later doesn't prune it.
+    fn sample_usage() -> UsageMap {
+        let now = now_unix();
+        let mut full = entry(4, now - 60);
+        full.time_bins[2] = 4;
+        full.daily.insert(crate::local_date(now), 4);
+        full.fail_count = 2;
+        full.last_failed = now - 120;
+        UsageMap::from([
+            ("full.desktop".to_string(), full),
+            ("bare.desktop".to_string(), entry(1, 10)),
+        ])
+    }
+
+    fn round_trips(store: &mut dyn UsageStore) {
+        let usage = sample_usage();
+        store.replace(&usage).unwrap();
+        assert_eq!(store.load(), usage);
+
+        store.record("new.desktop").unwrap();
+        store.record_failure("bare.desktop").unwrap();
+        let loaded = store.load();
+        assert_eq!(loaded["new.desktop"].count, 1);
+        assert_eq!(loaded["bare.desktop"].fail_count, 1);
+        assert_eq!(loaded["full.desktop"], usage["full.desktop"]);
+
+        let now = now_unix();
+        store
+            .update(&mut |usage, trash| {
+                trash_usage("new.desktop", usage, trash, now);
+            })
+            .unwrap();
+        let loaded = store.load();
+        assert!(!loaded.contains_key("new.desktop"));
+        assert_eq!(loaded["full.desktop"], usage["full.desktop"]);
+        let trash = store.load_trash();
+        assert_eq!(trash["new.desktop"].entry.count, 1);
+        assert_eq!(trash["new.desktop"].deleted_at, now);
+        // Anything past the grace period goes on the next write.
+        store
+            .update(&mut |_, trash| {
+                trash.get_mut("new.desktop").unwrap().deleted_at = 0;
+            })
+            .unwrap();
+        assert!(store.load_trash().is_empty());
+
+        let kept = UsageMap::from([("bare.desktop".to_string(), entry(1, 10))]);
+        store.replace(&kept).unwrap();
+        assert_eq!(store.load(), kept);
+    }
+
+    #[test]
+    fn json_store_round_trips() {
+        let dir = scratch_dir("json-store");
+        let mut store = JsonStore::new(dir.clone());
+        round_trips(&mut store);
+        assert_eq!(store.location(), dir.join("usage.json"));
+    }
+
+    #[test]
+    fn removing_one_entry_keeps_another_instances_launches() {
+        let dir = scratch_dir("json-store-remove");
+        let mut store = JsonStore::new(dir.clone());
+        store.record("ours.desktop").unwrap();
+        let stale = store.load();
+        JsonStore::new(dir).record("theirs.desktop").unwrap();
+
+        assert!(!stale.contains_key("theirs.desktop"));
+        store
+            .update(&mut |usage, _| {
+                usage.remove("ours.desktop");
+            })
+            .unwrap();
+        let loaded = store.load();
+        assert!(!loaded.contains_key("ours.desktop"));
+        assert_eq!(loaded["theirs.desktop"].count, 1);
+    }
+
+    #[test]
+    fn version_2_usage_gains_a_trash_on_the_next_write() {
+        let dir = scratch_dir("json-store-v2");
+        fs::write(
+            dir.join("usage.json"),
+            r#"{"version": 2, "entries": {"a.desktop": {"count": 3, "last_used": 20}}}"#,
+        )
+        .unwrap();
+        let mut store = JsonStore::new(dir.clone());
+        assert!(store.load_trash().is_empty());
+
+        let now = now_unix();
+        store
+            .update(&mut |usage, trash| {
+                trash_usage("a.desktop", usage, trash, now);
+            })
+            .unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("usage.json")).unwrap()).unwrap();
+        assert_eq!(written["version"], 3);
+        assert_eq!(written["trash"]["a.desktop"]["entry"]["count"], 3);
+        assert_eq!(store.load_trash()["a.desktop"].entry.count, 3);
+    }
+
+    #[test]
+    fn the_old_trash_file_is_moved_into_the_store() {
+        let dir = scratch_dir("json-store-legacy-trash");
+        let now = now_unix();
+        let legacy = UsageTrash::from([(
+            "a.desktop".to_string(),
+            TrashedUsage {
+                entry: entry(2, 10),
+                deleted_at: now,
+            },
+        )]);
+        let path = dir.join("usage-trash.json");
+        fs::write(&path, serde_json::to_string(&legacy).unwrap()).unwrap();
+
+        let mut store = JsonStore::new(dir.clone());
+        adopt_legacy_trash(&mut store, &dir);
+        assert_eq!(store.load_trash(), legacy);
+        assert!(!path.exists());
+        assert!(with_suffix(&path, ".migrated").exists());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_store_round_trips() {
+        let dir = scratch_dir("sqlite-store");
+        let mut store = SqliteStore::open(dir.clone()).unwrap();
+        round_trips(&mut store);
+        drop(store);
+        // A second open finds the data already there.
+        let reopened = SqliteStore::open(dir.clone()).unwrap();
+        assert_eq!(reopened.load().len(), 1);
+        assert_eq!(reopened.location(), dir.join("usage.sqlite3"));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_store_takes_over_usage_json_on_first_open() {
+        let dir = scratch_dir("sqlite-migrate");
+        let mut usage = sample_usage();
+        let now = now_unix();
+        let mut json = JsonStore::new(dir.clone());
+        json.replace(&usage).unwrap();
+        json.update(&mut |usage, trash| {
+            trash_usage("bare.desktop", usage, trash, now);
+        })
+        .unwrap();
+        let store = SqliteStore::open(dir.clone()).unwrap();
+        let bare = usage.remove("bare.desktop").unwrap();
+        assert_eq!(store.load(), usage);
+        assert_eq!(store.load_trash()["bare.desktop"].entry, bare);
+        assert!(!dir.join("usage.json").exists());
+        assert!(dir.join("usage.json.migrated").exists());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{app_cache, desktop_dirs};
+use gio::prelude::*;
+use gtk::glib;
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Package upgrades touch hundreds of desktop files at once; wait for the
+/// burst to settle before reloading.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+fn push_unique(dirs: &mut Vec<PathBuf>, dir: PathBuf) {
+    if !dirs.contains(&dir) {
+        dirs.push(dir);
+    }
+}
+
+fn collect_dirs(dir: &Path, dirs: &mut Vec<PathBuf>) {
+    push_unique(dirs, dir.to_path_buf());
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_dirs(&entry.path(), dirs);
+        } else if file_type.is_symlink()
+            && let Ok(target) = fs::canonicalize(entry.path())
+            && let Some(parent) = target.parent()
+        {
+            // A directory monitor doesn't see edits to a symlink's target,
+            // so watch wherever the targets live as well.
+            push_unique(dirs, parent.to_path_buf());
+        }
+    }
+}
+
+/// Every directory whose changes can affect the app list: the resolved
+/// application dirs, their subdirectories, and the directories symlinked
+/// entries point into.
+pub fn watched_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for dir in app_cache::application_dirs() {
+        collect_dirs(&dir, &mut dirs);
+    }
+    dirs.extend(desktop_dirs::dirs());
+    dirs
+}
+
+/// Watches each directory with its own monitor and calls `on_change` once
+/// per burst of events. The monitors stop when the returned handles drop.
+pub fn watch(dirs: &[PathBuf], on_change: impl Fn() + 'static) -> Vec<gio::FileMonitor> {
+    let on_change = Rc::new(on_change);
+    let pending: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+
+    dirs.iter()
+        .filter_map(|dir| {
+            let monitor = gio::File::for_path(dir)
+                .monitor_directory(
+                    gio::FileMonitorFlags::WATCH_MOVES,
+                    Option::<&gio::Cancellable>::None,
+                )
+                .ok()?;
+
+            let on_change = Rc::clone(&on_change);
+            let pending = Rc::clone(&pending);
+            monitor.connect_changed(move |_, _, _, _| {
+                if let Some(source) = pending.borrow_mut().take() {
+                    source.remove();
+                }
+                let on_change = Rc::clone(&on_change);
+                let pending_for_timeout = Rc::clone(&pending);
+                let source = glib::timeout_add_local_once(DEBOUNCE, move || {
+                    pending_for_timeout.borrow_mut().take();
+                    on_change();
+                });
+                pending.borrow_mut().replace(source);
+            });
+            Some(monitor)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::os::unix::fs::symlink;
+    use std::time::Instant;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("hyperfind-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn collect_dirs_follows_subdirectories_and_symlink_targets() {
+        let root = scratch_dir("collect-dirs");
+        let apps = root.join("applications");
+        let store = root.join("store");
+        fs::create_dir_all(apps.join("kde")).unwrap();
+        fs::create_dir_all(&store).unwrap();
+        fs::write(store.join("a.desktop"), "").unwrap();
+        symlink(store.join("a.desktop"), apps.join("a.desktop")).unwrap();
+        symlink(store.join("a.desktop"), apps.join("kde").join("a.desktop")).unwrap();
+
+        let mut dirs = Vec::new();
+        collect_dirs(&apps, &mut dirs);
+        dirs.sort();
+        let store = fs::canonicalize(&store).unwrap();
+        let kde = apps.join("kde");
+        assert_eq!(dirs, vec![apps, kde, store]);
+    }
+
+    #[test]
+    fn a_burst_of_changes_is_reported_once() {
+        let context = glib::MainContext::default();
+        let _acquired = context.acquire().unwrap();
+        let dir = scratch_dir("watch-burst");
+        let changes = Rc::new(Cell::new(0));
+        let changes_for_watch = Rc::clone(&changes);
+        let _monitors = watch(std::slice::from_ref(&dir), move || {
+            changes_for_watch.set(changes_for_watch.get() + 1)
+        });
+
+        for name in ["a.desktop", "b.desktop", "c.desktop"] {
+            fs::write(dir.join(name), "[Desktop Entry]\n").unwrap();
+        }
+        let deadline = Instant::now() + DEBOUNCE * 4;
+        while Instant::now() < deadline {
+            if !context.iteration(false) {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        assert_eq!(changes.get(), 1);
+    }
+
+    /// glib reads the XDG variables once per process, so the fixture is
+    /// checked by running this test again in a child with them set.
+    const FIXTURE_VAR: &str = "HYPERFIND_TEST_DATA_DIRS_FIXTURE";
+
+    fn desktop_entry(name: &str) -> String {
+        format!(
+            "[Desktop Entry]\nType=Application\nName={}\nExec=true\n",
+            name
+        )
+    }
+
+    #[test]
+    fn data_dirs_with_symlinks_and_overrides() {
+        let Some(root) = std::env::var_os(FIXTURE_VAR).map(PathBuf::from) else {
+            let root = scratch_dir("data-dirs-fixture");
+            let home = root.join("home");
+            let system = root.join("system");
+            let store = root.join("store");
+            fs::create_dir_all(home.join("applications")).unwrap();
+            fs::create_dir_all(system.join("applications")).unwrap();
+            fs::create_dir_all(&store).unwrap();
+            fs::write(store.join("linked.desktop"), desktop_entry("Linked")).unwrap();
+            fs::write(store.join("relinked.desktop"), desktop_entry("Relinked")).unwrap();
+            symlink(
+                store.join("linked.desktop"),
+                system.join("applications").join("linked.desktop"),
+            )
+            .unwrap();
+            fs::write(
+                system.join("applications").join("shadowed.desktop"),
+                desktop_entry("System"),
+            )
+            .unwrap();
+            fs::write(
+                home.join("applications").join("shadowed.desktop"),
+                desktop_entry("Override"),
+            )
+            .unwrap();
+            // A second data dir leading to the same tree is only read once.
+            symlink(&system, root.join("system-link")).unwrap();
+
+            let data_dirs =
+                std::env::join_paths([system.clone(), root.join("system-link")]).unwrap();
+            let output = std::process::Command::new(std::env::current_exe().unwrap())
+                .args([
+                    "--exact",
+                    "watch::tests::data_dirs_with_symlinks_and_overrides",
+                ])
+                .env(FIXTURE_VAR, &root)
+                .env("HOME", &root)
+                .env("XDG_DATA_HOME", &home)
+                .env("XDG_DATA_DIRS", data_dirs)
+                .env("XDG_CACHE_HOME", root.join("cache"))
+                .output()
+                .unwrap();
+            assert!(
+                output.status.success(),
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            return;
+        };
+
+        let home = fs::canonicalize(root.join("home")).unwrap();
+        let system = fs::canonicalize(root.join("system")).unwrap();
+        let store = fs::canonicalize(root.join("store")).unwrap();
+        assert_eq!(
+            app_cache::application_dirs(),
+            [home.join("applications"), system.join("applications")]
+        );
+        assert!(watched_dirs().contains(&store));
+
+        let names: Vec<(String, String)> = gio::AppInfo::all()
+            .iter()
+            .filter_map(|info| Some((info.id()?.to_string(), info.name().to_string())))
+            .collect();
+        assert!(names.contains(&("linked.desktop".to_string(), "Linked".to_string())));
+        assert!(names.contains(&("shadowed.desktop".to_string(), "Override".to_string())));
+        assert!(!names.iter().any(|(_, name)| name == "System"));
+
+        let link = system.join("applications").join("linked.desktop");
+        let before = app_cache::desktop_dirs_hash();
+        assert_eq!(app_cache::desktop_dirs_hash(), before);
+        fs::remove_file(&link).unwrap();
+        symlink(store.join("relinked.desktop"), &link).unwrap();
+        let relinked = app_cache::desktop_dirs_hash();
+        assert_ne!(relinked, before);
+
+        let shadowing = fs::File::options()
+            .append(true)
+            .open(home.join("applications").join("shadowed.desktop"))
+            .unwrap();
+        let later = fs::metadata(&link).unwrap().modified().unwrap() + Duration::from_secs(60);
+        shadowing.set_modified(later).unwrap();
+        assert_ne!(app_cache::desktop_dirs_hash(), relinked);
+    }
+}